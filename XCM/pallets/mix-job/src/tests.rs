@@ -1,5 +1,13 @@
-use crate::{mock::*, Error, Event, JobStatus, Jobs, LastJobForElection, NextJobId};
-use frame::testing_prelude::*;
+use crate::{
+	migrations, mock::*, ArchiveQueue, ArchiveQueueRange, Error, Event, HoldReason, JobErrorCode,
+	JobKind, JobStatus, Jobs, LastJobForElection, NextJobId, Orchestrators, ParaCallbacks,
+	ParaQuota, ParaUsageOf, ResultUri, TimeoutSchedule,
+};
+use frame::{
+	deps::frame_support::{storage::unhashed, traits::UncheckedOnRuntimeUpgrade},
+	testing_prelude::*,
+};
+use xcm::latest::{Instruction, Junction, Location};
 
 #[test]
 fn submit_job_creates_job_and_increments_counter() {
@@ -13,7 +21,7 @@ fn submit_job_creates_job_and_increments_counter() {
 		assert_eq!(LastJobForElection::<Test>::iter().count(), 0);
 
 		// Act: call submit_job(ALICE, 47)
-		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(1), 47));
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(1), JobKind::Election(47)));
 
 		// Assert: NextJobId == 1
 		assert_eq!(NextJobId::<Test>::get(), 1);
@@ -22,7 +30,7 @@ fn submit_job_creates_job_and_increments_counter() {
 		let job = Jobs::<Test>::get(0).unwrap();
 		assert_eq!(job.job_id, 0);
 		assert_eq!(job.requester, 1);
-		assert_eq!(job.election_id, 47);
+		assert_eq!(job.kind, JobKind::Election(47));
 		assert_eq!(job.status, JobStatus::Pending);
 		assert_eq!(job.error_code, None);
 
@@ -33,8 +41,9 @@ fn submit_job_creates_job_and_increments_counter() {
 		System::assert_last_event(
 			Event::JobSubmitted {
 				job_id: 0,
-				election_id: 47,
+				kind: JobKind::Election(47),
 				requester: 1,
+				source_para: None,
 			}
 			.into(),
 		);
@@ -46,18 +55,31 @@ fn submit_job_respects_max_jobs_limit() {
 	new_test_ext().execute_with(|| {
 		// Arrange: create MaxJobs jobs (MaxJobs = 10 in mock)
 		for i in 0..10 {
-			assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(1), i as u32));
+			assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(1), JobKind::Election(i as u32)));
 		}
 		assert_eq!(Jobs::<Test>::iter().count(), 10);
 
 		// Act: try to submit one more job
 		assert_noop!(
-			MixJob::submit_job(RuntimeOrigin::signed(1), 100),
+			MixJob::submit_job(RuntimeOrigin::signed(1), JobKind::Election(100)),
 			Error::<Test>::JobLimitReached
 		);
 	});
 }
 
+/// Complete job `job_id` as `orchestrator` with placeholder results.
+fn complete(orchestrator: u64, job_id: u64) -> DispatchResult {
+	let uri: ResultUri = b"ipfs://result".to_vec().try_into().unwrap();
+	MixJob::complete_job(
+		RuntimeOrigin::signed(orchestrator),
+		job_id,
+		uri,
+		H256::repeat_byte(1),
+		H256::repeat_byte(2),
+		H256::repeat_byte(3),
+	)
+}
+
 #[test]
 fn update_job_status_allows_valid_transitions() {
 	new_test_ext().execute_with(|| {
@@ -65,43 +87,38 @@ fn update_job_status_allows_valid_transitions() {
 		System::set_block_number(1);
 
 		// Arrange: create a job in Pending
-		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(1), 47));
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(1), JobKind::Election(47)));
 		assert_eq!(Jobs::<Test>::get(0).unwrap().status, JobStatus::Pending);
 
-		// Act: update to Running
-		assert_ok!(MixJob::update_job_status(
-			RuntimeOrigin::signed(1),
-			0,
-			JobStatus::Running,
-			None
-		));
+		// Act: claim it, which moves it to Running
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(1), 0));
 
 		// Assert: status changed and event fired
 		assert_eq!(Jobs::<Test>::get(0).unwrap().status, JobStatus::Running);
 		System::assert_last_event(
-			Event::JobStatusUpdated {
+			Event::JobClaimed {
 				job_id: 0,
-				old_status: JobStatus::Pending as u8,
-				new_status: JobStatus::Running as u8,
+				orchestrator: ORCHESTRATOR,
+				lease_expires_at: 1 + LEASE_DURATION,
 			}
 			.into(),
 		);
 
-		// Act: update to Completed
-		assert_ok!(MixJob::update_job_status(
-			RuntimeOrigin::signed(1),
-			0,
-			JobStatus::Completed,
-			None
-		));
+		// Act: Completed is only reachable through complete_job
+		assert_noop!(
+			MixJob::update_job_status(RuntimeOrigin::signed(1), 0, JobStatus::Completed, None),
+			Error::<Test>::CompletionRequiresResults
+		);
+		assert_ok!(complete(1, 0));
 
 		// Assert: final status and event
 		assert_eq!(Jobs::<Test>::get(0).unwrap().status, JobStatus::Completed);
-		System::assert_last_event(
+		System::assert_has_event(
 			Event::JobStatusUpdated {
 				job_id: 0,
 				old_status: JobStatus::Running as u8,
 				new_status: JobStatus::Completed as u8,
+				orchestrator: ORCHESTRATOR,
 			}
 			.into(),
 		);
@@ -112,13 +129,9 @@ fn update_job_status_allows_valid_transitions() {
 fn update_job_status_blocks_invalid_transitions() {
 	new_test_ext().execute_with(|| {
 		// Arrange: create a job and set it to Completed
-		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(1), 47));
-		assert_ok!(MixJob::update_job_status(
-			RuntimeOrigin::signed(1),
-			0,
-			JobStatus::Completed,
-			None
-		));
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(1), JobKind::Election(47)));
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+		assert_ok!(complete(1, 0));
 		assert_eq!(Jobs::<Test>::get(0).unwrap().status, JobStatus::Completed);
 
 		// Act: try to move back to Running (invalid transition)
@@ -130,3 +143,1013 @@ fn update_job_status_blocks_invalid_transitions() {
 		// Assert: status remains Completed
 		assert_eq!(Jobs::<Test>::get(0).unwrap().status, JobStatus::Completed);
 	});
+}
+
+#[test]
+fn update_job_status_rejects_non_orchestrator() {
+	new_test_ext().execute_with(|| {
+		// Arrange: a pending job
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+
+		// Act: an account outside the orchestrator set tries to complete it
+		assert_noop!(
+			MixJob::update_job_status(RuntimeOrigin::signed(2), 0, JobStatus::Completed, None),
+			Error::<Test>::NotOrchestrator
+		);
+
+		// Assert: job untouched
+		let job = Jobs::<Test>::get(0).unwrap();
+		assert_eq!(job.status, JobStatus::Pending);
+		assert_eq!(job.last_updated_by, None);
+	});
+}
+
+#[test]
+fn update_job_status_records_orchestrator() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+		assert_ok!(MixJob::update_job_status(
+			RuntimeOrigin::signed(ORCHESTRATOR),
+			0,
+			JobStatus::Running,
+			None
+		));
+
+		assert_eq!(Jobs::<Test>::get(0).unwrap().last_updated_by, Some(ORCHESTRATOR));
+	});
+}
+
+#[test]
+fn orchestrator_set_is_managed_by_update_origin() {
+	new_test_ext().execute_with(|| {
+		// Signed accounts cannot manage the set.
+		assert_noop!(MixJob::add_orchestrator(RuntimeOrigin::signed(ORCHESTRATOR), 3), BadOrigin);
+		assert_noop!(
+			MixJob::remove_orchestrator(RuntimeOrigin::signed(ORCHESTRATOR), ORCHESTRATOR),
+			BadOrigin
+		);
+
+		// Root can add and remove.
+		assert_ok!(MixJob::add_orchestrator(RuntimeOrigin::root(), 3));
+		assert_noop!(
+			MixJob::add_orchestrator(RuntimeOrigin::root(), 3),
+			Error::<Test>::AlreadyOrchestrator
+		);
+		assert_eq!(Orchestrators::<Test>::get().into_inner(), vec![ORCHESTRATOR, 3]);
+
+		assert_ok!(MixJob::remove_orchestrator(RuntimeOrigin::root(), ORCHESTRATOR));
+		assert_eq!(Orchestrators::<Test>::get().into_inner(), vec![3]);
+	});
+}
+
+#[test]
+fn removed_orchestrator_cannot_update_jobs() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+		assert_ok!(MixJob::remove_orchestrator(RuntimeOrigin::root(), ORCHESTRATOR));
+
+		assert_noop!(
+			MixJob::update_job_status(
+				RuntimeOrigin::signed(ORCHESTRATOR),
+				0,
+				JobStatus::Running,
+				None
+			),
+			Error::<Test>::NotOrchestrator
+		);
+	});
+}
+
+#[test]
+fn complete_job_stores_results() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+
+		let uri: ResultUri = b"ipfs://result".to_vec().try_into().unwrap();
+		let result_hash = H256::repeat_byte(1);
+		let input_root = H256::repeat_byte(2);
+		let output_root = H256::repeat_byte(3);
+
+		// Only orchestrators can complete jobs.
+		assert_noop!(
+			MixJob::complete_job(
+				RuntimeOrigin::signed(2),
+				0,
+				uri.clone(),
+				result_hash,
+				input_root,
+				output_root
+			),
+			Error::<Test>::NotOrchestrator
+		);
+
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+		assert_ok!(MixJob::complete_job(
+			RuntimeOrigin::signed(ORCHESTRATOR),
+			0,
+			uri.clone(),
+			result_hash,
+			input_root,
+			output_root
+		));
+
+		let job = Jobs::<Test>::get(0).unwrap();
+		assert_eq!(job.status, JobStatus::Completed);
+		assert_eq!(job.result_uri, Some(uri.clone()));
+		assert_eq!(job.result_hash, Some(result_hash));
+		assert_eq!(job.input_root, Some(input_root));
+		assert_eq!(job.output_root, Some(output_root));
+		assert_eq!(ElectionCommitments::get(), vec![(47, input_root, output_root)]);
+
+		System::assert_has_event(
+			Event::JobCompleted {
+				job_id: 0,
+				result_uri: uri.clone(),
+				result_hash,
+				input_root,
+				output_root,
+			}
+			.into(),
+		);
+
+		// A completed job cannot be completed again.
+		assert_noop!(
+			MixJob::complete_job(
+				RuntimeOrigin::signed(ORCHESTRATOR),
+				0,
+				uri,
+				result_hash,
+				input_root,
+				output_root
+			),
+			Error::<Test>::InvalidStatusTransition
+		);
+	});
+}
+
+#[test]
+fn submit_job_requires_election_ready_for_mixing() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			MixJob::submit_job(RuntimeOrigin::signed(1), JobKind::Election(UNKNOWN_ELECTION)),
+			Error::<Test>::ElectionNotFound
+		);
+		assert_noop!(
+			MixJob::submit_job(RuntimeOrigin::signed(1), JobKind::Election(VOTING_ELECTION)),
+			Error::<Test>::ElectionNotReadyForMixing
+		);
+		assert_eq!(NextJobId::<Test>::get(), 0);
+	});
+}
+
+#[test]
+fn submit_job_checks_batch_targets() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			MixJob::submit_job(RuntimeOrigin::signed(1), JobKind::MessageBatch(UNKNOWN_BATCH)),
+			Error::<Test>::MessageBatchNotFound
+		);
+		assert_noop!(
+			MixJob::submit_job(RuntimeOrigin::signed(1), JobKind::PublicationBatch(UNKNOWN_BATCH)),
+			Error::<Test>::PublicationBatchNotFound
+		);
+
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(1), JobKind::MessageBatch(7)));
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(1), JobKind::PublicationBatch(7)));
+		assert_eq!(MixJob::jobs(0).unwrap().kind, JobKind::MessageBatch(7));
+		assert_eq!(MixJob::jobs(1).unwrap().kind, JobKind::PublicationBatch(7));
+	});
+}
+
+#[test]
+fn completing_batch_job_skips_election_commitments() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::MessageBatch(7)));
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+		assert_ok!(MixJob::complete_job(
+			RuntimeOrigin::signed(ORCHESTRATOR),
+			0,
+			b"ipfs://result".to_vec().try_into().unwrap(),
+			H256::repeat_byte(1),
+			H256::repeat_byte(2),
+			H256::repeat_byte(3),
+		));
+
+		assert_eq!(MixJob::jobs(0).unwrap().status, JobStatus::Completed);
+		assert!(ElectionCommitments::get().is_empty());
+		assert_eq!(LastJobForElection::<Test>::get(7), None);
+	});
+}
+
+#[test]
+fn submit_job_over_xcm_records_source_para() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::root(), JobKind::Election(47)));
+
+		let job = Jobs::<Test>::get(0).unwrap();
+		assert_eq!(job.source_para, Some(SIBLING_PARA));
+		assert_eq!(job.requester, SIBLING_PARA as u64);
+
+		System::assert_last_event(
+			Event::JobSubmitted {
+				job_id: 0,
+				kind: JobKind::Election(47),
+				requester: SIBLING_PARA as u64,
+				source_para: Some(SIBLING_PARA),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn sovereign_accounts_cannot_sign_for_jobs() {
+	new_test_ext().execute_with(|| {
+		// The sibling's sovereign account is funded but must use its native XCM origin.
+		assert_noop!(
+			MixJob::submit_job(RuntimeOrigin::signed(SIBLING_PARA as u64), JobKind::Election(47)),
+			Error::<Test>::SovereignAccountNotAllowed
+		);
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::root(), JobKind::Election(47)));
+		assert_eq!(Jobs::<Test>::get(0).unwrap().source_para, Some(SIBLING_PARA));
+	});
+}
+
+#[test]
+fn completed_xcm_jobs_notify_source_para() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		// Without a registered callback no message is sent.
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::root(), JobKind::Election(47)));
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+		assert_ok!(MixJob::update_job_status(
+			RuntimeOrigin::signed(ORCHESTRATOR),
+			0,
+			JobStatus::Failed,
+			None
+		));
+		assert!(SentXcm::get().is_empty());
+
+		assert_noop!(
+			MixJob::set_para_callback(RuntimeOrigin::signed(2), SIBLING_PARA, Some([42, 0])),
+			BadOrigin
+		);
+		assert_ok!(MixJob::set_para_callback(RuntimeOrigin::root(), SIBLING_PARA, Some([42, 0])));
+		assert_eq!(ParaCallbacks::<Test>::get(SIBLING_PARA), Some([42, 0]));
+
+		// Jobs from signed accounts have no source para to notify.
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 1));
+		assert_ok!(MixJob::update_job_status(
+			RuntimeOrigin::signed(ORCHESTRATOR),
+			1,
+			JobStatus::Failed,
+			None
+		));
+		assert!(SentXcm::get().is_empty());
+
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::root(), JobKind::Election(47)));
+		let uri: ResultUri = b"ipfs://result".to_vec().try_into().unwrap();
+		let result_hash = H256::repeat_byte(1);
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 2));
+		assert_ok!(MixJob::complete_job(
+			RuntimeOrigin::signed(ORCHESTRATOR),
+			2,
+			uri.clone(),
+			result_hash,
+			H256::repeat_byte(2),
+			H256::repeat_byte(3)
+		));
+
+		let sent = SentXcm::get();
+		assert_eq!(sent.len(), 1);
+		let (dest, message) = &sent[0];
+		assert_eq!(*dest, Location::new(1, [Junction::Parachain(SIBLING_PARA)]));
+
+		let mut expected_call = vec![42, 0];
+		(2u64, JobStatus::Completed, Some(result_hash), Some(uri)).encode_to(&mut expected_call);
+		assert!(matches!(
+			message.0.last(),
+			Some(Instruction::Transact { call, .. }) if call.clone().into_encoded() == expected_call
+		));
+
+		System::assert_last_event(
+			Event::CallbackSent {
+				job_id: 2,
+				para_id: SIBLING_PARA,
+				message_id: message.using_encoded(blake2_256),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn para_allowlist_is_managed_by_update_origin() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let quota = ParaQuota { max_active_jobs: 1, max_jobs_per_period: 1, period: 5 };
+		assert_noop!(MixJob::set_para_quota(RuntimeOrigin::signed(2), 3000, quota), BadOrigin);
+		assert_noop!(
+			MixJob::set_para_quota(RuntimeOrigin::root(), 3000, ParaQuota { period: 0, ..quota }),
+			Error::<Test>::InvalidParaQuota
+		);
+
+		assert_ok!(MixJob::set_para_quota(RuntimeOrigin::root(), 3000, quota));
+		System::assert_last_event(Event::ParaQuotaSet { para_id: 3000, quota }.into());
+
+		assert_noop!(MixJob::remove_para(RuntimeOrigin::signed(2), 3000), BadOrigin);
+		assert_ok!(MixJob::remove_para(RuntimeOrigin::root(), 3000));
+		System::assert_last_event(Event::ParaRemoved { para_id: 3000 }.into());
+		assert_noop!(
+			MixJob::remove_para(RuntimeOrigin::root(), 3000),
+			Error::<Test>::ParaNotAllowed
+		);
+	});
+}
+
+#[test]
+fn submit_job_over_xcm_requires_allowed_para() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(MixJob::remove_para(RuntimeOrigin::root(), SIBLING_PARA));
+
+		assert_noop!(
+			MixJob::submit_job(RuntimeOrigin::root(), JobKind::Election(47)),
+			Error::<Test>::ParaNotAllowed
+		);
+
+		// Nor can the sibling sign with its sovereign account instead.
+		assert_noop!(
+			MixJob::submit_job(RuntimeOrigin::signed(SIBLING_PARA as u64), JobKind::Election(47)),
+			Error::<Test>::SovereignAccountNotAllowed
+		);
+
+		// Other signed submissions are not subject to the allowlist.
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+	});
+}
+
+#[test]
+fn submit_job_over_xcm_enforces_active_job_limit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		for _ in 0..SIBLING_QUOTA.max_active_jobs {
+			assert_ok!(MixJob::submit_job(RuntimeOrigin::root(), JobKind::Election(47)));
+		}
+		assert_noop!(
+			MixJob::submit_job(RuntimeOrigin::root(), JobKind::Election(47)),
+			Error::<Test>::TooManyActiveParaJobs
+		);
+		// The sovereign account cannot sign past the limit.
+		assert_noop!(
+			MixJob::submit_job(RuntimeOrigin::signed(SIBLING_PARA as u64), JobKind::Election(47)),
+			Error::<Test>::SovereignAccountNotAllowed
+		);
+
+		// Finishing a job frees a slot.
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+		assert_ok!(MixJob::update_job_status(
+			RuntimeOrigin::signed(ORCHESTRATOR),
+			0,
+			JobStatus::Failed,
+			None
+		));
+		assert_eq!(ParaUsageOf::<Test>::get(SIBLING_PARA).active_jobs, 2);
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::root(), JobKind::Election(47)));
+	});
+}
+
+#[test]
+fn submit_job_over_xcm_enforces_rate_limit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		for job_id in 0..SIBLING_QUOTA.max_jobs_per_period as u64 {
+			assert_ok!(MixJob::submit_job(RuntimeOrigin::root(), JobKind::Election(47)));
+			assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), job_id));
+			assert_ok!(MixJob::update_job_status(
+				RuntimeOrigin::signed(ORCHESTRATOR),
+				job_id,
+				JobStatus::Failed,
+				None
+			));
+		}
+		assert_noop!(
+			MixJob::submit_job(RuntimeOrigin::root(), JobKind::Election(47)),
+			Error::<Test>::ParaRateLimited
+		);
+		assert_noop!(
+			MixJob::submit_job(RuntimeOrigin::signed(SIBLING_PARA as u64), JobKind::Election(47)),
+			Error::<Test>::SovereignAccountNotAllowed
+		);
+
+		// A new window starts `period` blocks after the first submission.
+		System::set_block_number(1 + SIBLING_QUOTA.period - 1);
+		assert_noop!(
+			MixJob::submit_job(RuntimeOrigin::root(), JobKind::Election(47)),
+			Error::<Test>::ParaRateLimited
+		);
+		System::set_block_number(1 + SIBLING_QUOTA.period);
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::root(), JobKind::Election(47)));
+		assert_eq!(ParaUsageOf::<Test>::get(SIBLING_PARA).window_start, 1 + SIBLING_QUOTA.period);
+	});
+}
+
+#[test]
+fn finished_jobs_are_archived_after_retention() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(48)));
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+		assert_ok!(MixJob::update_job_status(
+			RuntimeOrigin::signed(ORCHESTRATOR),
+			0,
+			JobStatus::Failed,
+			None
+		));
+
+		// Still retained.
+		System::set_block_number(RETENTION);
+		MixJob::on_idle(RETENTION, Weight::MAX);
+		assert!(Jobs::<Test>::contains_key(0));
+
+		// No leftover weight, nothing archived.
+		System::set_block_number(1 + RETENTION);
+		assert_eq!(MixJob::on_idle(1 + RETENTION, Weight::zero()), Weight::zero());
+		assert!(Jobs::<Test>::contains_key(0));
+
+		MixJob::on_idle(1 + RETENTION, Weight::MAX);
+		assert!(!Jobs::<Test>::contains_key(0));
+		assert_eq!(LastJobForElection::<Test>::get(47), None);
+		System::assert_last_event(Event::JobArchived { job_id: 0 }.into());
+
+		// Unfinished jobs are kept.
+		assert!(Jobs::<Test>::contains_key(1));
+		assert_eq!(Jobs::<Test>::count(), 1);
+		assert_eq!(ArchiveQueueRange::<Test>::get(), (1, 1));
+	});
+}
+
+#[test]
+fn reap_job_removes_expired_job_and_pays_reward() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let pot: u64 = MixJobPalletId::get().into_account_truncating();
+		assert_ok!(<Balances as fungible::Mutate<u64>>::mint_into(&pot, 100));
+
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+		assert_noop!(MixJob::reap_job(RuntimeOrigin::signed(3), 0), Error::<Test>::JobNotFinished);
+
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+		assert_ok!(complete(ORCHESTRATOR, 0));
+		assert_noop!(MixJob::reap_job(RuntimeOrigin::signed(3), 0), Error::<Test>::JobRetained);
+
+		System::set_block_number(1 + RETENTION);
+		assert_ok!(MixJob::reap_job(RuntimeOrigin::signed(3), 0));
+		assert!(!Jobs::<Test>::contains_key(0));
+		assert_eq!(Balances::free_balance(3), REAP_REWARD);
+		System::assert_last_event(
+			Event::JobReaped { job_id: 0, who: 3, reward: REAP_REWARD }.into(),
+		);
+		assert_noop!(MixJob::reap_job(RuntimeOrigin::signed(3), 0), Error::<Test>::JobNotFound);
+
+		// The archive queue drops the reaped job.
+		MixJob::on_idle(1 + RETENTION, Weight::MAX);
+		assert_eq!(ArchiveQueueRange::<Test>::get(), (1, 1));
+	});
+}
+
+#[test]
+fn archival_frees_job_capacity() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		for job_id in 0..10 {
+			assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+			assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), job_id));
+			assert_ok!(MixJob::update_job_status(
+				RuntimeOrigin::signed(ORCHESTRATOR),
+				job_id,
+				JobStatus::Failed,
+				None
+			));
+		}
+		assert_noop!(
+			MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)),
+			Error::<Test>::JobLimitReached
+		);
+
+		System::set_block_number(1 + RETENTION);
+		MixJob::on_idle(1 + RETENTION, Weight::MAX);
+		assert_eq!(Jobs::<Test>::count(), 0);
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+	});
+}
+
+#[test]
+fn pending_jobs_time_out() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::root(), JobKind::Election(47)));
+
+		MixJob::on_initialize(PENDING_TIMEOUT);
+		assert_eq!(Jobs::<Test>::get(0).unwrap().status, JobStatus::Pending);
+
+		System::set_block_number(1 + PENDING_TIMEOUT);
+		let weight = MixJob::on_initialize(1 + PENDING_TIMEOUT);
+
+		let job = Jobs::<Test>::get(0).unwrap();
+		assert_eq!(job.status, JobStatus::Failed);
+		assert_eq!(job.error_code, Some(JobErrorCode::TimedOut));
+		assert_eq!(job.last_update, 1 + PENDING_TIMEOUT);
+		System::assert_has_event(
+			Event::JobTimedOut { job_id: 0, old_status: JobStatus::Pending as u8 }.into(),
+		);
+
+		// The job came from a sibling, so the callback to it is counted.
+		let db = <Test as frame_system::Config>::DbWeight::get();
+		assert_eq!(weight, db.reads_writes(4, 5).saturating_add(CallbackWeight::get()));
+
+		// The sibling's slot is released.
+		assert_eq!(ParaUsageOf::<Test>::get(SIBLING_PARA).active_jobs, 0);
+	});
+}
+
+#[test]
+fn running_jobs_time_out_without_updates() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+
+		// A Running -> Running update restarts the timeout.
+		System::set_block_number(3);
+		assert_ok!(MixJob::update_job_status(
+			RuntimeOrigin::signed(ORCHESTRATOR),
+			0,
+			JobStatus::Running,
+			None
+		));
+
+		System::set_block_number(1 + RUNNING_TIMEOUT);
+		MixJob::on_initialize(1 + RUNNING_TIMEOUT);
+		assert_eq!(Jobs::<Test>::get(0).unwrap().status, JobStatus::Running);
+
+		System::set_block_number(3 + RUNNING_TIMEOUT);
+		MixJob::on_initialize(3 + RUNNING_TIMEOUT);
+		let job = Jobs::<Test>::get(0).unwrap();
+		assert_eq!(job.status, JobStatus::Failed);
+		assert_eq!(job.error_code, Some(JobErrorCode::TimedOut));
+		System::assert_has_event(
+			Event::JobTimedOut { job_id: 0, old_status: JobStatus::Running as u8 }.into(),
+		);
+
+		// No check of the original submission is left behind.
+		assert_eq!(TimeoutSchedule::<Test>::iter().count(), 0);
+		assert_eq!(Jobs::<Test>::get(0).unwrap().next_check, None);
+	});
+}
+
+#[test]
+fn finished_jobs_do_not_time_out() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let code: BoundedVec<u8, ConstU32<256>> = b"mixnode down".to_vec().try_into().unwrap();
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+		assert_ok!(MixJob::update_job_status(
+			RuntimeOrigin::signed(ORCHESTRATOR),
+			0,
+			JobStatus::Failed,
+			Some(code.clone())
+		));
+
+		System::set_block_number(1 + PENDING_TIMEOUT);
+		MixJob::on_initialize(1 + PENDING_TIMEOUT);
+		assert_eq!(
+			Jobs::<Test>::get(0).unwrap().error_code,
+			Some(JobErrorCode::Orchestrator(code))
+		);
+	});
+}
+
+/// Every scheduled check as `(block, job_id)`, in block order.
+fn scheduled_checks() -> Vec<(u64, u64)> {
+	let mut checks: Vec<_> = TimeoutSchedule::<Test>::iter()
+		.flat_map(|(at, jobs)| jobs.into_iter().map(move |job_id| (at, job_id)))
+		.collect();
+	checks.sort();
+	checks
+}
+
+#[test]
+fn rescheduling_keeps_one_check_per_job() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+		assert_eq!(scheduled_checks(), vec![(1 + PENDING_TIMEOUT, 0)]);
+
+		// Claiming moves the check to the Running timeout, before the lease expires.
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+		assert_eq!(scheduled_checks(), vec![(1 + RUNNING_TIMEOUT, 0)]);
+
+		for block in [2, 3] {
+			System::set_block_number(block);
+			assert_ok!(MixJob::update_job_status(
+				RuntimeOrigin::signed(ORCHESTRATOR),
+				0,
+				JobStatus::Running,
+				None
+			));
+		}
+		assert_eq!(scheduled_checks(), vec![(3 + RUNNING_TIMEOUT, 0)]);
+		assert_eq!(Jobs::<Test>::get(0).unwrap().next_check, Some(3 + RUNNING_TIMEOUT));
+
+		// Finishing the job drops its check.
+		assert_ok!(MixJob::update_job_status(
+			RuntimeOrigin::signed(ORCHESTRATOR),
+			0,
+			JobStatus::Failed,
+			None
+		));
+		assert_eq!(scheduled_checks(), vec![]);
+		assert_eq!(Jobs::<Test>::get(0).unwrap().next_check, None);
+	});
+}
+
+#[test]
+fn full_timeout_blocks_spill_to_the_next_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let per_block = <Test as crate::Config>::MaxTimeoutsPerBlock::get() as u64;
+		for _ in 0..=per_block {
+			assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+		}
+
+		let due = 1 + PENDING_TIMEOUT;
+		assert_eq!(TimeoutSchedule::<Test>::get(due).len() as u64, per_block);
+		assert_eq!(TimeoutSchedule::<Test>::get(due + 1).to_vec(), vec![per_block]);
+
+		// The spilled job still times out, one block late.
+		for block in [due, due + 1] {
+			System::set_block_number(block);
+			MixJob::on_initialize(block);
+		}
+		assert!(Jobs::<Test>::iter_values().all(|job| job.status == JobStatus::Failed));
+		assert_eq!(scheduled_checks(), vec![]);
+	});
+}
+
+#[test]
+fn expired_lease_reschedules_past_a_full_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+
+		// Jobs submitted along with it fill the block of its Pending timeout.
+		for _ in 0..<Test as crate::Config>::MaxTimeoutsPerBlock::get() {
+			assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+		}
+		System::set_block_number(LEASE_DURATION);
+		assert_ok!(MixJob::update_job_status(
+			RuntimeOrigin::signed(ORCHESTRATOR),
+			0,
+			JobStatus::Running,
+			None
+		));
+
+		let expiry = 1 + LEASE_DURATION;
+		System::set_block_number(expiry);
+		MixJob::on_initialize(expiry);
+
+		let job = Jobs::<Test>::get(0).unwrap();
+		assert_eq!(job.status, JobStatus::Pending);
+		assert_eq!(job.next_check, Some(1 + PENDING_TIMEOUT + 1));
+
+		for block in [1 + PENDING_TIMEOUT, 1 + PENDING_TIMEOUT + 1] {
+			System::set_block_number(block);
+			MixJob::on_initialize(block);
+		}
+		assert_eq!(Jobs::<Test>::get(0).unwrap().status, JobStatus::Failed);
+	});
+}
+
+#[test]
+fn claim_job_leases_pending_job() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MixJob::add_orchestrator(RuntimeOrigin::root(), 3));
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+
+		// Unclaimed jobs cannot be updated.
+		assert_noop!(
+			MixJob::update_job_status(
+				RuntimeOrigin::signed(ORCHESTRATOR),
+				0,
+				JobStatus::Failed,
+				None
+			),
+			Error::<Test>::NotLeaseHolder
+		);
+		assert_noop!(
+			MixJob::claim_job(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::NotOrchestrator
+		);
+
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+		let job = Jobs::<Test>::get(0).unwrap();
+		assert_eq!(job.status, JobStatus::Running);
+		assert_eq!(job.claimed_by, Some(ORCHESTRATOR));
+		assert_eq!(job.lease_expires_at, Some(1 + LEASE_DURATION));
+
+		// Another orchestrator can neither claim nor finish it.
+		assert_noop!(MixJob::claim_job(RuntimeOrigin::signed(3), 0), Error::<Test>::JobNotPending);
+		assert_noop!(
+			MixJob::update_job_status(RuntimeOrigin::signed(3), 0, JobStatus::Failed, None),
+			Error::<Test>::NotLeaseHolder
+		);
+		assert_noop!(
+			MixJob::renew_lease(RuntimeOrigin::signed(3), 0),
+			Error::<Test>::NotLeaseHolder
+		);
+	});
+}
+
+#[test]
+fn lease_can_be_renewed_by_holder() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+
+		System::set_block_number(9);
+		assert_ok!(MixJob::renew_lease(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+		assert_eq!(Jobs::<Test>::get(0).unwrap().lease_expires_at, Some(9 + LEASE_DURATION));
+		System::assert_last_event(
+			Event::LeaseRenewed { job_id: 0, lease_expires_at: 9 + LEASE_DURATION }.into(),
+		);
+
+		// The original expiry no longer returns the job to Pending.
+		assert_ok!(MixJob::update_job_status(
+			RuntimeOrigin::signed(ORCHESTRATOR),
+			0,
+			JobStatus::Running,
+			None
+		));
+		System::set_block_number(1 + LEASE_DURATION);
+		MixJob::on_initialize(1 + LEASE_DURATION);
+		assert_eq!(Jobs::<Test>::get(0).unwrap().status, JobStatus::Running);
+	});
+}
+
+#[test]
+fn expired_lease_returns_job_to_pending() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MixJob::add_orchestrator(RuntimeOrigin::root(), 3));
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+
+		// Keep the job alive so only the lease runs out.
+		let heartbeat_at = 1 + LEASE_DURATION - 1;
+		System::set_block_number(heartbeat_at);
+		assert_ok!(MixJob::update_job_status(
+			RuntimeOrigin::signed(ORCHESTRATOR),
+			0,
+			JobStatus::Running,
+			None
+		));
+
+		System::set_block_number(1 + LEASE_DURATION);
+		MixJob::on_initialize(1 + LEASE_DURATION);
+
+		let job = Jobs::<Test>::get(0).unwrap();
+		assert_eq!(job.status, JobStatus::Pending);
+		assert_eq!(job.claimed_by, None);
+		assert_eq!(job.lease_expires_at, None);
+		System::assert_last_event(
+			Event::LeaseExpired { job_id: 0, orchestrator: ORCHESTRATOR }.into(),
+		);
+
+		// The former holder lost the job; anyone may claim it again.
+		assert_noop!(
+			MixJob::update_job_status(
+				RuntimeOrigin::signed(ORCHESTRATOR),
+				0,
+				JobStatus::Failed,
+				None
+			),
+			Error::<Test>::NotLeaseHolder
+		);
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(3), 0));
+	});
+}
+
+#[test]
+fn expired_lease_keeps_the_pending_deadline() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+
+		let expiry = 1 + LEASE_DURATION;
+		System::set_block_number(expiry - 1);
+		assert_ok!(MixJob::update_job_status(
+			RuntimeOrigin::signed(ORCHESTRATOR),
+			0,
+			JobStatus::Running,
+			None
+		));
+		System::set_block_number(expiry);
+		MixJob::on_initialize(expiry);
+
+		// Back in Pending, the job still times out counting from its submission.
+		let job = Jobs::<Test>::get(0).unwrap();
+		assert_eq!(job.status, JobStatus::Pending);
+		assert_eq!(job.next_check, Some(1 + PENDING_TIMEOUT));
+
+		// Claimed again late, its next lease expires past that deadline and it fails at once.
+		System::set_block_number(PENDING_TIMEOUT - 1);
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+		let expiry = PENDING_TIMEOUT - 1 + LEASE_DURATION;
+		for block in [PENDING_TIMEOUT + 2, expiry - 1] {
+			System::set_block_number(block);
+			assert_ok!(MixJob::update_job_status(
+				RuntimeOrigin::signed(ORCHESTRATOR),
+				0,
+				JobStatus::Running,
+				None
+			));
+		}
+		System::set_block_number(expiry);
+		MixJob::on_initialize(expiry);
+
+		let job = Jobs::<Test>::get(0).unwrap();
+		assert_eq!(job.status, JobStatus::Failed);
+		assert_eq!(job.error_code, Some(JobErrorCode::TimedOut));
+		System::assert_has_event(
+			Event::LeaseExpired { job_id: 0, orchestrator: ORCHESTRATOR }.into(),
+		);
+		System::assert_has_event(
+			Event::JobTimedOut { job_id: 0, old_status: JobStatus::Pending as u8 }.into(),
+		);
+	});
+}
+
+/// Deposit and fee held from `who` for its jobs.
+fn held(who: u64) -> u64 {
+	<Balances as fungible::InspectHold<u64>>::balance_on_hold(&HoldReason::JobDeposit.into(), &who)
+}
+
+#[test]
+fn submit_job_holds_deposit_and_fee() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+
+		let job = Jobs::<Test>::get(0).unwrap();
+		assert_eq!((job.deposit, job.fee), (JOB_DEPOSIT, SERVICE_FEE));
+		assert_eq!(held(2), JOB_DEPOSIT + SERVICE_FEE);
+		assert_eq!(Balances::free_balance(2), INITIAL_BALANCE - JOB_DEPOSIT - SERVICE_FEE);
+
+		// Jobs arriving over XCM are paid by the sibling's sovereign account.
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::root(), JobKind::Election(47)));
+		assert_eq!(held(SIBLING_PARA as u64), JOB_DEPOSIT + SERVICE_FEE);
+
+		// Account 5 has no funds.
+		assert_noop!(
+			MixJob::submit_job(RuntimeOrigin::signed(5), JobKind::Election(47)),
+			TokenError::FundsUnavailable
+		);
+	});
+}
+
+#[test]
+fn completed_job_pays_fee_to_orchestrator() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+		assert_ok!(complete(ORCHESTRATOR, 0));
+
+		assert_eq!(held(2), 0);
+		assert_eq!(Balances::free_balance(2), INITIAL_BALANCE - SERVICE_FEE);
+		assert_eq!(Balances::free_balance(ORCHESTRATOR), INITIAL_BALANCE + SERVICE_FEE);
+		System::assert_has_event(
+			Event::ServiceFeePaid { job_id: 0, orchestrator: ORCHESTRATOR, fee: SERVICE_FEE }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn failed_job_slashes_part_of_deposit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+		assert_ok!(MixJob::claim_job(RuntimeOrigin::signed(ORCHESTRATOR), 0));
+		assert_ok!(MixJob::update_job_status(
+			RuntimeOrigin::signed(ORCHESTRATOR),
+			0,
+			JobStatus::Failed,
+			None
+		));
+
+		// The fee is refunded; half the deposit goes to the pallet account.
+		let slashed = JOB_DEPOSIT / 2;
+		let pot: u64 = MixJobPalletId::get().into_account_truncating();
+		assert_eq!(held(2), 0);
+		assert_eq!(Balances::free_balance(2), INITIAL_BALANCE - slashed);
+		assert_eq!(Balances::free_balance(pot), slashed);
+		System::assert_has_event(
+			Event::DepositSlashed { job_id: 0, requester: 2, amount: slashed }.into(),
+		);
+	});
+}
+
+#[test]
+fn timed_out_job_is_refunded() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(MixJob::submit_job(RuntimeOrigin::signed(2), JobKind::Election(47)));
+
+		System::set_block_number(1 + PENDING_TIMEOUT);
+		MixJob::on_initialize(1 + PENDING_TIMEOUT);
+
+		assert_eq!(Jobs::<Test>::get(0).unwrap().status, JobStatus::Failed);
+		assert_eq!(held(2), 0);
+		assert_eq!(Balances::free_balance(2), INITIAL_BALANCE);
+	});
+}
+
+/// A job of storage version 0.
+#[derive(Encode)]
+struct MixJobInfoV0 {
+	job_id: u64,
+	requester: u64,
+	source_para: Option<u32>,
+	election_id: u32,
+	created_at: u64,
+	status: JobStatus,
+	last_update: u64,
+	error_code: Option<BoundedVec<u8, ConstU32<256>>>,
+}
+
+#[test]
+fn migration_to_v1_translates_jobs() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		let statuses = [JobStatus::Pending, JobStatus::Running, JobStatus::Failed];
+		for (job_id, status) in statuses.into_iter().enumerate() {
+			let job = MixJobInfoV0 {
+				job_id: job_id as u64,
+				requester: 2,
+				source_para: None,
+				election_id: 47,
+				created_at: 1,
+				status,
+				last_update: 2,
+				error_code: (status == JobStatus::Failed)
+					.then(|| BoundedVec::truncate_from(b"oops".to_vec())),
+			};
+			unhashed::put(&Jobs::<Test>::hashed_key_for(job_id as u64), &job);
+		}
+
+		migrations::InnerMigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Jobs::<Test>::count(), 3);
+		let running = Jobs::<Test>::get(1).unwrap();
+		assert_eq!(running.kind, JobKind::Election(47));
+		assert_eq!((running.status, running.last_update), (JobStatus::Pending, 3));
+		assert_eq!((running.deposit, running.fee), (0, 0));
+		assert_eq!(
+			Jobs::<Test>::get(2).unwrap().error_code,
+			Some(JobErrorCode::Orchestrator(BoundedVec::truncate_from(b"oops".to_vec())))
+		);
+
+		// The failed job awaits archival, the others a new claim or the Pending timeout of
+		// their submission.
+		assert_eq!(ArchiveQueueRange::<Test>::get(), (0, 1));
+		assert_eq!(ArchiveQueue::<Test>::get(0), Some(2));
+		MixJob::on_initialize(1 + PENDING_TIMEOUT);
+		assert_eq!(Jobs::<Test>::get(0).unwrap().status, JobStatus::Failed);
+		assert_eq!(Jobs::<Test>::get(1).unwrap().status, JobStatus::Failed);
+	});
+}
@@ -59,13 +59,17 @@ use xcm::latest::prelude::{AssetId, BodyId};
 // Local module imports
 use super::{
 	weights::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight},
-	AccountId, Aura, Balance, Balances, Block, BlockNumber, CollatorSelection, ConsensusHook, Hash,
-	MessageQueue, Nonce, PalletInfo, ParachainSystem, Runtime, RuntimeCall, RuntimeEvent,
-	RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask, Session, SessionKeys,
-	System, WeightToFee, XcmpQueue, AVERAGE_ON_INITIALIZE_RATIO, CENTS, EXISTENTIAL_DEPOSIT, HOURS,
-	MAXIMUM_BLOCK_WEIGHT, MICRO_UNIT, NORMAL_DISPATCH_RATIO, SLOT_DURATION, VERSION,
+	AccountId, Aura, Balance, Balances, Block, BlockNumber, CollatorSelection, ConsensusHook,
+	DaomixVoting, Hash, MessageQueue, Nonce, PalletInfo, ParachainSystem, Runtime, RuntimeCall,
+	RuntimeEvent, RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask, Session,
+	SessionKeys, System, WeightToFee, XcmpQueue, AVERAGE_ON_INITIALIZE_RATIO, CENTS, DAYS,
+	EXISTENTIAL_DEPOSIT, HOURS, MAXIMUM_BLOCK_WEIGHT, MICRO_UNIT, MINUTES,
+	NORMAL_DISPATCH_RATIO, SLOT_DURATION, VERSION,
+};
+use xcm_config::{
+	EnsureSiblingParachain, MixJobCallbackFeeSource, RelayLocation, SiblingOfSovereignAccount,
+	SiblingSovereignAccount, XcmOriginToTransactDispatchOrigin,
 };
-use xcm_config::{RelayLocation, XcmOriginToTransactDispatchOrigin};
 
 parameter_types! {
 	pub const Version: RuntimeVersion = VERSION;
@@ -101,7 +105,10 @@ parameter_types! {
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 #[allow(unused_parens)]
-type SingleBlockMigrations = ();
+type SingleBlockMigrations = (
+	pallet_daomix_voting::migrations::MigrateV0ToV1<Runtime>,
+	pallet_mix_job::migrations::MigrateV0ToV1<Runtime>,
+);
 
 /// The default types are being injected by [`derive_impl`](`frame_support::derive_impl`) from
 /// [`ParaChainDefaultConfig`](`struct@frame_system::config_preludes::ParaChainDefaultConfig`),
@@ -192,6 +199,10 @@ impl pallet_transaction_payment::Config for Runtime {
 	type WeightInfo = ();
 }
 
+impl pallet_skip_feeless_payment::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+}
+
 impl pallet_sudo::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -347,17 +358,118 @@ impl pallet_parachain_template::Config for Runtime {
 	type WeightInfo = pallet_parachain_template::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	pub const MaxPhaseTransitionsPerBlock: u32 = 64;
+	// As many of the largest ballots as fit in the `on_initialize` share of the PoV budget.
+	pub MaxBallotsHashedPerBlock: u32 = {
+		use pallet_daomix_voting::weights::{SubstrateWeight, WeightInfo};
+		let budget = AVERAGE_ON_INITIALIZE_RATIO * MAXIMUM_BLOCK_WEIGHT.proof_size();
+		(budget / SubstrateWeight::<Runtime>::hash_ballot().proof_size()).max(1) as u32
+	};
+	pub const ShuffleChallengesPerPair: u32 = 16;
+	pub const MaxLinksPerProof: u32 = 8;
+	pub const ElectionDeposit: Balance = 100 * CENTS;
+	pub const TallyChallengePeriod: BlockNumber = 2 * HOURS;
+	pub const TallyBond: Balance = 500 * CENTS;
+	pub const TallyDisputeBond: Balance = 100 * CENTS;
+	pub const MaxTallyDisputes: u32 = 16;
+	pub const MaxTallyTrustees: u32 = 16;
+	pub const DkgRoundPeriod: BlockNumber = 10 * MINUTES;
+	pub const VotingPalletId: PalletId = PalletId(*b"dm/votes");
+	pub const FeelessBallotFee: Balance = 5 * CENTS;
+	pub const MaxFeelessBallots: u32 = 3;
+	pub const VotingUnsignedPriority: u64 = 1 << 20;
+	pub const VotingUnsignedLongevity: u64 = 10 * MINUTES as u64;
+}
+
 /// Configure the DaoMix Voting pallet.
 impl pallet_daomix_voting::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_daomix_voting::weights::SubstrateWeight<Runtime>;
+	type MaxPhaseTransitionsPerBlock = MaxPhaseTransitionsPerBlock;
+	type MaxBallotsHashedPerBlock = MaxBallotsHashedPerBlock;
+	// The mixer has no per-ballot layer keys yet, so opened links are not decrypted on-chain;
+	// tallies still wait until every committed hop has answered its challenges.
+	type LinkVerifier = ();
+	type ShuffleChallengesPerPair = ShuffleChallengesPerPair;
+	type MaxLinksPerProof = MaxLinksPerProof;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type Currency = Balances;
+	type ElectionDeposit = ElectionDeposit;
+	type ChallengePeriod = TallyChallengePeriod;
+	type TallyBond = TallyBond;
+	type DisputeBond = TallyDisputeBond;
+	type MaxDisputes = MaxTallyDisputes;
+	type DisputeOrigin = EnsureRoot<AccountId>;
+	type MaxTrustees = MaxTallyTrustees;
+	type DkgRoundPeriod = DkgRoundPeriod;
+	// Membership proofs are checked under the verifying key of the circuit in
+	// `pallets/daomix-voting/membership-circuit`, in Wasm as validators provide no BLS12-381
+	// host calls. That key comes from a single-party setup: replace it with the outcome of a
+	// ceremony before holding real elections.
+	#[cfg(not(feature = "runtime-benchmarks"))]
+	type MembershipVerifier = pallet_daomix_voting::groth16::Groth16Verifier<
+		pallet_daomix_voting::groth16::MembershipCircuitKey,
+	>;
+	// Verification costs the same under any key, so benchmarks use one they can prove for.
+	#[cfg(feature = "runtime-benchmarks")]
+	type MembershipVerifier = pallet_daomix_voting::groth16::Groth16Verifier<
+		pallet_daomix_voting::groth16::trapdoor::TrapdoorKey,
+	>;
+	type PalletId = VotingPalletId;
+	type FeelessBallotFee = FeelessBallotFee;
+	type MaxFeelessBallots = MaxFeelessBallots;
+	type UnsignedPriority = VotingUnsignedPriority;
+	type UnsignedLongevity = VotingUnsignedLongevity;
 }
+
 parameter_types! {
 	pub const MaxJobs: u32 = 1_000;
+	pub const MaxOrchestrators: u32 = 16;
+	pub const MixJobPalletId: PalletId = PalletId(*b"dm/mixjb");
+	pub const MixJobRetentionPeriod: BlockNumber = 7 * DAYS;
+	pub const MixJobReapReward: Balance = CENTS;
+	pub const MixJobDeposit: Balance = 100 * CENTS;
+	pub const MixJobServiceFee: Balance = 20 * CENTS;
+	pub const MixJobFailureSlash: Perbill = Perbill::from_percent(20);
+	pub const MixJobPendingTimeout: BlockNumber = 6 * HOURS;
+	pub const MixJobRunningTimeout: BlockNumber = HOURS;
+	pub const MixJobLeaseDuration: BlockNumber = 10 * MINUTES;
+	pub const MaxJobTimeoutsPerBlock: u32 = 64;
+	// Reading the callback, queueing the message for the sibling and charging its delivery fee.
+	pub MixJobCallbackWeight: Weight =
+		RocksDbWeight::get().reads_writes(6, 4).saturating_add(Weight::from_parts(50_000_000, 4_096));
 }
 
 /// Configure the MixJob pallet.
 impl pallet_mix_job::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_mix_job::weights::SubstrateWeight<Runtime>;
 	type MaxJobs = MaxJobs;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type Currency = Balances;
+	type JobDeposit = MixJobDeposit;
+	type ServiceFee = MixJobServiceFee;
+	type FailureSlash = MixJobFailureSlash;
+	type PalletId = MixJobPalletId;
+	type JobRetentionPeriod = MixJobRetentionPeriod;
+	type ReapReward = MixJobReapReward;
+	type PendingTimeout = MixJobPendingTimeout;
+	type RunningTimeout = MixJobRunningTimeout;
+	type LeaseDuration = MixJobLeaseDuration;
+	type MaxTimeoutsPerBlock = MaxJobTimeoutsPerBlock;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type MaxOrchestrators = MaxOrchestrators;
+	type ElectionProvider = DaomixVoting;
+	// No messaging or publishing pallet yet, so batch jobs are rejected.
+	type MessageBatches = ();
+	type PublicationBatches = ();
+	type XcmOrigin = EnsureSiblingParachain;
+	type SovereignAccountOf = SiblingSovereignAccount;
+	type ParaOfSovereignAccount = SiblingOfSovereignAccount;
+	type XcmRouter = xcm_config::XcmRouter;
+	type XcmExecutor = polkadot_sdk::staging_xcm_executor::XcmExecutor<xcm_config::XcmConfig>;
+	type CallbackFeeSource = MixJobCallbackFeeSource;
+	type CallbackWeight = MixJobCallbackWeight;
 }
 
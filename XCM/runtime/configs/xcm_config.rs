@@ -1,22 +1,26 @@
-use super::PriceForParentDelivery;
+use super::{MixJobPalletId, PriceForParentDelivery};
 use crate::{
 	AccountId, AllPalletsWithSystem, Balances, ParachainInfo, ParachainSystem, PolkadotXcm,
 	Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, WeightToFee, XcmpQueue,
 };
 
+use core::marker::PhantomData;
 use polkadot_sdk::{
 	staging_xcm as xcm, staging_xcm_builder as xcm_builder, staging_xcm_executor as xcm_executor, *,
 };
 
 use frame_support::{
 	parameter_types,
-	traits::{ConstU32, Contains, Everything, Nothing},
+	traits::{
+		ConstU32, Contains, EnsureOrigin, Everything, EverythingBut, Nothing, ProcessMessageError,
+	},
 	weights::Weight,
 };
 use frame_system::EnsureRoot;
 use pallet_xcm::XcmPassthrough;
 use polkadot_parachain_primitives::primitives::Sibling;
 use polkadot_runtime_common::impls::ToAuthor;
+use sp_runtime::traits::{AccountIdConversion, Convert};
 use polkadot_sdk::{
 	polkadot_sdk_frame::traits::Disabled,
 	staging_xcm_builder::{DenyRecursively, DenyThenTry},
@@ -30,7 +34,10 @@ use xcm_builder::{
 	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
 	TrailingSetTopicAsId, UsingComponents, WithComputedOrigin, WithUniqueTopic,
 };
-use xcm_executor::XcmExecutor;
+use xcm_executor::{
+	traits::{Properties, ShouldExecute},
+	XcmExecutor,
+};
 
 parameter_types! {
 	pub const RelayLocation: Location = Location::parent();
@@ -39,6 +46,13 @@ parameter_types! {
 	// For the real deployment, it is recommended to set `RelayNetwork` according to the relay chain
 	// and prepend `UniversalLocation` with `GlobalConsensus(RelayNetwork::get())`.
 	pub UniversalLocation: InteriorLocation = Parachain(ParachainInfo::parachain_id().into()).into();
+	// Local account paying the delivery fees of mix job result callbacks.
+	pub MixJobCallbackFeeSource: Location = AccountId32 {
+		network: None,
+		id: AccountIdConversion::<AccountId>::into_account_truncating(&MixJobPalletId::get())
+			.into(),
+	}
+	.into();
 }
 
 /// Type for specifying how a `Location` can be converted into an `AccountId`. This is used
@@ -102,14 +116,24 @@ impl Contains<Location> for ParentOrParentsExecutivePlurality {
 	}
 }
 
+/// Sibling parachains and any location inside them.
+pub struct SiblingParachains;
+impl Contains<Location> for SiblingParachains {
+	fn contains(location: &Location) -> bool {
+		matches!(location.unpack(), (1, [Parachain(_), ..]))
+	}
+}
+
 pub type Barrier = TrailingSetTopicAsId<
 	DenyThenTry<
 		DenyRecursively<DenyReserveTransferToRelayChain>,
 		(
 			TakeWeightCredit,
+			// Sibling parachains may only submit mix jobs.
+			AllowMixJobFromSiblings<MixJobXcmCalls>,
 			WithComputedOrigin<
 				(
-					AllowTopLevelPaidExecutionFrom<Everything>,
+					AllowTopLevelPaidExecutionFrom<EverythingBut<SiblingParachains>>,
 					AllowExplicitUnpaidExecutionFrom<ParentOrParentsExecutivePlurality>,
 					// ^^^ Parent and its exec plurality get free execution
 				),
@@ -120,20 +144,97 @@ pub type Barrier = TrailingSetTopicAsId<
 	>,
 >;
 
-/// Allow MixJob pallet calls from sibling parachains via XCM
-pub struct AllowMixJobFromSiblings;
-impl Contains<(Location, Xcm<RuntimeCall>)> for AllowMixJobFromSiblings {
-	fn contains((origin, xcm): &(Location, Xcm<RuntimeCall>)) -> bool {
-		// Only allow from sibling parachains (not relay chain or local)
-		let is_sibling_para = matches!(origin, Location { parents: 1, interior: Junctions::X1(_) });
+/// Runtime calls that may be dispatched through XCM `Transact`.
+pub struct MixJobXcmCalls;
+impl Contains<RuntimeCall> for MixJobXcmCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(call, RuntimeCall::MixJob(pallet_mix_job::Call::submit_job { .. }))
+	}
+}
+
+/// Allows paid execution from sibling parachains only for programs of the exact form
+/// `WithdrawAsset`, `BuyExecution`, `Transact`, where the call is transacted with
+/// `OriginKind::Native` and is in `AllowedCalls`.
+///
+/// The native origin makes `submit_job` see the sibling itself rather than its sovereign
+/// account, so the allowlist and quotas apply.
+///
+/// A trailing `SetTopic` is stripped beforehand by `TrailingSetTopicAsId`.
+pub struct AllowMixJobFromSiblings<AllowedCalls>(PhantomData<AllowedCalls>);
+impl<AllowedCalls: Contains<RuntimeCall>> ShouldExecute for AllowMixJobFromSiblings<AllowedCalls> {
+	fn should_execute<Call>(
+		origin: &Location,
+		instructions: &mut [Instruction<Call>],
+		max_weight: Weight,
+		_properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		if !matches!(origin.unpack(), (1, [Parachain(_)])) {
+			return Err(ProcessMessageError::Unsupported);
+		}
+
+		let [
+			WithdrawAsset(..),
+			BuyExecution { weight_limit, .. },
+			Transact { origin_kind: OriginKind::Native, call, .. },
+		] = instructions
+		else {
+			return Err(ProcessMessageError::BadFormat);
+		};
+
+		let enough_weight = match weight_limit {
+			Limited(weight) => weight.all_gte(max_weight),
+			Unlimited => true,
+		};
+		if !enough_weight {
+			return Err(ProcessMessageError::Overweight(max_weight));
+		}
+		*weight_limit = Limited(max_weight);
+
+		let call = call
+			.clone()
+			.into::<RuntimeCall>()
+			.try_into()
+			.map_err(|_| ProcessMessageError::BadFormat)?;
+		if !AllowedCalls::contains(&call) {
+			return Err(ProcessMessageError::Unsupported);
+		}
 
-		if !is_sibling_para {
-			return false;
+		Ok(())
+	}
+}
+
+/// Ensures the origin is a sibling parachain dispatching natively through XCM `Transact`
+/// (`OriginKind::Native`, converted by `SiblingParachainAsNative`), yielding its para id.
+pub struct EnsureSiblingParachain;
+impl EnsureOrigin<RuntimeOrigin> for EnsureSiblingParachain {
+	type Success = u32;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+		match o.clone().into() {
+			Ok(cumulus_pallet_xcm::Origin::SiblingParachain(para_id)) => Ok(para_id.into()),
+			_ => Err(o),
 		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		Ok(cumulus_pallet_xcm::Origin::SiblingParachain(1000.into()).into())
+	}
+}
+
+/// Sovereign account of a sibling parachain, matching `SiblingParachainConvertsVia`.
+pub struct SiblingSovereignAccount;
+impl Convert<u32, AccountId> for SiblingSovereignAccount {
+	fn convert(para_id: u32) -> AccountId {
+		Sibling::from(para_id).into_account_truncating()
+	}
+}
 
-		// For now, allow any XCM from sibling parachains (can be made more restrictive later)
-		// In production, you would check for specific Transact instructions
-		true
+/// Para id of a sibling parachain's sovereign account, inverting `SiblingSovereignAccount`.
+pub struct SiblingOfSovereignAccount;
+impl Convert<AccountId, Option<u32>> for SiblingOfSovereignAccount {
+	fn convert(who: AccountId) -> Option<u32> {
+		Sibling::try_from_account(&who).map(|Sibling(para_id)| para_id.into())
 	}
 }
 
@@ -164,7 +265,10 @@ impl xcm_executor::Config for XcmConfig {
 	type MessageExporter = ();
 	type UniversalAliases = Nothing;
 	type CallDispatcher = RuntimeCall;
-	type SafeCallFilter = Everything;
+	// Mix job submissions are the only calls this runtime takes over XCM: the barrier limits
+	// siblings to them, and no origin converted by `XcmOriginToTransactDispatchOrigin` is root,
+	// so the relay chain has nothing else to transact.
+	type SafeCallFilter = MixJobXcmCalls;
 	type Aliasers = Nothing;
 	type TransactionalProcessor = FrameTransactionalProcessor;
 	type HrmpNewChannelOpenRequestHandler = ();
@@ -191,8 +295,9 @@ impl pallet_xcm::Config for Runtime {
 	type SendXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
 	type XcmRouter = XcmRouter;
 	type ExecuteXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
-	type XcmExecuteFilter = AllowMixJobFromSiblings;
-	// ^ Allow MixJob pallet calls from sibling parachains for cross-chain mixing
+	type XcmExecuteFilter = Nothing;
+	// ^ Disable dispatchable execute on the XCM pallet.
+	// Needs to be `Everything` for local testing.
 	type XcmExecutor = XcmExecutor<XcmConfig>;
 	type XcmTeleportFilter = Everything;
 	type XcmReserveTransferFilter = Nothing;
@@ -76,6 +76,12 @@ async function fetchBallotsFromDaoChain(
       continue;
     }
 
+    // Skip ballots superseded by a later ballot of the same voter (last vote wins)
+    const replaced = await api.query.daomixVoting.replacedBallots(electionId, index);
+    if ((replaced as any).isTrue === true) {
+      continue;
+    }
+
     // Unwrap Option and convert BoundedVec<u8> to Uint8Array
     // Note: toHex() returns the raw hex without SCALE encoding, toU8a() includes SCALE prefix
     const bounded = (storage as any).unwrap ? (storage as any).unwrap() : storage;
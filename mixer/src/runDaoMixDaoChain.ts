@@ -90,6 +90,7 @@ import {
 	createElectionTx,
 	registerVoterTx,
 	loadTransportConfig,
+	waitForBlock,
 	type TransportConfig,
 } from "./substrateClient";
 import { castOnionBallotsOnDaoChain, type DaoChainBallot } from "./castOnionBallots";
@@ -247,9 +248,14 @@ async function main() {
 			plaintext: voterVotes[idx],
 		}));
 
+		// Ballots are only accepted once the election enters the Voting phase
+		await waitForBlock(api, regDeadline + 1);
 		await castOnionBallotsOnDaoChain(electionId, ballots, transportCfg);
 		console.log();
 
+		// Mix commitments are only accepted once the election enters the Mixing phase
+		await waitForBlock(api, voteDeadline + 1);
+
 		// 8) Run mix + tally on DaoChain
 		console.log(`🔄 Running mix-chain and tally for election ${electionId}...`);
 		await runDaoMixForElectionOnDaoChain(electionId, transportCfg);
@@ -286,6 +286,38 @@ export async function connectDaoChain(): Promise<DaoChainClients> {
 	return { api, admin, tally };
 }
 
+/**
+ * Resolve once the chain has imported a block with number >= `target`.
+ *
+ * Election phases advance in `on_initialize`, so callers wait for the block
+ * right after a deadline before calling phase-gated extrinsics.
+ */
+export async function waitForBlock(api: ApiPromise, target: number): Promise<void> {
+	const current = (await api.rpc.chain.getHeader()).number.toNumber();
+	if (current >= target) {
+		return;
+	}
+
+	console.log(`⏳ Waiting for block #${target} (current #${current})...`);
+	for (;;) {
+		await new Promise((resolve) => setTimeout(resolve, 2000));
+		const header = await api.rpc.chain.getHeader();
+		if (header.number.toNumber() >= target) {
+			return;
+		}
+	}
+}
+
+/**
+ * How the pallet treats a second ballot from the same voter.
+ */
+export type RevotePolicy = "RejectRevote" | "LastVoteWins";
+
+/**
+ * How the pallet counts the ballots of an election.
+ */
+export type TallyMode = "Mixnet" | { Homomorphic: { options: number } };
+
 /**
  * Create a new election on DaoChain.
  */
@@ -295,6 +327,8 @@ export async function createElectionTx(
 	registrationDeadline: number,
 	votingDeadline: number,
 	transportConfig?: TransportConfig,
+	revotePolicy: RevotePolicy = "RejectRevote",
+	tallyMode: TallyMode = "Mixnet",
 ): Promise<string> {
 	const { api, admin, tally } = clients;
 
@@ -303,6 +337,8 @@ export async function createElectionTx(
 		tally.address, // tally_authority
 		registrationDeadline,
 		votingDeadline,
+		revotePolicy,
+		tallyMode,
 	);
 
 	const hash = await submitExtrinsic(api, admin, tx, transportConfig);
@@ -0,0 +1,33 @@
+import { describe, it, expect } from "vitest";
+import { buildMerkleRoot } from "../src/orchestrator";
+import { HexString } from "../src/shared";
+
+function ballots(count: number): HexString[] {
+	return Array.from(
+		{ length: count },
+		(_, i) => `0x${Buffer.from(`ballot-${i}`).toString("hex")}` as HexString,
+	);
+}
+
+// The same roots are pinned in `pallets/daomix-voting/src/merkle.rs`, which builds the
+// input root the mix commitments are checked against.
+describe("buildMerkleRoot", () => {
+	it("is all zeroes without ballots", () => {
+		expect(buildMerkleRoot(ballots(0))).toBe(`0x${"00".repeat(32)}`);
+	});
+
+	it("is the leaf of a single ballot", () => {
+		expect(buildMerkleRoot(ballots(1))).toBe(
+			"0x0166671d2301d93d0fc12659e86b4e285bd21602d3258162649498c4e2c96b0b",
+		);
+	});
+
+	it("moves the last node of an odd level up unchanged", () => {
+		expect(buildMerkleRoot(ballots(3))).toBe(
+			"0xd7d34d52ef045d74107fd6849f0fddf85dc5b0429419a0c6350827c6be1e7db9",
+		);
+		expect(buildMerkleRoot(ballots(5))).toBe(
+			"0x8ad5e4239c38ef89abc7e5109a915019293d561d9b8f2a40bc7174d3faa89bcc",
+		);
+	});
+});
@@ -27,7 +27,7 @@ async function main() {
     console.log(`  - Next Job ID: ${nextJobId.toString()}`);
 
     // Check extrinsics
-    const submitJobTx = api.tx.mixJob.submitJob(42);
+    const submitJobTx = api.tx.mixJob.submitJob({ Election: 42 });
     console.log(`\n✓ MixJob Extrinsics:`);
     console.log(`  - submitJob hash: ${submitJobTx.hash.toHex()}`);
   }
@@ -0,0 +1,217 @@
+//! `daomix_*` RPC methods, served from the `DaoMixApi` runtime API.
+//!
+//! They let the orchestrator and front ends page through ballots and jobs without one storage
+//! query per item.
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::{error::ErrorObject, ErrorObjectOwned},
+};
+use pallet_daomix_voting::{
+	runtime_api::DaoMixApi as DaoMixRuntimeApi, BallotIndex, ElectionId, ElectionStatus,
+};
+use pallet_mix_job::{JobId, JobStatus, MixJobInfo};
+use parachain_template_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash};
+use serde::{Deserialize, Serialize};
+
+use polkadot_sdk::*;
+
+use sp_api::{ApiError, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256};
+use sp_runtime::traits::Block as BlockT;
+
+/// Error code of a failed runtime API call.
+const RUNTIME_ERROR: i32 = 1;
+
+/// A mix job as returned by the `daomix_*` methods.
+pub type MixJob = MixJobInfo<AccountId, BlockNumber, Hash, Balance>;
+
+/// Merkle inclusion proof of a ballot, as returned by `daomix_ballotProof`.
+///
+/// Check it with `pallet_daomix_voting::merkle::verify_proof`, which hashes each `proof` hash on
+/// the side given by `position`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BallotProof {
+	/// Index of the ballot.
+	pub index: BallotIndex,
+	/// Keccak-256 hash of the ballot ciphertext.
+	pub leaf: H256,
+	/// Position of the leaf among the valid ballots of the election.
+	pub position: u32,
+	/// Number of valid ballots of the election.
+	pub leaf_count: u32,
+	/// Sibling hashes from the leaf up to the root.
+	pub proof: Vec<H256>,
+	/// Input root of the election.
+	pub root: H256,
+}
+
+impl From<pallet_daomix_voting::BallotProof> for BallotProof {
+	fn from(proof: pallet_daomix_voting::BallotProof) -> Self {
+		Self {
+			index: proof.index,
+			leaf: proof.leaf.into(),
+			position: proof.position,
+			leaf_count: proof.leaf_count,
+			proof: proof.proof.into_iter().map(Into::into).collect(),
+			root: proof.root.into(),
+		}
+	}
+}
+
+/// DaoMix RPC methods. Every method reads the best block unless `at` is given.
+#[rpc(client, server)]
+pub trait DaoMixApi<BlockHash> {
+	/// Ballots of an election with index in `start..start + limit`, skipping replaced ballots.
+	#[method(name = "daomix_ballots")]
+	fn ballots(
+		&self,
+		election_id: ElectionId,
+		start: BallotIndex,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(BallotIndex, Bytes)>>;
+
+	/// Merkle inclusion proof of a ballot in the input root of its election.
+	#[method(name = "daomix_ballotProof")]
+	fn ballot_proof(
+		&self,
+		election_id: ElectionId,
+		index: BallotIndex,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<BallotProof>>;
+
+	/// Phase, deadlines and ballot counts of an election.
+	#[method(name = "daomix_electionStatus")]
+	fn election_status(
+		&self,
+		election_id: ElectionId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<ElectionStatus<BlockNumber>>>;
+
+	/// Jobs with `status` and an id in `start..start + limit`, in id order; at most
+	/// `MAX_JOBS_PER_PAGE` ids are scanned, so page by advancing `start` by `limit`.
+	#[method(name = "daomix_jobsByStatus")]
+	fn jobs_by_status(
+		&self,
+		status: JobStatus,
+		start: JobId,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<MixJob>>;
+
+	/// Stored jobs of an election, in id order.
+	#[method(name = "daomix_jobsForElection")]
+	fn jobs_for_election(
+		&self,
+		election_id: ElectionId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<MixJob>>;
+}
+
+/// Implementation of the `daomix_*` RPC methods.
+pub struct DaoMix<C> {
+	client: Arc<C>,
+}
+
+impl<C> DaoMix<C> {
+	/// Create a new `DaoMix` RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+impl<C: HeaderBackend<Block>> DaoMix<C> {
+	/// The requested block, or the best block.
+	fn at(&self, at: Option<<Block as BlockT>::Hash>) -> <Block as BlockT>::Hash {
+		at.unwrap_or_else(|| self.client.info().best_hash)
+	}
+}
+
+impl<C> DaoMixApiServer<<Block as BlockT>::Hash> for DaoMix<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: DaoMixRuntimeApi<Block, AccountId, Hash, BlockNumber, Balance>,
+{
+	fn ballots(
+		&self,
+		election_id: ElectionId,
+		start: BallotIndex,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(BallotIndex, Bytes)>> {
+		let ballots = self
+			.client
+			.runtime_api()
+			.ballots(self.at(at), election_id, start, limit)
+			.map_err(runtime_error)?;
+
+		Ok(ballots
+			.into_iter()
+			.map(|(index, ciphertext)| (index, ciphertext.into_inner().into()))
+			.collect())
+	}
+
+	fn ballot_proof(
+		&self,
+		election_id: ElectionId,
+		index: BallotIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<BallotProof>> {
+		let proof = self
+			.client
+			.runtime_api()
+			.ballot_proof(self.at(at), election_id, index)
+			.map_err(runtime_error)?;
+
+		Ok(proof.map(Into::into))
+	}
+
+	fn election_status(
+		&self,
+		election_id: ElectionId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<ElectionStatus<BlockNumber>>> {
+		self.client
+			.runtime_api()
+			.election_status(self.at(at), election_id)
+			.map_err(runtime_error)
+	}
+
+	fn jobs_by_status(
+		&self,
+		status: JobStatus,
+		start: JobId,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<MixJob>> {
+		self.client
+			.runtime_api()
+			.jobs_by_status(self.at(at), status, start, limit)
+			.map_err(runtime_error)
+	}
+
+	fn jobs_for_election(
+		&self,
+		election_id: ElectionId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<MixJob>> {
+		self.client
+			.runtime_api()
+			.jobs_for_election(self.at(at), election_id)
+			.map_err(runtime_error)
+	}
+}
+
+fn runtime_error(err: ApiError) -> ErrorObjectOwned {
+	ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Unable to query the DaoMix runtime API.",
+		Some(err.to_string()),
+	)
+}
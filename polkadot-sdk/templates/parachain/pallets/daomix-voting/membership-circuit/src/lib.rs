@@ -0,0 +1,400 @@
+//! Semaphore-style membership circuit of DaoMix anonymous elections.
+//!
+//! A voter's identity is a secret field element `s`. The election admin commits to the Merkle
+//! root of its voters' identity commitments `H(s, 0)` (`set_voter_allowlist` in
+//! `pallet-daomix-voting`), and a voter proves, without revealing `s` or its leaf, that:
+//!
+//! - `H(s, 0)` is a leaf of the tree of depth `DEPTH` under the public `root`,
+//! - the public `nullifier` is `H(s, scope)`, so an identity has one nullifier per election.
+//!
+//! The proof is also bound to the public `signal`, the hash of the ballot ciphertext. The public
+//! inputs are `[root, nullifier, signal, scope]`, as built by `membership::public_inputs` in the
+//! pallet, and the pallet's `groth16` module checks proofs under the verifying key of the setup
+//! whose proving key is `keys/membership.pk`.
+//!
+//! `H` is the MiMC compression `H(l, r) = E_l(r) + l + r` over the BLS12-381 scalar field, where
+//! `E_k` is the MiMC cipher with exponent 5 and `ROUNDS` rounds. Empty leaves are zero.
+
+use ark_bls12_381::Bls12_381;
+use ark_ff::{BigInteger, Field, PrimeField};
+use ark_groth16::{Groth16, Proof, ProvingKey, VerifyingKey};
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_snark::SNARK;
+use rand::{CryptoRng, RngCore};
+use sha3::{Digest, Keccak256};
+
+/// Scalar field of BLS12-381, which the circuit is over.
+pub type Fr = ark_bls12_381::Fr;
+
+/// Depth of the allowlist tree, so an election has at most `2^DEPTH` voters.
+pub const DEPTH: usize = 16;
+
+/// Rounds of the MiMC cipher: `ceil(255 / log2(5))`, as the field has 255 bits.
+pub const ROUNDS: usize = 110;
+
+/// A field element as the pallet takes it: 32 bytes, little-endian.
+pub type FieldBytes = [u8; 32];
+
+/// A field element in the pallet's encoding.
+pub fn to_bytes(element: Fr) -> FieldBytes {
+	let mut bytes = [0; 32];
+	bytes.copy_from_slice(&element.into_bigint().to_bytes_le());
+	bytes
+}
+
+/// The field element `bytes` encodes, or `None` if it is not below the modulus.
+pub fn from_bytes(bytes: &FieldBytes) -> Option<Fr> {
+	Fr::deserialize_compressed(&bytes[..]).ok()
+}
+
+/// A 32-byte hash as a field element, with its top byte cleared as the pallet's `to_field` does.
+fn hash_to_field(data: &[u8]) -> Fr {
+	let mut hash: [u8; 32] = Keccak256::digest(data).into();
+	hash[31] = 0;
+	Fr::from_le_bytes_mod_order(&hash)
+}
+
+/// Scope of the nullifiers of election `election_id`, as the pallet's `membership::scope`.
+pub fn scope(election_id: u32) -> Fr {
+	// SCALE encoding of `(b"daomix/election", election_id)`
+	hash_to_field(&[&b"daomix/election"[..], &election_id.to_le_bytes()].concat())
+}
+
+/// Signal of a ballot: the hash of its ciphertext.
+pub fn signal(ciphertext: &[u8]) -> Fr {
+	hash_to_field(ciphertext)
+}
+
+/// Round constants of the MiMC cipher: zero, then hashes of `(b"daomix/mimc", round)`.
+pub fn round_constants() -> Vec<Fr> {
+	let mut constants = vec![Fr::from(0u64)];
+	constants.extend((1..ROUNDS as u32).map(|round| {
+		let hash = Keccak256::digest([&b"daomix/mimc"[..], &round.to_le_bytes()].concat());
+		Fr::from_le_bytes_mod_order(&hash)
+	}));
+	constants
+}
+
+/// The MiMC compression of `left` and `right`.
+pub fn hash(left: Fr, right: Fr) -> Fr {
+	let mut state = right;
+	for constant in round_constants() {
+		state = (state + left + constant).pow([5]);
+	}
+	state + left + left + right
+}
+
+/// Commitment to the identity `secret`, a leaf of the allowlist tree.
+pub fn identity_commitment(secret: Fr) -> Fr {
+	hash(secret, Fr::from(0u64))
+}
+
+/// Nullifier of the identity `secret` in the election of scope `scope`.
+pub fn nullifier(secret: Fr, scope: Fr) -> Fr {
+	hash(secret, scope)
+}
+
+/// The allowlist tree of some identity commitments, padded with empty leaves.
+pub struct Tree {
+	/// Nodes of each level, leaves first, without the empty nodes right of the last leaf.
+	levels: Vec<Vec<Fr>>,
+	/// Root of an empty subtree of each height.
+	empty: Vec<Fr>,
+}
+
+impl Tree {
+	/// The tree of `commitments`, or `None` if there are more than `2^DEPTH`.
+	pub fn new(commitments: &[Fr]) -> Option<Self> {
+		if commitments.len() > 1 << DEPTH {
+			return None
+		}
+		let mut empty = vec![Fr::from(0u64)];
+		for height in 0..DEPTH {
+			empty.push(hash(empty[height], empty[height]));
+		}
+		let mut levels = vec![commitments.to_vec()];
+		for height in 0..DEPTH {
+			let parents = levels[height]
+				.chunks(2)
+				.map(|pair| hash(pair[0], pair.get(1).copied().unwrap_or(empty[height])))
+				.collect();
+			levels.push(parents);
+		}
+		Some(Self { levels, empty })
+	}
+
+	/// The root.
+	pub fn root(&self) -> Fr {
+		self.levels[DEPTH].first().copied().unwrap_or(self.empty[DEPTH])
+	}
+
+	/// Path from leaf `index` to the root, or `None` if there is no such leaf.
+	pub fn path(&self, index: usize) -> Option<MerklePath> {
+		if index >= self.levels[0].len() {
+			return None
+		}
+		let siblings = (0..DEPTH)
+			.map(|height| {
+				let sibling = (index >> height) ^ 1;
+				self.levels[height].get(sibling).copied().unwrap_or(self.empty[height])
+			})
+			.collect();
+		Some(MerklePath { siblings, index: index as u32 })
+	}
+}
+
+/// Siblings of a leaf on its way to the root.
+#[derive(Clone)]
+pub struct MerklePath {
+	/// Sibling at each height, from the leaves up.
+	pub siblings: Vec<Fr>,
+	/// Index of the leaf, whose bit `h` tells whether the node at height `h` is a right child.
+	pub index: u32,
+}
+
+/// A membership statement and its witness.
+#[derive(Clone)]
+pub struct MembershipCircuit {
+	/// Public: root of the allowlist tree.
+	pub root: Fr,
+	/// Public: nullifier of the identity in the election.
+	pub nullifier: Fr,
+	/// Public: signal the proof is bound to.
+	pub signal: Fr,
+	/// Public: scope of the election.
+	pub scope: Fr,
+	/// Identity secret.
+	pub secret: Fr,
+	/// Path from the identity commitment to the root.
+	pub path: MerklePath,
+}
+
+impl MembershipCircuit {
+	/// A circuit of the right shape, for the setup.
+	pub fn blank() -> Self {
+		let zero = Fr::from(0u64);
+		Self {
+			root: zero,
+			nullifier: zero,
+			signal: zero,
+			scope: zero,
+			secret: zero,
+			path: MerklePath { siblings: vec![zero; DEPTH], index: 0 },
+		}
+	}
+
+	/// Public inputs of the statement, in the order the verifier takes them.
+	pub fn public_inputs(&self) -> [Fr; 4] {
+		[self.root, self.nullifier, self.signal, self.scope]
+	}
+}
+
+/// The MiMC compression of `left` and `right` in the circuit: three constraints per round.
+fn hash_gadget(
+	left: &FpVar<Fr>,
+	right: &FpVar<Fr>,
+	constants: &[Fr],
+) -> Result<FpVar<Fr>, SynthesisError> {
+	let mut state = right.clone();
+	for constant in constants {
+		let base = &state + left + *constant;
+		state = base.square()?.square()? * &base;
+	}
+	Ok(state + left + left + right)
+}
+
+impl ConstraintSynthesizer<Fr> for MembershipCircuit {
+	fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+		if self.path.siblings.len() != DEPTH {
+			return Err(SynthesisError::Unsatisfiable)
+		}
+		let constants = round_constants();
+
+		// Public inputs are allocated in the verifier's order
+		let root = FpVar::new_input(cs.clone(), || Ok(self.root))?;
+		let nullifier = FpVar::new_input(cs.clone(), || Ok(self.nullifier))?;
+		let signal = FpVar::new_input(cs.clone(), || Ok(self.signal))?;
+		let scope = FpVar::new_input(cs.clone(), || Ok(self.scope))?;
+
+		let secret = FpVar::new_witness(cs.clone(), || Ok(self.secret))?;
+		let zero = FpVar::zero();
+		let mut node = hash_gadget(&secret, &zero, &constants)?;
+		for (height, sibling) in self.path.siblings.iter().enumerate() {
+			let sibling = FpVar::new_witness(cs.clone(), || Ok(*sibling))?;
+			let is_right =
+				Boolean::new_witness(cs.clone(), || Ok(self.path.index >> height & 1 == 1))?;
+			let left = is_right.select(&sibling, &node)?;
+			let right = is_right.select(&node, &sibling)?;
+			node = hash_gadget(&left, &right, &constants)?;
+		}
+		node.enforce_equal(&root)?;
+
+		hash_gadget(&secret, &scope, &constants)?.enforce_equal(&nullifier)?;
+
+		// Constrain the signal, so the proof cannot be replayed with another one
+		let _ = signal.square()?;
+
+		Ok(())
+	}
+}
+
+/// Run the setup of the circuit. Whoever learns `rng`'s output can forge proofs.
+pub fn setup<R: RngCore + CryptoRng>(rng: &mut R) -> Result<ProvingKey<Bls12_381>, SynthesisError> {
+	let (proving_key, _) =
+		Groth16::<Bls12_381>::circuit_specific_setup(MembershipCircuit::blank(), rng)?;
+	Ok(proving_key)
+}
+
+/// A proof that the identity `secret`, the leaf at `path`, votes `ciphertext` in election
+/// `election_id` under the allowlist root `path` leads to, with the statement it proves.
+pub fn prove<R: RngCore + CryptoRng>(
+	proving_key: &ProvingKey<Bls12_381>,
+	secret: Fr,
+	path: MerklePath,
+	election_id: u32,
+	ciphertext: &[u8],
+	rng: &mut R,
+) -> Result<(MembershipCircuit, Proof<Bls12_381>), SynthesisError> {
+	let scope = scope(election_id);
+	let mut root = identity_commitment(secret);
+	for (height, sibling) in path.siblings.iter().enumerate() {
+		root = match path.index >> height & 1 {
+			0 => hash(root, *sibling),
+			_ => hash(*sibling, root),
+		};
+	}
+	let circuit = MembershipCircuit {
+		root,
+		nullifier: nullifier(secret, scope),
+		signal: signal(ciphertext),
+		scope,
+		secret,
+		path,
+	};
+	let proof = Groth16::<Bls12_381>::prove(proving_key, circuit.clone(), rng)?;
+	Ok((circuit, proof))
+}
+
+/// Whether `proof` is valid for `public_inputs` under `verifying_key`.
+pub fn verify(
+	verifying_key: &VerifyingKey<Bls12_381>,
+	public_inputs: &[Fr; 4],
+	proof: &Proof<Bls12_381>,
+) -> bool {
+	Groth16::<Bls12_381>::verify(verifying_key, public_inputs, proof).unwrap_or(false)
+}
+
+fn compressed<T: CanonicalSerialize>(point: &T) -> Vec<u8> {
+	let mut bytes = Vec::new();
+	point
+		.serialize_compressed(&mut bytes)
+		.expect("writing to a vector does not fail");
+	bytes
+}
+
+/// A proof as the pallet takes it: the SCALE encoding of its `Groth16Proof`, `A`, `B` and `C`
+/// compressed.
+pub fn proof_bytes(proof: &Proof<Bls12_381>) -> Vec<u8> {
+	[compressed(&proof.a), compressed(&proof.b), compressed(&proof.c)].concat()
+}
+
+/// A verifying key as Rust source for the pallet: a `Groth16VerifyingKey` expression.
+pub fn verifying_key_source(verifying_key: &VerifyingKey<Bls12_381>) -> String {
+	let bytes = |point: Vec<u8>| {
+		let bytes: Vec<_> = point.iter().map(|byte| format!("0x{byte:02x}")).collect();
+		format!("[{}]", bytes.join(", "))
+	};
+	let ic: Vec<_> = verifying_key
+		.gamma_abc_g1
+		.iter()
+		.map(|point| bytes(compressed(point)))
+		.collect();
+	format!(
+		"Groth16VerifyingKey {{ alpha_g1: {}, beta_g2: {}, gamma_g2: {}, delta_g2: {}, ic: [{}] }}",
+		bytes(compressed(&verifying_key.alpha_g1)),
+		bytes(compressed(&verifying_key.beta_g2)),
+		bytes(compressed(&verifying_key.gamma_g2)),
+		bytes(compressed(&verifying_key.delta_g2)),
+		ic.join(", "),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::{rngs::StdRng, SeedableRng};
+
+	#[test]
+	fn tree_paths_lead_to_the_root() {
+		let commitments: Vec<_> =
+			(1..=5u64).map(|secret| identity_commitment(secret.into())).collect();
+		let tree = Tree::new(&commitments).unwrap();
+		for (index, commitment) in commitments.iter().enumerate() {
+			let path = tree.path(index).unwrap();
+			let mut node = *commitment;
+			for (height, sibling) in path.siblings.iter().enumerate() {
+				node = match index >> height & 1 {
+					0 => hash(node, *sibling),
+					_ => hash(*sibling, node),
+				};
+			}
+			assert_eq!(node, tree.root());
+		}
+		assert!(tree.path(5).is_none());
+	}
+
+	#[test]
+	fn proofs_verify_for_their_statement_only() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let proving_key = setup(&mut rng).unwrap();
+		let secrets: Vec<Fr> = (1..=3u64).map(Fr::from).collect();
+		let commitments: Vec<_> =
+			secrets.iter().map(|secret| identity_commitment(*secret)).collect();
+		let tree = Tree::new(&commitments).unwrap();
+
+		let (circuit, proof) =
+			prove(&proving_key, secrets[1], tree.path(1).unwrap(), 7, b"ballot", &mut rng).unwrap();
+		assert_eq!(circuit.root, tree.root());
+		assert_eq!(circuit.nullifier, nullifier(secrets[1], scope(7)));
+		let inputs = circuit.public_inputs();
+		assert!(verify(&proving_key.vk, &inputs, &proof));
+
+		for input in 0..4 {
+			let mut other = inputs;
+			other[input] += Fr::from(1u64);
+			assert!(!verify(&proving_key.vk, &other, &proof));
+		}
+
+		// A secret off the allowlist leads to another root
+		let (circuit, proof) =
+			prove(&proving_key, Fr::from(9u64), tree.path(1).unwrap(), 7, b"ballot", &mut rng)
+				.unwrap();
+		assert_ne!(circuit.root, tree.root());
+		let forged = [tree.root(), circuit.nullifier, circuit.signal, circuit.scope];
+		assert!(!verify(&proving_key.vk, &forged, &proof));
+	}
+
+	#[test]
+	fn the_committed_proving_key_proves() {
+		let bytes = include_bytes!("../keys/membership.pk");
+		let proving_key = ProvingKey::<Bls12_381>::deserialize_compressed(&bytes[..]).unwrap();
+		let secret = Fr::from(1u64);
+		let tree = Tree::new(&[identity_commitment(secret)]).unwrap();
+		let mut rng = StdRng::seed_from_u64(0);
+		let (circuit, proof) =
+			prove(&proving_key, secret, tree.path(0).unwrap(), 0, b"ballot", &mut rng).unwrap();
+		assert!(verify(&proving_key.vk, &circuit.public_inputs(), &proof));
+	}
+
+	#[test]
+	fn field_encoding_matches_the_pallet() {
+		let element = Fr::from(0x0102u64);
+		let mut bytes = [0; 32];
+		bytes[..2].copy_from_slice(&[0x02, 0x01]);
+		assert_eq!(to_bytes(element), bytes);
+		assert_eq!(from_bytes(&bytes), Some(element));
+		assert_eq!(from_bytes(&[0xff; 32]), None);
+	}
+}
@@ -0,0 +1,115 @@
+//! Setup and prover of the DaoMix membership circuit.
+//!
+//! Field elements are read and printed as the hex of their 32-byte little-endian encoding, the
+//! `FieldElement` of `pallet-daomix-voting`.
+
+use ark_bls12_381::Bls12_381;
+use ark_groth16::ProvingKey;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use daomix_membership_circuit::{self as circuit, Fr, Tree};
+use rand::{rngs::OsRng, RngCore};
+use std::{fs::File, io::BufReader, process::ExitCode};
+
+const USAGE: &str = "\
+usage:
+  daomix-membership setup <proving-key>
+      Run the setup and write the proving key; print the verifying key as Rust source.
+  daomix-membership identity
+      Print a new identity secret and its commitment.
+  daomix-membership root <commitment>...
+      Print the allowlist root of the commitments.
+  daomix-membership prove <proving-key> <election-id> <secret> <ciphertext> <commitment>...
+      Prove that <secret> is on the allowlist of the commitments and votes <ciphertext>
+      (hex) in the election; print the root, the nullifier and the proof.";
+
+fn field(hex: &str) -> Result<Fr, String> {
+	let bytes: [u8; 32] = hex::decode(hex.trim_start_matches("0x"))
+		.ok()
+		.and_then(|bytes| bytes.try_into().ok())
+		.ok_or_else(|| format!("not 32 bytes of hex: {hex}"))?;
+	circuit::from_bytes(&bytes).ok_or_else(|| format!("not a field element: {hex}"))
+}
+
+fn print_field(name: &str, element: Fr) {
+	println!("{name}: 0x{}", hex::encode(circuit::to_bytes(element)));
+}
+
+fn tree(commitments: &[String]) -> Result<(Vec<Fr>, Tree), String> {
+	let commitments = commitments.iter().map(|hex| field(hex)).collect::<Result<Vec<_>, _>>()?;
+	let tree = Tree::new(&commitments).ok_or("too many commitments")?;
+	Ok((commitments, tree))
+}
+
+fn setup(path: &str) -> Result<(), String> {
+	let proving_key = circuit::setup(&mut OsRng).map_err(|e| e.to_string())?;
+	let file = File::create(path).map_err(|e| e.to_string())?;
+	proving_key.serialize_compressed(file).map_err(|e| e.to_string())?;
+	println!("{}", circuit::verifying_key_source(&proving_key.vk));
+	Ok(())
+}
+
+fn identity() -> Result<(), String> {
+	let mut bytes = [0; 64];
+	OsRng.fill_bytes(&mut bytes);
+	let secret = <Fr as ark_ff::PrimeField>::from_le_bytes_mod_order(&bytes);
+	print_field("secret", secret);
+	print_field("commitment", circuit::identity_commitment(secret));
+	Ok(())
+}
+
+fn prove(args: &[String]) -> Result<(), String> {
+	let [proving_key, election_id, secret, ciphertext, commitments @ ..] = args else {
+		return Err(USAGE.into())
+	};
+	let file = File::open(proving_key).map_err(|e| e.to_string())?;
+	let proving_key = ProvingKey::<Bls12_381>::deserialize_compressed(BufReader::new(file))
+		.map_err(|e| e.to_string())?;
+	let election_id =
+		election_id.parse().map_err(|_| format!("not an election id: {election_id}"))?;
+	let secret = field(secret)?;
+	let ciphertext =
+		hex::decode(ciphertext.trim_start_matches("0x")).map_err(|_| "ciphertext is not hex")?;
+
+	let (commitments, tree) = tree(commitments)?;
+	let commitment = circuit::identity_commitment(secret);
+	let index = commitments
+		.iter()
+		.position(|leaf| *leaf == commitment)
+		.ok_or("the secret's commitment is not on the allowlist")?;
+	let path = tree.path(index).ok_or("no path to the commitment")?;
+
+	let (statement, proof) =
+		circuit::prove(&proving_key, secret, path, election_id, &ciphertext, &mut OsRng)
+			.map_err(|e| e.to_string())?;
+	if !circuit::verify(&proving_key.vk, &statement.public_inputs(), &proof) {
+		return Err("the proof does not verify".into())
+	}
+	print_field("root", statement.root);
+	print_field("nullifier", statement.nullifier);
+	println!("proof: 0x{}", hex::encode(circuit::proof_bytes(&proof)));
+	Ok(())
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+	match args {
+		[command, path] if command == "setup" => setup(path),
+		[command] if command == "identity" => identity(),
+		[command, commitments @ ..] if command == "root" => {
+			print_field("root", tree(commitments)?.1.root());
+			Ok(())
+		},
+		[command, args @ ..] if command == "prove" => prove(args),
+		_ => Err(USAGE.into()),
+	}
+}
+
+fn main() -> ExitCode {
+	let args: Vec<String> = std::env::args().skip(1).collect();
+	match run(&args) {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(error) => {
+			eprintln!("{error}");
+			ExitCode::FAILURE
+		},
+	}
+}
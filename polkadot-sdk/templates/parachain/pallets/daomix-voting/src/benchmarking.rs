@@ -0,0 +1,950 @@
+//! Benchmarking setup for pallet-daomix-voting
+
+use super::*;
+use crate::{
+	dkg::GroupElement,
+	elgamal, membership,
+	merkle::{self, MerkleHash},
+	shuffle::{
+		self, ChallengeSide, HopCommitment, HopIndex, LinkChallenge, LinkOpening, MerkleProof,
+		ShuffleLinkVerifier,
+	},
+};
+use curve25519_dalek::{constants::RISTRETTO_BASEPOINT_COMPRESSED, scalar::Scalar};
+use frame::{deps::frame_benchmarking::v2::*, prelude::*};
+use sp_std::vec::Vec;
+
+/// Generator of Ristretto255, a valid commitment and committee key.
+const GENERATOR: GroupElement = RISTRETTO_BASEPOINT_COMPRESSED.0;
+
+/// Give `who` enough to hold an election deposit, a tally bond and a dispute bond and to pay for
+/// a feeless ballot, several times over.
+fn fund<T: Config>(who: &T::AccountId) {
+	let needed = T::Currency::minimum_balance()
+		.saturating_add(T::ElectionDeposit::get())
+		.saturating_add(T::TallyBond::get())
+		.saturating_add(T::DisputeBond::get())
+		.saturating_add(T::FeelessBallotFee::get());
+	T::Currency::set_balance(who, needed.saturating_mul(4u32.into()));
+}
+
+/// Fill `PhaseSchedule` from block `at` on, so a transition scheduled there skips every full
+/// block it may.
+fn fill_schedule<T: Config>(mut at: BlockNumberFor<T>) {
+	let full = sp_std::vec![ElectionId::MAX; T::MaxPhaseTransitionsPerBlock::get() as usize];
+	for _ in 0..MAX_SCHEDULE_SPILL {
+		PhaseSchedule::<T>::insert(at, BoundedVec::truncate_from(full.clone()));
+		at.saturating_inc();
+	}
+}
+
+/// Insert a mixnet election of `admin`, also its tally authority, in `phase`, holding its
+/// deposit.
+fn mixnet_election<T: Config>(
+	election_id: ElectionId,
+	admin: &T::AccountId,
+	phase: ElectionPhase,
+) -> Result<(), BenchmarkError> {
+	fund::<T>(admin);
+	let deposit = T::ElectionDeposit::get();
+	T::Currency::hold(&HoldReason::ElectionDeposit.into(), admin, deposit)?;
+	let election = Election::<T> {
+		admin: admin.clone(),
+		deposit,
+		tally_authority: admin.clone(),
+		registration_deadline: 1_000u32.into(),
+		voting_deadline: 2_000u32.into(),
+		revote_policy: RevotePolicy::RejectRevote,
+		tally_mode: TallyMode::Mixnet,
+		keys: None,
+		committee: None,
+		commitment_input_root: None,
+		commitment_output_root: None,
+		phase,
+	};
+	Elections::<T>::insert(election_id, election);
+	Ok(())
+}
+
+/// Insert a mixnet election of `authority` in its disputable phase, with a tally holding its
+/// bond whose challenge window closes at the current block, and `disputes` open disputes
+/// holding theirs, the first of them on the mix commitments.
+fn disputed_election<T: Config>(
+	election_id: ElectionId,
+	authority: &T::AccountId,
+	disputes: u32,
+) -> Result<(), BenchmarkError> {
+	mixnet_election::<T>(election_id, authority, ElectionPhase::Disputable)?;
+	let bond = T::TallyBond::get();
+	T::Currency::hold(&HoldReason::TallyBond.into(), authority, bond)?;
+	let tally = TallyResult::<T> {
+		result_uri: BoundedVec::truncate_from(sp_std::vec![0; 256]),
+		result_hash: T::Hashing::hash(b"result"),
+		challenge_deadline: frame_system::Pallet::<T>::block_number(),
+		bond,
+	};
+	TallyResults::<T>::insert(election_id, tally);
+
+	let mut open = Vec::new();
+	for index in 0..disputes {
+		let challenger: T::AccountId = account("challenger", index, 0);
+		fund::<T>(&challenger);
+		let bond = T::DisputeBond::get();
+		T::Currency::hold(&HoldReason::DisputeBond.into(), &challenger, bond)?;
+		let evidence = if index == 0 {
+			DisputeEvidence::RootMismatch { expected: [0; 32], committed: [1; 32] }
+		} else {
+			let evidence_uri = BoundedVec::truncate_from(sp_std::vec![0; 256]);
+			DisputeEvidence::InvalidDecryption { leaf: [0; 32], evidence_uri }
+		};
+		open.push(Dispute { challenger, bond, evidence });
+	}
+	Disputes::<T>::insert(election_id, BoundedVec::truncate_from(open));
+	Ok(())
+}
+
+/// Phase of an election, if it exists.
+fn phase<T: Config>(election_id: ElectionId) -> Option<ElectionPhase> {
+	Elections::<T>::get(election_id).map(|election| election.phase)
+}
+
+/// Accounts of a tally committee of `count` trustees.
+fn trustees<T: Config>(count: u32) -> Vec<T::AccountId> {
+	(0..count).map(|index| account("trustee", index, 0)).collect()
+}
+
+/// A tally committee of `trustees` with `threshold`, whose DKG starts at the current block and
+/// established `public_key`, if any.
+fn committee<T: Config>(
+	trustees: &[T::AccountId],
+	threshold: u32,
+	public_key: Option<GroupElement>,
+) -> TallyCommittee<T> {
+	let trustees = trustees.iter().map(|trustee| (trustee.clone(), [0; 32])).collect::<Vec<_>>();
+	TallyCommittee {
+		trustees: BoundedVec::truncate_from(trustees),
+		threshold,
+		dkg_start: frame_system::Pallet::<T>::block_number(),
+		commitments: BoundedVec::truncate_from(public_key.into_iter().collect()),
+		public_key,
+	}
+}
+
+/// A DKG deal of `threshold` commitments to the generator and `trustees` shares of the longest
+/// length.
+fn deal<T: Config>(trustees: u32, threshold: u32) -> DkgDeal<T> {
+	DkgDeal {
+		commitments: BoundedVec::truncate_from(sp_std::vec![GENERATOR; threshold as usize]),
+		encrypted_shares: BoundedVec::truncate_from(sp_std::vec![
+			EncryptedShare::truncate_from(sp_std::vec![0; 128]);
+			trustees as usize
+		]),
+	}
+}
+
+/// Insert a homomorphic election of `admin` with `options` options in `phase`, handed to
+/// `committee`.
+fn committee_election<T: Config>(
+	election_id: ElectionId,
+	admin: &T::AccountId,
+	options: u8,
+	phase: ElectionPhase,
+	committee: TallyCommittee<T>,
+) {
+	let election = Election::<T> {
+		admin: admin.clone(),
+		deposit: Zero::zero(),
+		tally_authority: admin.clone(),
+		registration_deadline: Zero::zero(),
+		voting_deadline: 1_000u32.into(),
+		revote_policy: RevotePolicy::LastVoteWins,
+		tally_mode: TallyMode::Homomorphic { options },
+		keys: None,
+		committee: Some(committee),
+		commitment_input_root: None,
+		commitment_output_root: None,
+		phase,
+	};
+	Elections::<T>::insert(election_id, election);
+}
+
+/// The share at `index` of a polynomial of `threshold` coefficients of one, which `deal`
+/// commits to.
+fn dealt_share(index: u32, threshold: u32) -> Scalar {
+	(0..threshold)
+		.scan(Scalar::ONE, |power, _| {
+			let term = *power;
+			*power *= Scalar::from(index);
+			Some(term)
+		})
+		.sum()
+}
+
+/// An established tally committee of `MaxTrustees` trustees with `threshold`, whose committee
+/// polynomial has every coefficient committed to the generator.
+fn established_committee<T: Config>(threshold: u32) -> (Vec<T::AccountId>, TallyCommittee<T>) {
+	let trustees = trustees::<T>(T::MaxTrustees::get());
+	let mut committee = committee::<T>(&trustees, threshold, Some(GENERATOR));
+	committee.commitments = BoundedVec::truncate_from(sp_std::vec![GENERATOR; threshold as usize]);
+	(trustees, committee)
+}
+
+/// Insert a homomorphic election of `admin` with `options` options in its voting phase, with a
+/// committee key of `public_key` and a funded pot.
+fn voting_election<T: Config>(
+	election_id: ElectionId,
+	admin: &T::AccountId,
+	options: u8,
+	public_key: GroupElement,
+) {
+	let committee = committee::<T>(&[], 1, Some(public_key));
+	committee_election::<T>(election_id, admin, options, ElectionPhase::Voting, committee);
+
+	// Fund the pot, so ballots are feeless and it is charged
+	let fee = T::FeelessBallotFee::get();
+	let balance = T::Currency::minimum_balance().saturating_add(fee.saturating_mul(4u32.into()));
+	T::Currency::set_balance(&Pallet::<T>::pot_account(election_id), balance);
+}
+
+/// Root the mix output of `tallying_election` is committed to.
+const MIX_OUTPUT: MerkleHash = [2; 32];
+
+/// Insert a mixnet election of `authority` in its tallying phase, with a cascade of `hops` hops
+/// over `ballots` valid ballots.
+fn tallying_election<T: Config>(
+	election_id: ElectionId,
+	authority: &T::AccountId,
+	hops: u32,
+	ballots: u32,
+) -> Result<(), BenchmarkError> {
+	let output_root =
+		RootHash::<T>::decode(&mut &MIX_OUTPUT[..]).map_err(|_| BenchmarkError::Weightless)?;
+	let keys = ElectionKeys {
+		mix_keys: BoundedVec::truncate_from(sp_std::vec![[0; 32]; hops as usize]),
+		pq_mix_keys: None,
+		tally_key: [0; 32],
+	};
+	let election = Election::<T> {
+		admin: authority.clone(),
+		deposit: Zero::zero(),
+		tally_authority: authority.clone(),
+		registration_deadline: Zero::zero(),
+		voting_deadline: Zero::zero(),
+		revote_policy: RevotePolicy::RejectRevote,
+		tally_mode: TallyMode::Mixnet,
+		keys: Some(keys),
+		committee: None,
+		commitment_input_root: Some(Default::default()),
+		commitment_output_root: Some(output_root),
+		phase: ElectionPhase::Tallying,
+	};
+	Elections::<T>::insert(election_id, election);
+	ValidBallotCount::<T>::insert(election_id, ballots);
+	Ok(())
+}
+
+/// Root of a tree of `u32::MAX` leaves starting with `leaves`, and the path of each of them.
+///
+/// Only the first leaves are known; every level above them pairs with an arbitrary node, so each
+/// path has the 32 siblings of the deepest proof.
+fn deep_tree(leaves: &[MerkleHash]) -> (MerkleHash, Vec<MerkleProof>) {
+	let mut known = leaves.to_vec();
+	known.resize(leaves.len().next_power_of_two(), [0; 32]);
+	let depth = known.len().trailing_zeros() as u8;
+	let upper = (depth..32).map(|level| [level; 32]).collect::<Vec<_>>();
+
+	let root = merkle::proof(known.clone(), 0)
+		.unwrap_or_default()
+		.iter()
+		.chain(&upper)
+		.fold(known[0], |node, sibling| merkle::hash_pair(&node, sibling));
+	let paths = (0..leaves.len())
+		.map(|position| {
+			let mut path = merkle::proof(known.clone(), position).unwrap_or_default();
+			path.extend_from_slice(&upper);
+			BoundedVec::truncate_from(path)
+		})
+		.collect();
+	(root, paths)
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+	use frame_system::RawOrigin;
+
+	// Worst case: the pot pays for the ballot, which replaces an earlier one taken out of the
+	// encrypted tally.
+	#[benchmark]
+	fn cast_vote(o: Linear<2, { elgamal::MAX_OPTIONS }>) -> Result<(), BenchmarkError> {
+		let voter: T::AccountId = whitelisted_caller();
+		let election_id = 0;
+		let public_key = GENERATOR;
+		voting_election::<T>(election_id, &voter, o as u8, public_key);
+		Voters::<T>::insert(election_id, &voter, true);
+
+		let context = Pallet::<T>::ballot_context(election_id, &BallotSender::Voter(voter.clone()));
+		let ballot = |seed: &[u8]| {
+			elgamal::prove_ballot(&public_key, &context, o as u8, 0, seed)
+				.map(|ballot| ballot.encode())
+				.ok_or(BenchmarkError::Stop("ballot"))
+		};
+		Pallet::<T>::cast_vote(
+			RawOrigin::Signed(voter.clone()).into(),
+			election_id,
+			ballot(b"first")?,
+		)
+		.map_err(|e| e.error)?;
+		let ciphertext = ballot(b"second")?;
+
+		#[extrinsic_call]
+		cast_vote(RawOrigin::Signed(voter), election_id, ciphertext);
+
+		assert_eq!(BallotCount::<T>::get(election_id), 2);
+		assert!(EncryptedTallies::<T>::contains_key(election_id));
+		assert_eq!(
+			FeelessBallots::<T>::get(election_id, BallotSender::Voter(whitelisted_caller())),
+			2
+		);
+		Ok(())
+	}
+
+	// Worst case: the pot pays for an unsigned ballot, whose membership proof is checked under
+	// a Groth16 key, and which replaces an earlier one taken out of the encrypted tally.
+	#[benchmark]
+	fn cast_anonymous_vote(o: Linear<2, { elgamal::MAX_OPTIONS }>) -> Result<(), BenchmarkError> {
+		let election_id = 0;
+		let public_key = GENERATOR;
+		voting_election::<T>(election_id, &whitelisted_caller(), o as u8, public_key);
+		let (root, nullifier) = ([1; 32], [2; 32]);
+		VoterAllowlists::<T>::insert(election_id, root);
+
+		let context = Pallet::<T>::ballot_context(election_id, &BallotSender::Nullifier(nullifier));
+		let ballot = |seed: &[u8]| -> Result<(Vec<u8>, Vec<u8>), BenchmarkError> {
+			let ciphertext = elgamal::prove_ballot(&public_key, &context, o as u8, 0, seed)
+				.ok_or(BenchmarkError::Stop("ballot"))?
+				.encode();
+			let public_inputs =
+				membership::public_inputs(election_id, &root, &nullifier, &ciphertext);
+			let proof = T::MembershipVerifier::prove(&public_inputs)
+				.ok_or(BenchmarkError::Stop("membership proof"))?;
+			Ok((proof, ciphertext))
+		};
+		let (proof, ciphertext) = ballot(b"first")?;
+		Pallet::<T>::cast_anonymous_vote(
+			RawOrigin::None.into(),
+			election_id,
+			root,
+			nullifier,
+			proof,
+			ciphertext,
+		)
+		.map_err(|e| e.error)?;
+		let (proof, ciphertext) = ballot(b"second")?;
+
+		#[extrinsic_call]
+		cast_anonymous_vote(RawOrigin::None, election_id, root, nullifier, proof, ciphertext);
+
+		assert_eq!(BallotCount::<T>::get(election_id), 2);
+		assert_eq!(FeelessBallots::<T>::get(election_id, BallotSender::Nullifier(nullifier)), 2);
+		Ok(())
+	}
+
+	// Worst case: the last hop of the longest cascade is committed, which checks it against the
+	// mix output and sets the seed block.
+	#[benchmark]
+	fn commit_shuffle_hop() -> Result<(), BenchmarkError> {
+		let authority: T::AccountId = whitelisted_caller();
+		let election_id = 0;
+		let hops = <MixCascade<X25519PublicKey>>::bound() as HopIndex;
+		tallying_election::<T>(election_id, &authority, hops.into(), 1)?;
+		let last = hops - 1;
+		let previous =
+			HopCommitment { input_root: [0; 32], output_root: [1; 32], link_root: [0; 32] };
+		ShuffleHops::<T>::insert(election_id, last - 1, previous);
+		ShuffleStages::<T>::insert(election_id, ShuffleStage::Committing { committed: last });
+		let commitment =
+			HopCommitment { input_root: [1; 32], output_root: MIX_OUTPUT, link_root: [0; 32] };
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(authority), election_id, last, commitment);
+
+		assert!(matches!(
+			ShuffleStages::<T>::get(election_id),
+			Some(ShuffleStage::Challenging { .. })
+		));
+		Ok(())
+	}
+
+	// Worst case: every pair of `h` hops draws its challenges.
+	#[benchmark]
+	fn draw_shuffle_challenges(
+		h: Linear<1, { <MixCascade<X25519PublicKey>>::bound() as u32 }>,
+	) -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let election_id = 0;
+		tallying_election::<T>(election_id, &caller, h, u32::MAX)?;
+		let seed_block = frame_system::Pallet::<T>::block_number();
+		ShuffleStages::<T>::insert(election_id, ShuffleStage::Challenging { seed_block });
+		frame_system::BlockHash::<T>::insert(seed_block, T::Hashing::hash(b"seed"));
+		frame_system::Pallet::<T>::set_block_number(seed_block.saturating_add(One::one()));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), election_id);
+
+		assert!(matches!(ShuffleStages::<T>::get(election_id), Some(ShuffleStage::Proving { .. })));
+		Ok(())
+	}
+
+	// Worst case: `l` openings with paths of the full depth each scan every challenge of the
+	// hop, and answering the last ones proves the shuffle.
+	#[benchmark]
+	fn prove_shuffle_links(
+		l: Linear<1, { T::MaxLinksPerProof::get().min(T::ShuffleChallengesPerPair::get()) }>,
+	) -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (election_id, hop) = (0, 0);
+		tallying_election::<T>(election_id, &caller, 1, u32::MAX)?;
+
+		let salt = [3; 32];
+		let (mut inputs, mut outputs, mut evidence, mut links) =
+			(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+		for position in 0..l {
+			let input_leaf = merkle::hash_leaf(&position.encode());
+			let (output_leaf, link_evidence) = T::LinkVerifier::link(hop, &input_leaf)
+				.ok_or(BenchmarkError::Stop("link evidence"))?;
+			links.push(shuffle::link_leaf(position, position, &input_leaf, &output_leaf, &salt));
+			inputs.push(input_leaf);
+			outputs.push(output_leaf);
+			evidence.push(BoundedVec::truncate_from(link_evidence));
+		}
+		let (input_root, input_paths) = deep_tree(&inputs);
+		let (output_root, output_paths) = deep_tree(&outputs);
+		let (link_root, link_paths) = deep_tree(&links);
+		ShuffleHops::<T>::insert(
+			election_id,
+			hop,
+			HopCommitment { input_root, output_root, link_root },
+		);
+
+		// The challenges answered last come after every other challenge of the hop.
+		let answered = LinkChallenge { side: ChallengeSide::Output, position: 0, answered: true };
+		let mut challenges =
+			sp_std::vec![answered; (T::ShuffleChallengesPerPair::get() - l) as usize];
+		challenges.extend((0..l).map(|position| LinkChallenge {
+			answered: false,
+			position,
+			..answered
+		}));
+		ShuffleChallenges::<T>::insert(election_id, hop, BoundedVec::truncate_from(challenges));
+		ShuffleStages::<T>::insert(election_id, ShuffleStage::Proving { open: l });
+
+		let openings = (0..l as usize)
+			.map(|position| LinkOpening {
+				output_index: position as u32,
+				input_index: position as u32,
+				salt,
+				link_proof: link_paths[position].clone(),
+				input_leaf: inputs[position],
+				input_proof: input_paths[position].clone(),
+				output_leaf: outputs[position],
+				output_proof: output_paths[position].clone(),
+				evidence: evidence[position].clone(),
+			})
+			.collect::<Vec<_>>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), election_id, hop, BoundedVec::truncate_from(openings));
+
+		assert_eq!(ShuffleStages::<T>::get(election_id), Some(ShuffleStage::Proven));
+		Ok(())
+	}
+
+	// Worst case: the committee replaces one whose DKG holds a deal of every trustee and a
+	// complaint by every trustee against each of them.
+	#[benchmark]
+	fn set_tally_committee(t: Linear<1, { T::MaxTrustees::get() }>) -> Result<(), BenchmarkError> {
+		let admin: T::AccountId = whitelisted_caller();
+		let election_id = 0;
+		let max = T::MaxTrustees::get();
+		let previous = trustees::<T>(max);
+		let committee = committee::<T>(&previous, max, None);
+		committee_election::<T>(election_id, &admin, 2, ElectionPhase::Registration, committee);
+		for dealer in &previous {
+			DkgDeals::<T>::insert(election_id, dealer, deal::<T>(max, max));
+			for complainer in &previous {
+				DkgComplaints::<T>::insert(election_id, (dealer, complainer), false);
+			}
+		}
+		let new = (0..t)
+			.map(|index| (account("new trustee", index, 0), [0; 32]))
+			.collect::<Vec<(T::AccountId, X25519PublicKey)>>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(admin), election_id, new, t);
+
+		assert_eq!(DkgDeals::<T>::iter_prefix(election_id).count(), 0);
+		assert_eq!(DkgComplaints::<T>::iter_prefix(election_id).count(), 0);
+		Ok(())
+	}
+
+	// Worst case: the last of `t` trustees deals `k` commitments, each decompressed, and `t`
+	// shares of the longest length.
+	#[benchmark]
+	fn dkg_deal(
+		t: Linear<1, { T::MaxTrustees::get() }>,
+		k: Linear<1, { T::MaxTrustees::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let k = k.min(t);
+		let election_id = 0;
+		let trustees = trustees::<T>(t);
+		let dealer = trustees[t as usize - 1].clone();
+		let committee = committee::<T>(&trustees, k, None);
+		committee_election::<T>(election_id, &dealer, 2, ElectionPhase::Registration, committee);
+		let commitments = sp_std::vec![GENERATOR; k as usize];
+		let encrypted_shares = sp_std::vec![sp_std::vec![0; 128]; t as usize];
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(dealer.clone()), election_id, commitments, encrypted_shares);
+
+		assert!(DkgDeals::<T>::contains_key(election_id, &dealer));
+		Ok(())
+	}
+
+	// Worst case: the last of `t` trustees complains about a deal.
+	#[benchmark]
+	fn dkg_complain(t: Linear<1, { T::MaxTrustees::get() }>) -> Result<(), BenchmarkError> {
+		let election_id = 0;
+		let trustees = trustees::<T>(t);
+		let (dealer, complainer) = (trustees[0].clone(), trustees[t as usize - 1].clone());
+		let committee = committee::<T>(&trustees, t, None);
+		let complaint_round = committee
+			.dkg_start
+			.saturating_add(T::DkgRoundPeriod::get())
+			.saturating_add(One::one());
+		committee_election::<T>(election_id, &dealer, 2, ElectionPhase::Registration, committee);
+		DkgDeals::<T>::insert(election_id, &dealer, deal::<T>(t, t));
+		frame_system::Pallet::<T>::set_block_number(complaint_round);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(complainer.clone()), election_id, dealer.clone());
+
+		assert_eq!(DkgComplaints::<T>::get(election_id, (dealer, complainer)), Some(false));
+		Ok(())
+	}
+
+	// Worst case: a dealer answers the last of `t` trustees with the share its `k` commitments
+	// give at the highest index.
+	#[benchmark]
+	fn dkg_answer_complaint(
+		t: Linear<1, { T::MaxTrustees::get() }>,
+		k: Linear<1, { T::MaxTrustees::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let k = k.min(t);
+		let election_id = 0;
+		let trustees = trustees::<T>(t);
+		let (dealer, complainer) = (trustees[0].clone(), trustees[t as usize - 1].clone());
+		let committee = committee::<T>(&trustees, k, None);
+		committee_election::<T>(election_id, &dealer, 2, ElectionPhase::Registration, committee);
+		DkgDeals::<T>::insert(election_id, &dealer, deal::<T>(t, k));
+		DkgComplaints::<T>::insert(election_id, (&dealer, &complainer), false);
+
+		let share = dealt_share(t, k).to_bytes();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(dealer.clone()), election_id, complainer.clone(), share);
+
+		assert_eq!(DkgComplaints::<T>::get(election_id, (dealer, complainer)), Some(true));
+		Ok(())
+	}
+
+	// Worst case: `t` dealers of `k` commitments all qualify despite `c` answered complaints,
+	// and the committee key replaces the published tally key.
+	#[benchmark]
+	fn finish_dkg(
+		t: Linear<1, { T::MaxTrustees::get() }>,
+		k: Linear<1, { T::MaxTrustees::get() }>,
+		c: Linear<0, { T::MaxTrustees::get() * T::MaxTrustees::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let k = k.min(t);
+		let election_id = 0;
+		let trustees = trustees::<T>(t);
+		let committee = committee::<T>(&trustees, k, None);
+		let closed = committee
+			.dkg_start
+			.saturating_add(T::DkgRoundPeriod::get().saturating_mul(3u32.into()))
+			.saturating_add(One::one());
+		committee_election::<T>(election_id, &caller, 2, ElectionPhase::Registration, committee);
+		Elections::<T>::mutate(election_id, |election| {
+			if let Some(election) = election {
+				election.keys = Some(ElectionKeys {
+					mix_keys: BoundedVec::new(),
+					pq_mix_keys: None,
+					tally_key: [0; 32],
+				});
+			}
+		});
+		for dealer in &trustees {
+			DkgDeals::<T>::insert(election_id, dealer, deal::<T>(t, k));
+		}
+		for index in 0..c {
+			let dealer = &trustees[(index % t) as usize];
+			let complainer: T::AccountId = account("complainer", index, 0);
+			DkgComplaints::<T>::insert(election_id, (dealer, complainer), true);
+		}
+		frame_system::Pallet::<T>::set_block_number(closed);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), election_id);
+
+		let election = Elections::<T>::get(election_id).ok_or(BenchmarkError::Stop("election"))?;
+		let public_key = election.committee.and_then(|committee| committee.public_key);
+		assert!(public_key.is_some());
+		assert_eq!(election.keys.map(|keys| keys.tally_key), public_key);
+		Ok(())
+	}
+
+	// Worst case: the last of `t` trustees attests to the tally of a mixnet election, whose
+	// tally key is checked against the committee key.
+	#[benchmark]
+	fn attest_tally(t: Linear<1, { T::MaxTrustees::get() }>) -> Result<(), BenchmarkError> {
+		let election_id = 0;
+		let trustees = trustees::<T>(t);
+		let trustee = trustees[t as usize - 1].clone();
+		tallying_election::<T>(election_id, &trustee, 1, 1)?;
+		Elections::<T>::mutate(election_id, |election| {
+			if let Some(election) = election {
+				election.committee = Some(committee::<T>(&trustees, t, Some(GENERATOR)));
+				if let Some(keys) = election.keys.as_mut() {
+					keys.tally_key = GENERATOR;
+				}
+			}
+		});
+		let result_hash = T::Hashing::hash(b"result");
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(trustee.clone()), election_id, result_hash);
+
+		assert_eq!(TallyAttestations::<T>::get(election_id, &trustee), Some(result_hash));
+		Ok(())
+	}
+
+	// Worst case: the last of `MaxTrustees` trustees proves its share of each of `o` options
+	// against a verification key evaluated from `k` commitments.
+	#[benchmark]
+	fn submit_partial_decryption(
+		o: Linear<2, { elgamal::MAX_OPTIONS }>,
+		k: Linear<1, { T::MaxTrustees::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let election_id = 0;
+		let (trustees, committee) = established_committee::<T>(k);
+		let trustee = trustees[trustees.len() - 1].clone();
+		committee_election::<T>(election_id, &trustee, o as u8, ElectionPhase::Tallying, committee);
+		let ciphertext = elgamal::ElGamalCiphertext { a: GENERATOR, b: GENERATOR };
+		EncryptedTallies::<T>::insert(
+			election_id,
+			BoundedVec::truncate_from(sp_std::vec![ciphertext; o as usize]),
+		);
+
+		let (context, secret) = (election_id.encode(), dealt_share(trustees.len() as u32, k));
+		let partials = (0..o)
+			.map(|option| {
+				elgamal::prove_partial(&context, &secret.to_bytes(), &ciphertext, &option.encode())
+			})
+			.collect::<Option<Vec<_>>>()
+			.ok_or(BenchmarkError::Stop("partial decryption"))?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(trustee.clone()), election_id, partials);
+
+		assert!(PartialDecryptions::<T>::contains_key(election_id, &trustee));
+		Ok(())
+	}
+
+	// Worst case: only the last `k` of `MaxTrustees` trustees published their partial
+	// decryption, which are combined for each of `o` counts as large as a count can be.
+	#[benchmark]
+	fn submit_decrypted_counts(
+		o: Linear<2, { elgamal::MAX_OPTIONS }>,
+		k: Linear<1, { T::MaxTrustees::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let authority: T::AccountId = whitelisted_caller();
+		let election_id = 0;
+		let (trustees, committee) = established_committee::<T>(k);
+		committee_election::<T>(
+			election_id,
+			&authority,
+			o as u8,
+			ElectionPhase::Tallying,
+			committee,
+		);
+
+		// The tally encrypts `count` under randomness one, so each share is the trustee's secret
+		// share times the generator and they combine to the committee secret, one.
+		let count = u64::from(u32::MAX);
+		let g = curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+		let b = (Scalar::from(count + 1) * g).compress().to_bytes();
+		let ciphertext = elgamal::ElGamalCiphertext { a: GENERATOR, b };
+		EncryptedTallies::<T>::insert(
+			election_id,
+			BoundedVec::truncate_from(sp_std::vec![ciphertext; o as usize]),
+		);
+		for index in trustees.len() as u32 - k + 1..=trustees.len() as u32 {
+			let share = (dealt_share(index, k) * g).compress().to_bytes();
+			PartialDecryptions::<T>::insert(
+				election_id,
+				&trustees[index as usize - 1],
+				BoundedVec::truncate_from(sp_std::vec![share; o as usize]),
+			);
+		}
+		let counts = sp_std::vec![count; o as usize];
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(authority), election_id, counts);
+
+		assert!(DecryptedCounts::<T>::contains_key(election_id));
+		Ok(())
+	}
+
+	// Worst case: both phase transitions skip every full block they may.
+	#[benchmark]
+	fn create_election() -> Result<(), BenchmarkError> {
+		let admin: T::AccountId = whitelisted_caller();
+		fund::<T>(&admin);
+		let now = frame_system::Pallet::<T>::block_number();
+		let registration_deadline = now.saturating_add(One::one());
+		let voting_deadline = registration_deadline.saturating_add((2 * MAX_SCHEDULE_SPILL).into());
+		fill_schedule::<T>(registration_deadline.saturating_add(One::one()));
+		fill_schedule::<T>(voting_deadline.saturating_add(One::one()));
+		let tally_mode = TallyMode::Homomorphic { options: elgamal::MAX_OPTIONS as u8 };
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(admin.clone()),
+			0,
+			admin,
+			registration_deadline,
+			voting_deadline,
+			RevotePolicy::LastVoteWins,
+			tally_mode,
+		);
+
+		assert_eq!(phase::<T>(0), Some(ElectionPhase::Registration));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn register_voter() -> Result<(), BenchmarkError> {
+		let admin: T::AccountId = whitelisted_caller();
+		mixnet_election::<T>(0, &admin, ElectionPhase::Registration)?;
+		let voter: T::AccountId = account("voter", 0, 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(admin), 0, voter.clone());
+
+		assert!(Voters::<T>::contains_key(0, &voter));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_mix_commitments() -> Result<(), BenchmarkError> {
+		let authority: T::AccountId = whitelisted_caller();
+		mixnet_election::<T>(0, &authority, ElectionPhase::Mixing)?;
+		let input_root = [1; 32];
+		InputRoots::<T>::insert(0, input_root);
+		let commitment_input_root =
+			RootHash::<T>::decode(&mut &input_root[..]).map_err(|_| BenchmarkError::Weightless)?;
+		let commitment_output_root = T::Hashing::hash(b"output");
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(authority), 0, commitment_input_root, commitment_output_root);
+
+		assert_eq!(phase::<T>(0), Some(ElectionPhase::Tallying));
+		Ok(())
+	}
+
+	// Worst case: a mixnet election with a proven shuffle, whose result `t` trustees attested
+	// to, and whose challenge window skips every full block it may.
+	#[benchmark]
+	fn submit_tally(t: Linear<1, { T::MaxTrustees::get() }>) -> Result<(), BenchmarkError> {
+		let authority: T::AccountId = whitelisted_caller();
+		let election_id = 0;
+		tallying_election::<T>(election_id, &authority, 1, 1)?;
+		fund::<T>(&authority);
+		let trustees = trustees::<T>(t);
+		Elections::<T>::mutate(election_id, |election| {
+			if let Some(election) = election {
+				election.committee = Some(committee::<T>(&trustees, t, Some(GENERATOR)));
+			}
+		});
+		let result_hash = T::Hashing::hash(b"result");
+		for trustee in &trustees {
+			TallyAttestations::<T>::insert(election_id, trustee, result_hash);
+		}
+		ShuffleStages::<T>::insert(election_id, ShuffleStage::Proven);
+		let now = frame_system::Pallet::<T>::block_number();
+		let challenge_deadline = now.saturating_add(T::ChallengePeriod::get());
+		fill_schedule::<T>(challenge_deadline.saturating_add(One::one()));
+		let result_uri = sp_std::vec![0; 256];
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(authority), election_id, result_uri, result_hash);
+
+		assert_eq!(phase::<T>(election_id), Some(ElectionPhase::Disputable));
+		Ok(())
+	}
+
+	// Worst case: a disputed tally, whose bond and the bonds of its `d` disputes are released.
+	#[benchmark]
+	fn cancel_election(d: Linear<0, { T::MaxDisputes::get() }>) -> Result<(), BenchmarkError> {
+		let admin: T::AccountId = whitelisted_caller();
+		disputed_election::<T>(0, &admin, d)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(admin), 0);
+
+		assert_eq!(phase::<T>(0), Some(ElectionPhase::Cancelled));
+		Ok(())
+	}
+
+	// Worst case: `h` mix keys, each with an ML-KEM key, and a tally key checked against the
+	// committee key.
+	#[benchmark]
+	fn set_election_keys(
+		h: Linear<1, { <MixCascade<X25519PublicKey>>::bound() as u32 }>,
+	) -> Result<(), BenchmarkError> {
+		let admin: T::AccountId = whitelisted_caller();
+		let committee = committee::<T>(&trustees::<T>(1), 1, Some(GENERATOR));
+		committee_election::<T>(0, &admin, 2, ElectionPhase::Registration, committee);
+		let mix_keys = sp_std::vec![[0; 32]; h as usize];
+		let pq_mix_keys = sp_std::vec![[0; 1184]; h as usize];
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(admin), 0, mix_keys, Some(pq_mix_keys), GENERATOR);
+
+		assert!(Elections::<T>::get(0).is_some_and(|election| election.keys.is_some()));
+		Ok(())
+	}
+
+	// Worst case: a missing ballot, checked against the ballots, is the last dispute the tally
+	// can take.
+	#[benchmark]
+	fn dispute_tally() -> Result<(), BenchmarkError> {
+		let challenger: T::AccountId = whitelisted_caller();
+		let open = T::MaxDisputes::get().saturating_sub(1);
+		disputed_election::<T>(0, &account("authority", 0, 0), open)?;
+		fund::<T>(&challenger);
+		BallotCount::<T>::insert(0, 1);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(challenger), 0, DisputeEvidence::MissingBallot { index: 0 });
+
+		assert_eq!(Disputes::<T>::decode_len(0), Some(open as usize + 1));
+		Ok(())
+	}
+
+	// Worst case: the last open dispute is rejected once the challenge window closed, which
+	// finalizes the election.
+	#[benchmark]
+	fn reject_dispute() -> Result<(), BenchmarkError> {
+		let origin =
+			T::DisputeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		disputed_election::<T>(0, &account("authority", 0, 0), 1)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now.saturating_add(One::one()));
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, 0, 0);
+
+		assert_eq!(phase::<T>(0), Some(ElectionPhase::Finalized));
+		Ok(())
+	}
+
+	// Worst case: the first of `d` disputes, on the mix commitments, is upheld, which releases
+	// every bond and clears the attestations of `MaxTrustees` trustees, the verified counts and
+	// the shuffle proof of every hop.
+	#[benchmark]
+	fn uphold_dispute(d: Linear<1, { T::MaxDisputes::get() }>) -> Result<(), BenchmarkError> {
+		let origin =
+			T::DisputeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let election_id = 0;
+		disputed_election::<T>(election_id, &account("authority", 0, 0), d)?;
+
+		let result_hash = T::Hashing::hash(b"result");
+		for trustee in trustees::<T>(T::MaxTrustees::get()) {
+			TallyAttestations::<T>::insert(election_id, trustee, result_hash);
+		}
+		let counts = sp_std::vec![0; elgamal::MAX_OPTIONS as usize];
+		DecryptedCounts::<T>::insert(election_id, elgamal::OptionCounts::truncate_from(counts));
+		let commitment =
+			HopCommitment { input_root: [0; 32], output_root: [0; 32], link_root: [0; 32] };
+		let challenge = LinkChallenge { side: ChallengeSide::Output, position: 0, answered: true };
+		let challenges = sp_std::vec![challenge; T::ShuffleChallengesPerPair::get() as usize];
+		for hop in 0..<MixCascade<X25519PublicKey>>::bound() as HopIndex {
+			ShuffleHops::<T>::insert(election_id, hop, commitment);
+			ShuffleChallenges::<T>::insert(
+				election_id,
+				hop,
+				BoundedVec::truncate_from(challenges.clone()),
+			);
+		}
+		ShuffleStages::<T>::insert(election_id, ShuffleStage::Proven);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, election_id, 0);
+
+		assert_eq!(phase::<T>(election_id), Some(ElectionPhase::Mixing));
+		assert!(!ShuffleStages::<T>::contains_key(election_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_voter_allowlist() -> Result<(), BenchmarkError> {
+		// Without a membership verifier, the call fails before touching storage
+		if !T::MembershipVerifier::ENABLED {
+			return Err(BenchmarkError::Weightless)
+		}
+		let admin: T::AccountId = whitelisted_caller();
+		mixnet_election::<T>(0, &admin, ElectionPhase::Registration)?;
+		let root = [1; 32];
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(admin), 0, root);
+
+		assert_eq!(VoterAllowlists::<T>::get(0), Some(root));
+		Ok(())
+	}
+
+	// Worst case: the transfer creates the funding pot.
+	#[benchmark]
+	fn fund_election() -> Result<(), BenchmarkError> {
+		let funder: T::AccountId = whitelisted_caller();
+		mixnet_election::<T>(0, &account("admin", 0, 0), ElectionPhase::Voting)?;
+		fund::<T>(&funder);
+		let amount = T::Currency::minimum_balance().saturating_add(T::FeelessBallotFee::get());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(funder), 0, amount);
+
+		assert_eq!(T::Currency::balance(&Pallet::<T>::pot_account(0)), amount);
+		Ok(())
+	}
+
+	// Worst case: the transfer empties and removes the funding pot.
+	#[benchmark]
+	fn reclaim_election_funds() -> Result<(), BenchmarkError> {
+		let admin: T::AccountId = whitelisted_caller();
+		mixnet_election::<T>(0, &admin, ElectionPhase::Finalized)?;
+		let pot = Pallet::<T>::pot_account(0);
+		T::Currency::set_balance(&pot, T::Currency::minimum_balance().saturating_mul(2u32.into()));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(admin), 0);
+
+		assert!(T::Currency::balance(&pot).is_zero());
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,73 @@
+//! Ristretto255 checks for the distributed key generation of a tally committee.
+//!
+//! Trustees run a joint-Feldman DKG. Trustee `i` picks a polynomial `f_i` of degree `t - 1`,
+//! publishes `C_i,k = a_i,k * G` for each coefficient and sends `f_i(j)` to the trustee at
+//! 1-based index `j`. A share is correct if `f_i(j) * G == sum_k C_i,k * j^k`. The committee
+//! polynomial is the sum of the qualified dealers' polynomials: its constant commitment is the
+//! committee key, its evaluation at `j` the verification key of trustee `j`, and the matching
+//! secret is only ever shared `t`-of-`n`.
+
+use curve25519_dalek::{
+	constants::RISTRETTO_BASEPOINT_POINT,
+	ristretto::{CompressedRistretto, RistrettoPoint},
+	scalar::Scalar,
+};
+use sp_std::vec::Vec;
+
+/// A compressed Ristretto255 point.
+pub type GroupElement = [u8; 32];
+
+/// A canonically encoded Ristretto255 scalar.
+pub type ShareScalar = [u8; 32];
+
+pub(crate) fn decompress(point: &GroupElement) -> Option<RistrettoPoint> {
+	CompressedRistretto(*point).decompress()
+}
+
+pub(crate) fn scalar(bytes: &ShareScalar) -> Option<Scalar> {
+	Scalar::from_canonical_bytes(*bytes).into()
+}
+
+/// Whether `point` is a valid compressed group element.
+pub fn is_valid_element(point: &GroupElement) -> bool {
+	decompress(point).is_some()
+}
+
+/// `f(index) * G` for the polynomial `f` committed to by `commitments`, lowest degree first.
+pub fn evaluate(commitments: &[GroupElement], index: u32) -> Option<RistrettoPoint> {
+	let x = Scalar::from(index);
+	let mut power = Scalar::ONE;
+	let mut value = RistrettoPoint::default();
+	for commitment in commitments {
+		value += decompress(commitment)? * power;
+		power *= x;
+	}
+	Some(value)
+}
+
+/// Whether `share` is the evaluation at `index` of the polynomial committed to by
+/// `commitments`, lowest degree first.
+pub fn verify_share(commitments: &[GroupElement], index: u32, share: &ShareScalar) -> bool {
+	let (Some(share), Some(expected)) = (scalar(share), evaluate(commitments, index)) else {
+		return false
+	};
+	share * RISTRETTO_BASEPOINT_POINT == expected
+}
+
+/// Coefficient-wise sum of polynomial commitments of `coefficients` entries each, or `None` if
+/// one of them is invalid.
+pub fn combine_commitments<'a>(
+	polynomials: impl IntoIterator<Item = &'a [GroupElement]>,
+	coefficients: usize,
+) -> Option<Vec<GroupElement>> {
+	let mut sum = sp_std::vec![RistrettoPoint::default(); coefficients];
+	for polynomial in polynomials {
+		if polynomial.len() != coefficients {
+			return None
+		}
+		for (total, commitment) in sum.iter_mut().zip(polynomial) {
+			*total += decompress(commitment)?;
+		}
+	}
+	Some(sum.iter().map(|point| point.compress().to_bytes()).collect())
+}
@@ -0,0 +1,527 @@
+//! Exponential ElGamal on Ristretto255 for homomorphic tallies.
+//!
+//! A ballot holds one ciphertext `(r * G, m * G + r * K)` per option, where `K` is the committee
+//! key and `m` is 1 for the chosen option and 0 otherwise. Ciphertexts add up option by option,
+//! so the sum of all ballots encrypts the count of each option. Trustee `i` decrypts its part
+//! of an aggregate `(A, B)` as `D_i = s_i * A` and proves with a Chaum-Pedersen proof that
+//! `log_G(Y_i) == log_A(D_i)`, `Y_i` being its verification key (see `dkg`). Any `t` partial
+//! decryptions recover `count * G = B - sum_i l_i * D_i` with Lagrange coefficients `l_i` at 0.
+//!
+//! A ballot is only summed if it proves that it is well formed: a disjunctive Chaum-Pedersen
+//! proof per option that `log_G(A) == log_K(B - m * G)` for `m` in `{0, 1}`, and a Chaum-Pedersen
+//! proof that the option-wise sum `(sum A, sum B - G)` encrypts 0, i.e. that exactly one option is
+//! chosen.
+//!
+//! Every Fiat-Shamir challenge hashes a `context` along with the statement: the election and
+//! sender of a ballot (see `Pallet::ballot_context`), or the election of a partial decryption,
+//! so a proof cannot be replayed by another voter or in another election.
+
+use crate::dkg::{self, GroupElement, ShareScalar};
+use curve25519_dalek::{
+	constants::RISTRETTO_BASEPOINT_POINT, ristretto::RistrettoPoint, scalar::Scalar,
+};
+use frame::prelude::*;
+use sp_std::vec::Vec;
+
+/// Maximum options of a homomorphic election.
+pub const MAX_OPTIONS: u32 = 16;
+
+/// Ciphertext of one option of a ballot.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	MaxEncodedLen,
+	TypeInfo,
+	Clone,
+	Copy,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+)]
+pub struct ElGamalCiphertext {
+	/// `r * G`.
+	pub a: GroupElement,
+	/// `m * G + r * K`.
+	pub b: GroupElement,
+}
+
+/// One ciphertext per option.
+pub type ElGamalBallot = BoundedVec<ElGamalCiphertext, ConstU32<MAX_OPTIONS>>;
+
+/// Decrypted count of each option of a homomorphic election.
+pub type OptionCounts = BoundedVec<u64, ConstU32<MAX_OPTIONS>>;
+
+/// Non-interactive Chaum-Pedersen proof that two points share a discrete logarithm.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	MaxEncodedLen,
+	TypeInfo,
+	Clone,
+	Copy,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+)]
+pub struct DleqProof {
+	/// Fiat-Shamir challenge `c`.
+	pub challenge: ShareScalar,
+	/// Response `z = w + c * s`.
+	pub response: ShareScalar,
+}
+
+/// Disjunctive Chaum-Pedersen proof that a ciphertext encrypts 0 or 1.
+///
+/// Entry `m` proves `log_G(A) == log_K(B - m * G)`; the challenges add up to the Fiat-Shamir
+/// challenge, so only one of the two statements can be simulated.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	MaxEncodedLen,
+	TypeInfo,
+	Clone,
+	Copy,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+)]
+pub struct BitProof {
+	/// Challenges `c_0` and `c_1`.
+	pub challenges: [ShareScalar; 2],
+	/// Responses `z_0` and `z_1`.
+	pub responses: [ShareScalar; 2],
+}
+
+/// A ballot with its validity proof; the `ciphertext` of `cast_vote` in homomorphic elections.
+#[derive(Encode, Decode, DecodeWithMemTracking, TypeInfo, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct ProvenBallot {
+	/// One ciphertext per option.
+	pub ciphertexts: ElGamalBallot,
+	/// Proof that each ciphertext encrypts 0 or 1, in option order.
+	pub bit_proofs: BoundedVec<BitProof, ConstU32<MAX_OPTIONS>>,
+	/// Proof that the ciphertexts add up to an encryption of 1.
+	pub sum_proof: DleqProof,
+}
+
+/// A trustee's share of the decryption of one option of the aggregate.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	MaxEncodedLen,
+	TypeInfo,
+	Clone,
+	Copy,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+)]
+pub struct PartialDecryption {
+	/// `D_i = s_i * A`.
+	pub share: GroupElement,
+	/// Proof that `D_i` uses the same secret as the trustee's verification key.
+	pub proof: DleqProof,
+}
+
+/// Option-wise sum, or difference if `subtract`, of two ballots of the same length.
+///
+/// `None` if the lengths differ or a point is invalid.
+pub fn accumulate(
+	total: &[ElGamalCiphertext],
+	ballot: &[ElGamalCiphertext],
+	subtract: bool,
+) -> Option<Vec<ElGamalCiphertext>> {
+	if total.len() != ballot.len() {
+		return None
+	}
+
+	let combine = |x: &GroupElement, y: &GroupElement| -> Option<GroupElement> {
+		let (x, y) = (dkg::decompress(x)?, dkg::decompress(y)?);
+		let sum = if subtract { x - y } else { x + y };
+		Some(sum.compress().to_bytes())
+	};
+	total
+		.iter()
+		.zip(ballot)
+		.map(|(t, c)| Some(ElGamalCiphertext { a: combine(&t.a, &c.a)?, b: combine(&t.b, &c.b)? }))
+		.collect()
+}
+
+/// Whether every point of `ballot` is valid.
+pub fn is_valid_ballot(ballot: &[ElGamalCiphertext]) -> bool {
+	ballot
+		.iter()
+		.all(|c| dkg::is_valid_element(&c.a) && dkg::is_valid_element(&c.b))
+}
+
+/// The ballot SCALE-encoded in `bytes`, if it has `options` valid ciphertexts.
+///
+/// The validity proof is not checked, see `verify_ballot`.
+pub fn decode_ballot(bytes: &[u8], options: u8) -> Option<ProvenBallot> {
+	let mut input = bytes;
+	let ballot = ProvenBallot::decode(&mut input).ok()?;
+	(input.is_empty() &&
+		ballot.ciphertexts.len() == options as usize &&
+		is_valid_ballot(&ballot.ciphertexts))
+	.then_some(ballot)
+}
+
+/// Fiat-Shamir challenge of a Chaum-Pedersen proof over `(G, y)` and `(a, d)` in `context`.
+fn dleq_challenge(
+	context: &[u8],
+	y: &RistrettoPoint,
+	a: &RistrettoPoint,
+	d: &RistrettoPoint,
+	commit_g: &RistrettoPoint,
+	commit_a: &RistrettoPoint,
+) -> Scalar {
+	let transcript = (
+		b"daomix/dleq",
+		context,
+		y.compress().to_bytes(),
+		a.compress().to_bytes(),
+		d.compress().to_bytes(),
+		commit_g.compress().to_bytes(),
+		commit_a.compress().to_bytes(),
+	);
+	Scalar::from_bytes_mod_order_wide(&blake2_512(&transcript.encode()))
+}
+
+/// Whether `proof` shows `log_G(y) == log_base(d)` in `context`.
+fn verify_dleq(
+	context: &[u8],
+	y: &RistrettoPoint,
+	base: &RistrettoPoint,
+	d: &RistrettoPoint,
+	proof: &DleqProof,
+) -> bool {
+	let (Some(c), Some(z)) = (dkg::scalar(&proof.challenge), dkg::scalar(&proof.response)) else {
+		return false
+	};
+
+	let commit_g = z * RISTRETTO_BASEPOINT_POINT - c * y;
+	let commit_a = z * base - c * d;
+	dleq_challenge(context, y, base, d, &commit_g, &commit_a) == c
+}
+
+/// Fiat-Shamir challenge of a `BitProof` for `(a, b)` under `key` in `context`.
+fn bit_challenge(
+	context: &[u8],
+	key: &RistrettoPoint,
+	a: &RistrettoPoint,
+	b: &RistrettoPoint,
+	commits: &[(RistrettoPoint, RistrettoPoint); 2],
+) -> Scalar {
+	let transcript = (
+		b"daomix/bit",
+		context,
+		key.compress().to_bytes(),
+		a.compress().to_bytes(),
+		b.compress().to_bytes(),
+		commits.map(|(g, k)| (g.compress().to_bytes(), k.compress().to_bytes())),
+	);
+	Scalar::from_bytes_mod_order_wide(&blake2_512(&transcript.encode()))
+}
+
+/// Whether `proof` shows that `ciphertext` encrypts 0 or 1 under `key` in `context`.
+fn verify_bit(
+	context: &[u8],
+	key: &RistrettoPoint,
+	ciphertext: &ElGamalCiphertext,
+	proof: &BitProof,
+) -> bool {
+	let (Some(a), Some(b)) = (dkg::decompress(&ciphertext.a), dkg::decompress(&ciphertext.b))
+	else {
+		return false
+	};
+
+	let mut commits = [(RistrettoPoint::default(), RistrettoPoint::default()); 2];
+	let mut challenge = Scalar::ZERO;
+	for (m, commit) in commits.iter_mut().enumerate() {
+		let (Some(c), Some(z)) =
+			(dkg::scalar(&proof.challenges[m]), dkg::scalar(&proof.responses[m]))
+		else {
+			return false
+		};
+		let b_m = b - Scalar::from(m as u64) * RISTRETTO_BASEPOINT_POINT;
+		*commit = (z * RISTRETTO_BASEPOINT_POINT - c * a, z * key - c * b_m);
+		challenge += c;
+	}
+
+	bit_challenge(context, key, &a, &b, &commits) == challenge
+}
+
+/// Whether the validity proof of `ballot` holds under the committee key `public_key` and was
+/// made for `context`.
+pub fn verify_ballot(public_key: &GroupElement, context: &[u8], ballot: &ProvenBallot) -> bool {
+	let Some(key) = dkg::decompress(public_key) else { return false };
+	let ciphertexts = &ballot.ciphertexts;
+	if ballot.bit_proofs.len() != ciphertexts.len() ||
+		!ciphertexts
+			.iter()
+			.zip(&ballot.bit_proofs)
+			.all(|(c, p)| verify_bit(context, &key, c, p))
+	{
+		return false
+	}
+
+	let mut sum_a = RistrettoPoint::default();
+	let mut sum_b = RistrettoPoint::default();
+	for c in ciphertexts {
+		let (Some(a), Some(b)) = (dkg::decompress(&c.a), dkg::decompress(&c.b)) else {
+			return false
+		};
+		sum_a += a;
+		sum_b += b;
+	}
+	verify_dleq(context, &sum_a, &key, &(sum_b - RISTRETTO_BASEPOINT_POINT), &ballot.sum_proof)
+}
+
+/// A valid ballot for `choice` out of `options` under `public_key` in `context`.
+///
+/// Nonces are derived from `seed`, so this is only fit for benchmarks and tests.
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+pub fn prove_ballot(
+	public_key: &GroupElement,
+	context: &[u8],
+	options: u8,
+	choice: u8,
+	seed: &[u8],
+) -> Option<ProvenBallot> {
+	let key = dkg::decompress(public_key)?;
+	let nonce = |tag: &[u8], option: u8| {
+		Scalar::from_bytes_mod_order_wide(&blake2_512(&(seed, tag, option).encode()))
+	};
+	let g = RISTRETTO_BASEPOINT_POINT;
+
+	let mut ciphertexts = Vec::new();
+	let mut bit_proofs = Vec::new();
+	let mut randomness = Scalar::ZERO;
+	for option in 0..options {
+		let m = usize::from(option == choice);
+		let r = nonce(b"r", option);
+		let (a, b) = (r * g, Scalar::from(m as u64) * g + r * key);
+
+		// Simulate the false statement, then answer the real one
+		let (fake_c, fake_z, w) = (nonce(b"c", option), nonce(b"z", option), nonce(b"w", option));
+		let fake_b = b - Scalar::from((1 - m) as u64) * g;
+		let mut commits = [(w * g, w * key); 2];
+		commits[1 - m] = (fake_z * g - fake_c * a, fake_z * key - fake_c * fake_b);
+		let c = bit_challenge(context, &key, &a, &b, &commits) - fake_c;
+
+		let mut challenges = [fake_c.to_bytes(); 2];
+		let mut responses = [fake_z.to_bytes(); 2];
+		challenges[m] = c.to_bytes();
+		responses[m] = (w + c * r).to_bytes();
+
+		ciphertexts
+			.push(ElGamalCiphertext { a: a.compress().to_bytes(), b: b.compress().to_bytes() });
+		bit_proofs.push(BitProof { challenges, responses });
+		randomness += r;
+	}
+
+	let (sum_a, w) = (randomness * g, nonce(b"sum", 0));
+	let sum_b = ciphertexts.iter().filter_map(|c| dkg::decompress(&c.b)).sum::<RistrettoPoint>();
+	let d = sum_b - g;
+	let c = dleq_challenge(context, &sum_a, &key, &d, &(w * g), &(w * key));
+	let sum_proof =
+		DleqProof { challenge: c.to_bytes(), response: (w + c * randomness).to_bytes() };
+
+	Some(ProvenBallot {
+		ciphertexts: BoundedVec::try_from(ciphertexts).ok()?,
+		bit_proofs: BoundedVec::try_from(bit_proofs).ok()?,
+		sum_proof,
+	})
+}
+
+/// Whether `partial` is the decryption share of `ciphertext` by the trustee with verification
+/// key `verification_key`, proven for `context`.
+pub fn verify_partial(
+	context: &[u8],
+	verification_key: &RistrettoPoint,
+	ciphertext: &ElGamalCiphertext,
+	partial: &PartialDecryption,
+) -> bool {
+	let (Some(a), Some(d)) = (dkg::decompress(&ciphertext.a), dkg::decompress(&partial.share))
+	else {
+		return false
+	};
+	verify_dleq(context, verification_key, &a, &d, &partial.proof)
+}
+
+/// The decryption share of `ciphertext` by the trustee with secret share `secret`, proven for
+/// `context`.
+///
+/// The nonce is derived from `seed`, so this is only fit for benchmarks and tests.
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+pub fn prove_partial(
+	context: &[u8],
+	secret: &ShareScalar,
+	ciphertext: &ElGamalCiphertext,
+	seed: &[u8],
+) -> Option<PartialDecryption> {
+	let (s, a) = (dkg::scalar(secret)?, dkg::decompress(&ciphertext.a)?);
+	let g = RISTRETTO_BASEPOINT_POINT;
+	let (y, d) = (s * g, s * a);
+	let w = Scalar::from_bytes_mod_order_wide(&blake2_512(&(seed, b"partial").encode()));
+	let c = dleq_challenge(context, &y, &a, &d, &(w * g), &(w * a));
+	let proof = DleqProof { challenge: c.to_bytes(), response: (w + c * s).to_bytes() };
+	Some(PartialDecryption { share: d.compress().to_bytes(), proof })
+}
+
+/// Whether `b` minus the combined decryption `shares` is `count * G`.
+///
+/// `shares` pairs each 1-based trustee index with its share; the indices must be distinct.
+pub fn check_count(b: &GroupElement, shares: &[(u32, GroupElement)], count: u64) -> bool {
+	let Some(mut plain) = dkg::decompress(b) else { return false };
+
+	for (i, share) in shares {
+		let Some(share) = dkg::decompress(share) else { return false };
+		// Lagrange coefficient at 0: prod_{j != i} j / (j - i)
+		let xi = Scalar::from(*i);
+		let mut coefficient = Scalar::ONE;
+		for (j, _) in shares.iter().filter(|(j, _)| j != i) {
+			let xj = Scalar::from(*j);
+			coefficient *= xj * (xj - xi).invert();
+		}
+		plain -= coefficient * share;
+	}
+
+	Scalar::from(count) * RISTRETTO_BASEPOINT_POINT == plain
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Secret of the committee key `SECRET * G`, shared 2-of-n as `SECRET + SLOPE * x`.
+	const SECRET: u64 = 7;
+	const SLOPE: u64 = 11;
+
+	fn public_key() -> GroupElement {
+		(Scalar::from(SECRET) * RISTRETTO_BASEPOINT_POINT).compress().to_bytes()
+	}
+
+	fn ballot(options: u8, choice: u8, context: &[u8]) -> ProvenBallot {
+		prove_ballot(&public_key(), context, options, choice, &[choice]).unwrap()
+	}
+
+	#[test]
+	fn proven_ballots_verify() {
+		for options in 2..=4 {
+			for choice in 0..options {
+				let ballot = ballot(options, choice, b"context");
+				assert!(verify_ballot(&public_key(), b"context", &ballot));
+				assert_eq!(decode_ballot(&ballot.encode(), options), Some(ballot));
+			}
+		}
+	}
+
+	#[test]
+	fn proofs_only_verify_in_their_context() {
+		let ballot = ballot(3, 1, b"context");
+		assert!(!verify_ballot(&public_key(), b"other context", &ballot));
+
+		let other_key = RISTRETTO_BASEPOINT_POINT.compress().to_bytes();
+		assert!(!verify_ballot(&other_key, b"context", &ballot));
+	}
+
+	#[test]
+	fn ballot_choosing_no_option_is_rejected() {
+		// Every option encrypts 0, so the bit proofs hold but the sum proof cannot
+		let ballot = ballot(3, 3, b"context");
+		let key = dkg::decompress(&public_key()).unwrap();
+		assert!(ballot
+			.ciphertexts
+			.iter()
+			.zip(&ballot.bit_proofs)
+			.all(|(c, p)| verify_bit(b"context", &key, c, p)));
+		assert!(!verify_ballot(&public_key(), b"context", &ballot));
+	}
+
+	#[test]
+	fn ballot_choosing_two_options_is_rejected() {
+		// Each ciphertext encrypts 0 or 1 with its own valid bit proof, but they add up to 2
+		let (first, second) = (ballot(2, 0, b"context"), ballot(2, 1, b"context"));
+		let twice = ProvenBallot {
+			ciphertexts: BoundedVec::truncate_from(vec![
+				first.ciphertexts[0],
+				second.ciphertexts[0],
+			]),
+			bit_proofs: BoundedVec::truncate_from(vec![first.bit_proofs[0], second.bit_proofs[0]]),
+			sum_proof: first.sum_proof,
+		};
+		assert!(!verify_ballot(&public_key(), b"context", &twice));
+	}
+
+	#[test]
+	fn ballot_with_a_non_bit_option_is_rejected() {
+		// Moving `G` from option 1 to option 0 keeps the sum at 1 but encrypts 2 and -1
+		let mut ballot = ballot(2, 0, b"context");
+		let g = RISTRETTO_BASEPOINT_POINT;
+		let mut ciphertexts = ballot.ciphertexts.to_vec();
+		for (ciphertext, shift) in ciphertexts.iter_mut().zip([g, -g]) {
+			ciphertext.b = (dkg::decompress(&ciphertext.b).unwrap() + shift).compress().to_bytes();
+		}
+		ballot.ciphertexts = BoundedVec::truncate_from(ciphertexts);
+		assert!(!verify_ballot(&public_key(), b"context", &ballot));
+	}
+
+	#[test]
+	fn ballot_missing_a_bit_proof_is_rejected() {
+		let mut ballot = ballot(2, 0, b"context");
+		ballot.bit_proofs = BoundedVec::truncate_from(ballot.bit_proofs[..1].to_vec());
+		assert!(!verify_ballot(&public_key(), b"context", &ballot));
+	}
+
+	#[test]
+	fn partial_decryptions_verify_against_the_verification_key() {
+		let ballot = ballot(2, 0, b"context");
+		let share = Scalar::from(SECRET) + Scalar::from(SLOPE);
+		let verification_key = share * RISTRETTO_BASEPOINT_POINT;
+		let partial =
+			prove_partial(b"context", &share.to_bytes(), &ballot.ciphertexts[0], b"seed").unwrap();
+
+		assert!(verify_partial(b"context", &verification_key, &ballot.ciphertexts[0], &partial));
+		assert!(!verify_partial(b"other", &verification_key, &ballot.ciphertexts[0], &partial));
+		assert!(!verify_partial(b"context", &verification_key, &ballot.ciphertexts[1], &partial));
+		let other_key = RISTRETTO_BASEPOINT_POINT;
+		assert!(!verify_partial(b"context", &other_key, &ballot.ciphertexts[0], &partial));
+	}
+
+	#[test]
+	fn counts_are_checked_against_decryption_shares() {
+		let ballots = [ballot(2, 0, b"a"), ballot(2, 1, b"b"), ballot(2, 0, b"c")];
+		let total = ballots
+			.iter()
+			.skip(1)
+			.try_fold(ballots[0].ciphertexts.to_vec(), |total, ballot| {
+				accumulate(&total, &ballot.ciphertexts, false)
+			})
+			.unwrap();
+
+		// Trustee `i` decrypts with its share `SECRET + SLOPE * i`
+		let shares = |option: usize, trustees: &[u32]| -> Vec<(u32, GroupElement)> {
+			let a = dkg::decompress(&total[option].a).unwrap();
+			trustees
+				.iter()
+				.map(|&i| {
+					let share = Scalar::from(SECRET) + Scalar::from(SLOPE) * Scalar::from(i);
+					(i, (share * a).compress().to_bytes())
+				})
+				.collect()
+		};
+
+		for trustees in [[1, 2], [2, 3], [3, 1]] {
+			assert!(check_count(&total[0].b, &shares(0, &trustees), 2));
+			assert!(check_count(&total[1].b, &shares(1, &trustees), 1));
+			assert!(!check_count(&total[0].b, &shares(0, &trustees), 1));
+			assert!(!check_count(&total[1].b, &shares(0, &trustees), 1));
+		}
+		// One share is below the threshold
+		assert!(!check_count(&total[0].b, &shares(0, &[1]), 2));
+	}
+}
@@ -0,0 +1,377 @@
+//! Groth16 verifier over BLS12-381 for membership proofs.
+//!
+//! Points are in arkworks' compressed encoding. The runtime pins a [`Groth16VerifyingKey`] and a
+//! proof is the SCALE encoding of [`Groth16Proof`]. A proof `(A, B, C)` is valid for public
+//! inputs `x` if `e(A, B) == e(alpha, beta) * e(vk_x, gamma) * e(C, delta)`, where
+//! `vk_x = ic_0 + sum_i x_i * ic_(i + 1)`.
+//!
+//! Pairings run in the runtime on `ark-bls12-381`, without host calls, so relay chain validators
+//! can check blocks holding anonymous ballots; this makes a verification expensive, see
+//! `WeightInfo::cast_anonymous_vote`. [`MembershipCircuitKey`] is the verifying key of the
+//! circuit in `membership-circuit`.
+
+use crate::membership::{FieldElement, MembershipVerifier};
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective, G2Affine};
+use ark_ec::{
+	pairing::{Pairing, PairingOutput},
+	AdditiveGroup, AffineRepr, CurveGroup, VariableBaseMSM,
+};
+use ark_scale::ark_serialize::CanonicalDeserialize;
+use frame::prelude::*;
+
+/// Compressed G1 point.
+pub type G1Bytes = [u8; 48];
+
+/// Compressed G2 point.
+pub type G2Bytes = [u8; 96];
+
+/// Groth16 verifying key of a circuit with four public inputs.
+#[derive(Encode, Decode, TypeInfo, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct Groth16VerifyingKey {
+	/// `alpha * G1`.
+	pub alpha_g1: G1Bytes,
+	/// `beta * G2`.
+	pub beta_g2: G2Bytes,
+	/// `gamma * G2`.
+	pub gamma_g2: G2Bytes,
+	/// `delta * G2`.
+	pub delta_g2: G2Bytes,
+	/// Constant term, then one point per public input.
+	pub ic: [G1Bytes; 5],
+}
+
+/// Groth16 proof.
+#[derive(Encode, Decode, TypeInfo, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct Groth16Proof {
+	/// `A` in G1.
+	pub a: G1Bytes,
+	/// `B` in G2.
+	pub b: G2Bytes,
+	/// `C` in G1.
+	pub c: G1Bytes,
+}
+
+fn g1(bytes: &G1Bytes) -> Option<G1Affine> {
+	G1Affine::deserialize_compressed(&bytes[..]).ok()
+}
+
+fn g2(bytes: &G2Bytes) -> Option<G2Affine> {
+	G2Affine::deserialize_compressed(&bytes[..]).ok()
+}
+
+fn decode_exact<T: Decode>(mut bytes: &[u8]) -> Option<T> {
+	let value = T::decode(&mut bytes).ok()?;
+	bytes.is_empty().then_some(value)
+}
+
+/// Decoded verifying key.
+struct PreparedKey {
+	alpha_g1: G1Affine,
+	beta_g2: G2Affine,
+	gamma_g2: G2Affine,
+	delta_g2: G2Affine,
+	ic: [G1Affine; 5],
+}
+
+/// The decoded verifying key, or `None` if a point is invalid.
+fn prepare(key: &Groth16VerifyingKey) -> Option<PreparedKey> {
+	let mut ic = [G1Affine::zero(); 5];
+	for (point, bytes) in ic.iter_mut().zip(&key.ic) {
+		*point = g1(bytes)?;
+	}
+	Some(PreparedKey {
+		alpha_g1: g1(&key.alpha_g1)?,
+		beta_g2: g2(&key.beta_g2)?,
+		gamma_g2: g2(&key.gamma_g2)?,
+		delta_g2: g2(&key.delta_g2)?,
+		ic,
+	})
+}
+
+/// Whether `proof` is valid, or `None` if an input is malformed.
+fn verify_proof(
+	verifying_key: &Groth16VerifyingKey,
+	public_inputs: &[FieldElement; 4],
+	proof: &[u8],
+) -> Option<bool> {
+	let proof = decode_exact::<Groth16Proof>(proof)?;
+	let (a, b, c) = (g1(&proof.a)?, g2(&proof.b)?, g1(&proof.c)?);
+	let key = prepare(verifying_key)?;
+
+	// Non-canonical field elements are rejected
+	let inputs = public_inputs
+		.iter()
+		.map(|input| Fr::deserialize_compressed(&input[..]).ok())
+		.collect::<Option<sp_std::vec::Vec<_>>>()?;
+	let vk_x = key.ic[0].into_group() + G1Projective::msm(&key.ic[1..], &inputs).ok()?;
+
+	// All four pairings share a single final exponentiation
+	let product = Bls12_381::multi_pairing(
+		[-a, key.alpha_g1, vk_x.into_affine(), c],
+		[b, key.beta_g2, key.gamma_g2, key.delta_g2],
+	);
+	Some(product == PairingOutput::ZERO)
+}
+
+/// Groth16 over BLS12-381, under the verifying key `Key` pins.
+pub struct Groth16Verifier<Key>(PhantomData<Key>);
+
+impl<Key: Get<Groth16VerifyingKey>> MembershipVerifier for Groth16Verifier<Key> {
+	const ENABLED: bool = true;
+
+	fn verify(public_inputs: &[FieldElement; 4], proof: &[u8]) -> bool {
+		verify_proof(&Key::get(), public_inputs, proof).unwrap_or(false)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn prove(public_inputs: &[FieldElement; 4]) -> Option<sp_std::vec::Vec<u8>> {
+		if Key::get() != trapdoor::verifying_key() {
+			return None
+		}
+		trapdoor::prove(public_inputs).map(|proof| proof.encode())
+	}
+}
+
+/// Verifying key of the circuit in `membership-circuit`, from the setup whose proving key is
+/// `membership-circuit/keys/membership.pk`.
+///
+/// That setup had a single party, so the key is only as sound as that party's discarding its
+/// randomness: a chain holding real elections should replace it with the outcome of a
+/// multi-party ceremony, and ship the matching proving key to its voters.
+pub struct MembershipCircuitKey;
+impl Get<Groth16VerifyingKey> for MembershipCircuitKey {
+	fn get() -> Groth16VerifyingKey {
+		Groth16VerifyingKey {
+			alpha_g1: [
+				0xb5, 0x17, 0xaf, 0x2b, 0xba, 0x77, 0xcb, 0xba, 0x31, 0x50, 0xfc, 0xba, 0x81, 0xf8,
+				0x28, 0x42, 0xe5, 0x70, 0x3d, 0xaa, 0x4b, 0xad, 0xb4, 0xcb, 0xa0, 0x5e, 0xb7, 0x1d,
+				0x5b, 0xe2, 0x8c, 0xc2, 0xf8, 0x3c, 0x72, 0x7c, 0xc7, 0x04, 0x55, 0xdf, 0xd1, 0x3e,
+				0x67, 0x11, 0xbb, 0x73, 0x51, 0xe3,
+			],
+			beta_g2: [
+				0xa4, 0x8a, 0xa2, 0xd4, 0x1e, 0x64, 0xdc, 0x11, 0x5f, 0x48, 0x86, 0x11, 0x80, 0xfc,
+				0x36, 0x7d, 0x59, 0x7b, 0xd4, 0xf0, 0x0e, 0x60, 0xe7, 0x89, 0x75, 0x6b, 0xf5, 0x16,
+				0x2a, 0x78, 0x63, 0xac, 0xa5, 0x0d, 0x05, 0x65, 0x16, 0x8d, 0xfc, 0xe3, 0x4f, 0xe8,
+				0x6f, 0x47, 0x47, 0x6e, 0x62, 0xe2, 0x01, 0x03, 0x5e, 0xb9, 0xa0, 0x0d, 0x4c, 0x16,
+				0xc9, 0x6a, 0x23, 0xb6, 0xf9, 0x95, 0x6e, 0xe0, 0xfa, 0xe4, 0x01, 0x14, 0x15, 0xf4,
+				0x81, 0x4d, 0xe3, 0xba, 0xee, 0xc5, 0x2c, 0x88, 0x1e, 0x13, 0x16, 0x90, 0x00, 0x30,
+				0x18, 0xf9, 0x33, 0x25, 0xcc, 0xf9, 0xa2, 0x93, 0x9f, 0x54, 0x2c, 0xe6,
+			],
+			gamma_g2: [
+				0xb5, 0x01, 0x35, 0x05, 0xdf, 0x23, 0xe7, 0xb1, 0x0c, 0xea, 0xe1, 0xe3, 0x7a, 0xea,
+				0xf5, 0x0a, 0x39, 0xc9, 0x9f, 0x2c, 0xea, 0x62, 0xd6, 0x34, 0x92, 0x0e, 0xd4, 0x62,
+				0x62, 0x16, 0xf7, 0x70, 0xb8, 0xda, 0x82, 0xda, 0x3c, 0x60, 0x6a, 0xbe, 0x41, 0x4e,
+				0xc4, 0x98, 0x6a, 0x28, 0xc3, 0xf9, 0x11, 0xdc, 0xb7, 0xd1, 0xc2, 0xb5, 0x43, 0x01,
+				0xe3, 0x14, 0x46, 0x8d, 0x0d, 0xde, 0x2d, 0x79, 0x58, 0x64, 0x39, 0x33, 0xce, 0x8d,
+				0x8d, 0x45, 0xf5, 0x41, 0x17, 0xf4, 0x1b, 0x87, 0x44, 0x7b, 0x58, 0xa7, 0x07, 0x7b,
+				0x75, 0xcd, 0x7e, 0x4d, 0xb8, 0x6b, 0x6c, 0x34, 0x1b, 0xe8, 0xc8, 0x69,
+			],
+			delta_g2: [
+				0xa5, 0xd1, 0xb7, 0xc1, 0xcb, 0xa5, 0x03, 0x7f, 0x28, 0xab, 0x2e, 0xe5, 0xc8, 0xf4,
+				0x43, 0x0a, 0x17, 0x13, 0xbd, 0x0b, 0x57, 0xca, 0x96, 0xfe, 0xce, 0xb4, 0x58, 0x92,
+				0x08, 0xc7, 0x98, 0x0d, 0x69, 0xec, 0xc2, 0x26, 0xdf, 0x41, 0xed, 0x1d, 0xd2, 0x2b,
+				0xc3, 0x2f, 0x2b, 0x31, 0xf0, 0x49, 0x19, 0x44, 0xab, 0xc7, 0x2f, 0x2f, 0xc9, 0x38,
+				0x8e, 0xf0, 0x61, 0xa6, 0xab, 0x6f, 0x90, 0xda, 0x5f, 0x8e, 0xca, 0xaf, 0xb7, 0xa9,
+				0x89, 0x0b, 0xb1, 0xba, 0xa0, 0xe2, 0x93, 0x58, 0xb1, 0x45, 0xee, 0xba, 0xdf, 0x27,
+				0x92, 0xb4, 0x43, 0x27, 0x3d, 0x0a, 0x26, 0x9b, 0x51, 0x22, 0xd6, 0x45,
+			],
+			ic: [
+				[
+					0xa0, 0xac, 0x51, 0xc7, 0x8d, 0x8b, 0x99, 0x6d, 0xd1, 0x67, 0x8a, 0x7d, 0x5a,
+					0x32, 0x4d, 0x1f, 0x1b, 0xb7, 0x02, 0x79, 0xf6, 0x47, 0xb3, 0x2a, 0x99, 0x57,
+					0x2e, 0xa8, 0x04, 0x77, 0xcb, 0x36, 0x70, 0x29, 0x76, 0xf8, 0xc4, 0xc5, 0x01,
+					0xbd, 0xa8, 0xc4, 0xbb, 0xd6, 0xd2, 0xc9, 0x15, 0x3e,
+				],
+				[
+					0x98, 0x26, 0x25, 0x24, 0x24, 0x9f, 0xec, 0xb0, 0x4d, 0xc6, 0x73, 0x4b, 0xec,
+					0xcf, 0x85, 0xe0, 0x39, 0xdf, 0x28, 0xa3, 0x87, 0xdd, 0x06, 0xe1, 0x44, 0x49,
+					0xd5, 0xf5, 0x77, 0x74, 0x66, 0x79, 0x94, 0xb6, 0x9a, 0x02, 0x60, 0x40, 0x55,
+					0xab, 0x58, 0x29, 0x77, 0xd5, 0x74, 0x7e, 0x99, 0x90,
+				],
+				[
+					0xa4, 0x2f, 0x33, 0xa3, 0x48, 0x8f, 0x27, 0xfd, 0x4b, 0x41, 0xd6, 0xc3, 0x32,
+					0x67, 0xd1, 0xf2, 0xc3, 0x5c, 0xde, 0xaf, 0x52, 0xd6, 0x36, 0x6f, 0x50, 0x3b,
+					0xa5, 0xcc, 0x23, 0x3a, 0x89, 0x62, 0x36, 0x23, 0x82, 0x14, 0x51, 0x08, 0x00,
+					0x29, 0x78, 0xbf, 0xa9, 0x27, 0xbb, 0xcc, 0x2a, 0x96,
+				],
+				[
+					0xa3, 0x32, 0x05, 0xe5, 0xbf, 0x20, 0x63, 0x43, 0xb5, 0x3b, 0x48, 0xaf, 0x79,
+					0xb4, 0x50, 0x66, 0x59, 0x4c, 0xf9, 0x5f, 0x98, 0xd1, 0x70, 0x94, 0xc7, 0xb8,
+					0x46, 0x76, 0x0a, 0x9a, 0xe2, 0x30, 0x79, 0xeb, 0x11, 0xbc, 0x09, 0x1a, 0xfd,
+					0x39, 0x08, 0xd4, 0x61, 0xbb, 0x4c, 0x07, 0x23, 0x1c,
+				],
+				[
+					0xa3, 0xee, 0x0d, 0x9b, 0xfd, 0xf3, 0x82, 0x7e, 0x70, 0x24, 0x48, 0xe1, 0x96,
+					0x9f, 0x76, 0xf9, 0x7f, 0x6a, 0x06, 0x90, 0x93, 0xe4, 0x47, 0x62, 0x30, 0x42,
+					0x93, 0x02, 0xb5, 0x94, 0x0f, 0x5e, 0x86, 0x8c, 0x8c, 0xd9, 0x35, 0xbe, 0x99,
+					0x1e, 0x17, 0x05, 0x73, 0xd0, 0xd6, 0x6b, 0x10, 0xf8,
+				],
+			],
+		}
+	}
+}
+
+/// A proof made with `membership-circuit` under [`MembershipCircuitKey`].
+#[cfg(test)]
+pub mod circuit_proof {
+	use crate::{membership::FieldElement, ElectionId};
+
+	/// Election the proof is for.
+	pub const ELECTION_ID: ElectionId = 0;
+	/// Ballot the proof is bound to.
+	pub const CIPHERTEXT: &[u8] = b"ballot";
+	/// Root of an allowlist of three identities, the second of which made the proof.
+	pub const ROOT: FieldElement = [
+		0xcd, 0x45, 0x90, 0x43, 0xe9, 0xbf, 0x08, 0x22, 0x1a, 0xba, 0x4e, 0xcb, 0xec, 0xda, 0x06,
+		0xe8, 0xf3, 0xca, 0xf7, 0x4e, 0x5b, 0xcb, 0xd3, 0x81, 0x08, 0xf4, 0xd9, 0x17, 0xb9, 0x56,
+		0x7d, 0x55,
+	];
+	/// Nullifier of the second identity in the election.
+	pub const NULLIFIER: FieldElement = [
+		0x2c, 0xc2, 0x30, 0x37, 0x69, 0xa6, 0xea, 0x4c, 0xf3, 0xcd, 0xd5, 0xe5, 0x41, 0xb4, 0xfc,
+		0x71, 0xa3, 0xe5, 0xd3, 0x3a, 0xf4, 0x15, 0x55, 0x97, 0x12, 0xab, 0xfa, 0x84, 0x09, 0x21,
+		0xac, 0x51,
+	];
+	/// The encoded proof.
+	pub const PROOF: [u8; 192] = [
+		0xad, 0x9b, 0xec, 0x7b, 0x87, 0xd1, 0xa5, 0x6f, 0xc1, 0xf4, 0x1e, 0x7d, 0x87, 0x6f, 0x31,
+		0xd2, 0x2e, 0x8b, 0x47, 0xe0, 0xfe, 0xa5, 0xe6, 0xa9, 0x7a, 0xf7, 0x41, 0x0a, 0x7c, 0xac,
+		0x4f, 0x07, 0x52, 0x07, 0x8e, 0xa5, 0xcc, 0x2e, 0x58, 0xa1, 0xf4, 0xf8, 0x25, 0x6f, 0x1d,
+		0x30, 0x29, 0x01, 0x83, 0xef, 0x45, 0x9d, 0x1d, 0x3b, 0x4f, 0xbc, 0x9a, 0x75, 0x06, 0x1a,
+		0x60, 0x92, 0xb9, 0x05, 0xfc, 0x52, 0x63, 0x09, 0xd8, 0xcb, 0xfa, 0xb5, 0x15, 0x15, 0x79,
+		0x0e, 0x59, 0x98, 0xa0, 0x26, 0x5b, 0xa3, 0xc8, 0x1e, 0x94, 0xa0, 0xa3, 0x8e, 0xb0, 0xba,
+		0xfd, 0xe8, 0x19, 0x06, 0x47, 0xe7, 0x03, 0x6e, 0x44, 0x2a, 0x91, 0x73, 0xd4, 0xbe, 0x3b,
+		0xb3, 0xbd, 0x53, 0x33, 0x4e, 0x6e, 0x28, 0x55, 0xad, 0x19, 0xe7, 0x25, 0xfd, 0xfe, 0x72,
+		0xc5, 0xe9, 0x11, 0x73, 0x64, 0x88, 0x02, 0xee, 0x74, 0x18, 0xb8, 0xc0, 0x1a, 0x6a, 0x2e,
+		0x78, 0xdb, 0x92, 0xaf, 0xf7, 0x7f, 0xf4, 0x17, 0x9a, 0x89, 0xf0, 0x48, 0x5b, 0x09, 0xfd,
+		0x38, 0x0e, 0xc5, 0x51, 0x5b, 0x5a, 0xc9, 0x12, 0x84, 0xc6, 0x01, 0x87, 0xc7, 0x95, 0xa1,
+		0x77, 0x00, 0x95, 0xeb, 0xe5, 0x57, 0x48, 0x3e, 0x00, 0xee, 0x65, 0xe2, 0xb8, 0xcf, 0xf8,
+		0xe9, 0x66, 0x8d, 0x02, 0x4e, 0x56, 0x70, 0x2e, 0xc3, 0x23, 0x97, 0x63,
+	];
+}
+
+/// A verifying key whose trapdoor is known, so anything can be proven under it.
+///
+/// Verification costs the same under any key, so benchmarks pin this one to make valid proofs.
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+pub mod trapdoor {
+	use super::*;
+	use ark_scale::ark_serialize::CanonicalSerialize;
+
+	/// `alpha = ALPHA * G1` and `beta = BETA * G2`; `gamma` and `delta` are `G2` and
+	/// `ic_i = (i + 1) * G1`.
+	const ALPHA: u64 = 2;
+	const BETA: u64 = 3;
+
+	fn g1_bytes(scalar: Fr) -> G1Bytes {
+		let mut bytes = [0; 48];
+		let point = (G1Affine::generator() * scalar).into_affine();
+		point
+			.serialize_compressed(&mut bytes[..])
+			.expect("a compressed G1 point is 48 bytes");
+		bytes
+	}
+
+	fn g2_bytes(scalar: Fr) -> G2Bytes {
+		let mut bytes = [0; 96];
+		let point = (G2Affine::generator() * scalar).into_affine();
+		point
+			.serialize_compressed(&mut bytes[..])
+			.expect("a compressed G2 point is 96 bytes");
+		bytes
+	}
+
+	/// The verifying key.
+	pub fn verifying_key() -> Groth16VerifyingKey {
+		Groth16VerifyingKey {
+			alpha_g1: g1_bytes(Fr::from(ALPHA)),
+			beta_g2: g2_bytes(Fr::from(BETA)),
+			gamma_g2: g2_bytes(Fr::from(1u64)),
+			delta_g2: g2_bytes(Fr::from(1u64)),
+			ic: core::array::from_fn(|i| g1_bytes(Fr::from(i as u64 + 1))),
+		}
+	}
+
+	/// Pins `verifying_key` in `Groth16Verifier`.
+	pub struct TrapdoorKey;
+	impl Get<Groth16VerifyingKey> for TrapdoorKey {
+		fn get() -> Groth16VerifyingKey {
+			verifying_key()
+		}
+	}
+
+	/// A proof for `public_inputs`, or `None` if one is not a canonical field element.
+	///
+	/// With `vk_x = x * G1`, `B = G2` and `C = G1`, the pairing equation holds for
+	/// `A = (ALPHA * BETA + x + 1) * G1`.
+	pub fn prove(public_inputs: &[FieldElement; 4]) -> Option<Groth16Proof> {
+		let mut x = Fr::from(1u64);
+		for (i, input) in public_inputs.iter().enumerate() {
+			x += Fr::deserialize_compressed(&input[..]).ok()? * Fr::from(i as u64 + 2);
+		}
+		Some(Groth16Proof {
+			a: g1_bytes(Fr::from(ALPHA * BETA) + x + Fr::from(1u64)),
+			b: g2_bytes(Fr::from(1u64)),
+			c: g1_bytes(Fr::from(1u64)),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::membership;
+
+	fn inputs(nullifier: u8) -> [FieldElement; 4] {
+		membership::public_inputs(0, &[1; 32], &[nullifier; 32], b"ballot")
+	}
+
+	fn verify(public_inputs: &[FieldElement; 4], proof: &[u8]) -> bool {
+		Groth16Verifier::<trapdoor::TrapdoorKey>::verify(public_inputs, proof)
+	}
+
+	#[test]
+	fn proofs_verify_for_their_public_inputs_only() {
+		let proof = trapdoor::prove(&inputs(7)).unwrap();
+		assert!(verify(&inputs(7), &proof.encode()));
+		assert!(!verify(&inputs(8), &proof.encode()));
+
+		let swapped = Groth16Proof { a: proof.c, c: proof.a, ..proof.clone() };
+		assert!(!verify(&inputs(7), &swapped.encode()));
+	}
+
+	#[test]
+	fn malformed_proofs_and_inputs_are_rejected() {
+		let proof = trapdoor::prove(&inputs(7)).unwrap().encode();
+		assert!(!verify(&inputs(7), &proof[1..]));
+		assert!(!verify(&inputs(7), &[&proof[..], &[0]].concat()));
+
+		let mut invalid_point = proof.clone();
+		invalid_point[..48].copy_from_slice(&[0xff; 48]);
+		assert!(!verify(&inputs(7), &invalid_point));
+
+		// The scalar field modulus is below 2^255
+		let mut non_canonical = inputs(7);
+		non_canonical[1] = [0xff; 32];
+		assert!(trapdoor::prove(&non_canonical).is_none());
+		assert!(!verify(&non_canonical, &proof));
+	}
+
+	#[test]
+	fn membership_circuit_proofs_verify_under_the_pinned_key() {
+		use circuit_proof::*;
+		let verify = |nullifier: &FieldElement, ciphertext: &[u8], proof: &[u8]| {
+			let inputs = membership::public_inputs(ELECTION_ID, &ROOT, nullifier, ciphertext);
+			Groth16Verifier::<MembershipCircuitKey>::verify(&inputs, proof)
+		};
+
+		assert!(verify(&NULLIFIER, CIPHERTEXT, &PROOF));
+		assert!(!verify(&[2; 32], CIPHERTEXT, &PROOF));
+		assert!(!verify(&NULLIFIER, b"another ballot", &PROOF));
+
+		// Proofs under the trapdoor key are worthless under the circuit's
+		let inputs = membership::public_inputs(ELECTION_ID, &ROOT, &NULLIFIER, CIPHERTEXT);
+		let forged = trapdoor::prove(&inputs).unwrap();
+		assert!(!verify(&NULLIFIER, CIPHERTEXT, &forged.encode()));
+	}
+}
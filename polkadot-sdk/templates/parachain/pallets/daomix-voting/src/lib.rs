@@ -8,18 +8,152 @@
 
 pub use pallet::*;
 
+pub mod dkg;
+pub mod elgamal;
+pub mod groth16;
+pub mod membership;
+pub mod merkle;
+pub mod migrations;
+pub mod runtime_api;
+pub mod shuffle;
+pub mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
 #[frame::pallet]
 pub mod pallet {
-	use frame::prelude::*;
+	use crate::{
+		dkg::{self, GroupElement, ShareScalar},
+		elgamal::{self, ElGamalBallot, OptionCounts, PartialDecryption},
+		membership::{self, FieldElement, MembershipProof, MembershipVerifier, Nullifier},
+		merkle::{self, MerkleHash, Peaks},
+		shuffle::{self, HopCommitment, HopIndex, LinkChallenge, LinkOpening, ShuffleLinkVerifier},
+		weights::WeightInfo,
+	};
+	use frame::{deps::frame_support::storage::with_storage_layer, prelude::*};
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		#[allow(deprecated)]
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// A type representing the weights required by the dispatchables of this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Maximum number of automatic phase transitions scheduled for a single block.
+		///
+		/// This bounds the work done by `on_initialize`.
+		#[pallet::constant]
+		type MaxPhaseTransitionsPerBlock: Get<u32>;
+
+		/// Maximum ballots hashed into input roots by a single block.
+		///
+		/// This bounds the work done by `on_initialize` once voting closes. Each ballot costs
+		/// `WeightInfo::hash_ballot`, whose proof holds a whole `Ciphertext`, so this should
+		/// keep that many ballots within the `on_initialize` share of the PoV budget.
+		#[pallet::constant]
+		type MaxBallotsHashedPerBlock: Get<u32>;
+
+		/// Checks that an opened link of a mix hop is a valid decryption.
+		///
+		/// Tallies of elections with a mix cascade wait for a proven shuffle whatever it checks;
+		/// with `()` the proof only covers the commitments of each hop.
+		type LinkVerifier: ShuffleLinkVerifier;
+
+		/// Links challenged in each pair of mix hops.
+		///
+		/// Also the most challenges a single hop can get.
+		#[pallet::constant]
+		type ShuffleChallengesPerPair: Get<u32>;
+
+		/// Maximum link openings checked by a single `prove_shuffle_links` call.
+		#[pallet::constant]
+		type MaxLinksPerProof: Get<u32>;
+
+		/// Overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// Currency holding tally and dispute bonds.
+		type Currency: fungible::Mutate<Self::AccountId>
+			+ fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// Deposit held from the admin of each election until it is finalized or cancelled.
+		///
+		/// Every election schedules phase transitions, so this prices the `PhaseSchedule`
+		/// slots an account can take.
+		#[pallet::constant]
+		type ElectionDeposit: Get<BalanceOf<Self>>;
+
+		/// Blocks after a tally is submitted during which it can be disputed.
+		#[pallet::constant]
+		type ChallengePeriod: Get<BlockNumberFor<Self>>;
+
+		/// Bond held from the tally authority with each tally, paid to the challenger if a
+		/// dispute is upheld.
+		#[pallet::constant]
+		type TallyBond: Get<BalanceOf<Self>>;
+
+		/// Bond held from each challenger, paid to the tally authority if the dispute is
+		/// rejected.
+		#[pallet::constant]
+		type DisputeBond: Get<BalanceOf<Self>>;
+
+		/// Maximum open disputes against a single tally.
+		#[pallet::constant]
+		type MaxDisputes: Get<u32>;
+
+		/// Origin that resolves tally disputes (e.g. root or governance).
+		type DisputeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Maximum trustees in a tally committee.
+		#[pallet::constant]
+		type MaxTrustees: Get<u32>;
+
+		/// Blocks each round of a tally committee DKG (dealing, complaints, answers) lasts.
+		#[pallet::constant]
+		type DkgRoundPeriod: Get<BlockNumberFor<Self>>;
+
+		/// Checks the membership proofs of anonymous ballots under the verifying key it pins (see
+		/// `membership`). With `()`, elections cannot be anonymous.
+		type MembershipVerifier: MembershipVerifier;
+
+		/// Pallet id whose sub-accounts hold the funding pot of each election.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Amount the funding pot of an election pays for each feeless ballot.
+		#[pallet::constant]
+		type FeelessBallotFee: Get<BalanceOf<Self>>;
+
+		/// Feeless ballots each voter, or each nullifier of an anonymous election, may cast.
+		///
+		/// This is the spam bound of sponsored and unsigned ballots.
+		#[pallet::constant]
+		type MaxFeelessBallots: Get<u32>;
+
+		/// Transaction pool priority of unsigned anonymous ballots.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// Blocks an unsigned anonymous ballot stays valid in the pool at most, so one is not
+		/// kept and re-checked until voting closes.
+		#[pallet::constant]
+		type UnsignedLongevity: Get<TransactionLongevity>;
 	}
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Type aliases for clarity.
@@ -27,6 +161,248 @@ pub mod pallet {
 	pub type BallotIndex = u32;
 	pub type Ciphertext = BoundedVec<u8, ConstU32<65536>>; // Stores onion-encrypted ballots (max 64KB)
 	pub type RootHash<T> = <T as frame_system::Config>::Hash;
+	pub type X25519PublicKey = [u8; 32];
+	pub type MlKemPublicKey = [u8; 1184]; // ML-KEM-768 encapsulation key
+	pub type MixCascade<K> = BoundedVec<K, ConstU32<16>>; // At most 16 mix hops per election
+	pub type BalanceOf<T> = <<T as Config>::Currency as fungible::Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+	pub type DisputeOf<T> = Dispute<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+	pub type EncryptedShare = BoundedVec<u8, ConstU32<128>>; // A DKG share sealed to a trustee
+
+	/// Maximum ballots returned by one `DaoMixApi::ballots` page.
+	pub const MAX_BALLOTS_PER_PAGE: u32 = 256;
+
+	/// Blocks after a full `PhaseSchedule` entry that are tried before scheduling fails.
+	pub const MAX_SCHEDULE_SPILL: u32 = 64;
+
+	/// Reasons the pallet holds funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Deposit of the admin while its election is open.
+		ElectionDeposit,
+		/// Bond of the tally authority while its tally can be disputed.
+		TallyBond,
+		/// Bond of a challenger while its dispute is open.
+		DisputeBond,
+	}
+
+	/// Public keys voters need to build onions for an election.
+	#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Eq, PartialEq, RuntimeDebug)]
+	pub struct ElectionKeys {
+		/// X25519 public keys of the mix cascade, in peeling order.
+		pub mix_keys: MixCascade<X25519PublicKey>,
+		/// Optional ML-KEM-768 public keys, one per mix node in the same order.
+		pub pq_mix_keys: Option<MixCascade<MlKemPublicKey>>,
+		/// Public key of the innermost onion layer: the X25519 key of the tally authority, or
+		/// the committee key if the election has a tally committee.
+		pub tally_key: X25519PublicKey,
+	}
+
+	/// What happens when a registered voter casts a second ballot in the same election.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, Eq, PartialEq, RuntimeDebug,
+		DecodeWithMemTracking,
+	)]
+	pub enum RevotePolicy {
+		/// Only the first ballot is accepted; later ones are rejected.
+		RejectRevote,
+		/// A later ballot replaces the earlier one (last vote wins).
+		LastVoteWins,
+	}
+
+	/// How the ballots of an election are counted.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, Eq, PartialEq, RuntimeDebug,
+		DecodeWithMemTracking,
+	)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	pub enum TallyMode {
+		/// Onion ballots are mixed, then decrypted and counted one by one.
+		Mixnet,
+		/// Ballots are exponential ElGamal vectors over `options` choices, added up on-chain and
+		/// only decrypted as a sum by the tally committee (see `elgamal`).
+		Homomorphic { options: u8 },
+	}
+
+	/// Lifecycle phase of an election.
+	///
+	/// `Registration -> Voting -> Mixing` is driven by `on_initialize` once the deadlines pass,
+	/// `Mixing -> Tallying -> Disputable` by the tally authority, and `Disputable -> Finalized`
+	/// by `on_initialize` once the challenge window closes without an open dispute. An upheld
+	/// dispute moves the election back to `Tallying`, and any open phase can move to
+	/// `Cancelled` by the admin.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, Eq, PartialEq, RuntimeDebug,
+		DecodeWithMemTracking,
+	)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	pub enum ElectionPhase {
+		/// Voters can be registered.
+		Registration,
+		/// Registered voters can cast ballots.
+		Voting,
+		/// Voting is closed; waiting for the mix commitments.
+		Mixing,
+		/// Mix commitments are set; waiting for the tally.
+		Tallying,
+		/// The tally has been submitted and can be disputed until the challenge window closes.
+		Disputable,
+		/// The tally is final.
+		Finalized,
+		/// The election was cancelled by its admin.
+		Cancelled,
+	}
+
+	impl ElectionPhase {
+		/// Whether the election has reached a terminal phase.
+		pub fn is_closed(&self) -> bool {
+			matches!(self, ElectionPhase::Finalized | ElectionPhase::Cancelled)
+		}
+	}
+
+	/// Phase, deadlines and ballot counts of an election, as returned by `DaoMixApi`.
+	#[derive(Encode, Decode, TypeInfo, Clone, Eq, PartialEq, RuntimeDebug)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct ElectionStatus<BlockNumber> {
+		/// Current lifecycle phase.
+		pub phase: ElectionPhase,
+		/// How ballots are counted.
+		pub tally_mode: TallyMode,
+		/// Block number after which voter registration closes.
+		pub registration_deadline: BlockNumber,
+		/// Block number after which voting closes.
+		pub voting_deadline: BlockNumber,
+		/// Ballots cast, including replaced ones.
+		pub ballot_count: BallotIndex,
+		/// Ballots not replaced by a later ballot of the same voter.
+		pub valid_ballot_count: BallotIndex,
+		/// Whether the mix commitments are set.
+		pub has_mix_commitments: bool,
+		/// Last block in which the submitted tally can be disputed.
+		pub challenge_deadline: Option<BlockNumber>,
+	}
+
+	/// Merkle inclusion proof of a ballot in the input root of its election.
+	///
+	/// Check it with `merkle::verify_proof(&root, leaf, position, leaf_count, &proof)`.
+	#[derive(Encode, Decode, TypeInfo, Clone, Eq, PartialEq, RuntimeDebug)]
+	pub struct BallotProof {
+		/// Index of the ballot in `Ballots`.
+		pub index: BallotIndex,
+		/// Keccak-256 hash of the ciphertext, as emitted in `BallotCast`.
+		pub leaf: MerkleHash,
+		/// Position of the leaf among the valid ballots of the election.
+		pub position: u32,
+		/// Number of valid ballots of the election.
+		pub leaf_count: u32,
+		/// Sibling hashes from the leaf up to the root.
+		pub proof: sp_std::vec::Vec<MerkleHash>,
+		/// Input root of the election, which any accepted `commitment_input_root` equals.
+		pub root: MerkleHash,
+	}
+
+	/// Progress of the input root of an election in the `Mixing` phase.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Default, Eq, PartialEq, RuntimeDebug,
+	)]
+	pub struct InputRootBuilder {
+		/// Next ballot index to hash.
+		pub next_index: BallotIndex,
+		/// Valid ballots hashed into `peaks` so far.
+		pub leaf_count: u32,
+		/// Peaks of the tree over the hashed ballots.
+		pub peaks: Peaks,
+	}
+
+	/// Progress of the shuffle proof of an election, see `shuffle`.
+	#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+	pub enum ShuffleStage<BlockNumber> {
+		/// Hops `0..committed` of the cascade are committed.
+		Committing { committed: HopIndex },
+		/// Every hop is committed; challenges are drawn from the hash of `seed_block`.
+		Challenging { seed_block: BlockNumber },
+		/// Challenges are drawn and `open` of them are not answered yet.
+		Proving { open: u32 },
+		/// Every challenge is answered.
+		Proven,
+	}
+
+	/// Evidence that a submitted tally is wrong, judged by `Config::DisputeOrigin`.
+	#[derive(
+		Encode,
+		Decode,
+		DecodeWithMemTracking,
+		MaxEncodedLen,
+		TypeInfo,
+		Clone,
+		Eq,
+		PartialEq,
+		RuntimeDebug,
+	)]
+	pub enum DisputeEvidence {
+		/// A valid ballot the tally does not count.
+		MissingBallot { index: BallotIndex },
+		/// A root the mix or the tally should have produced, and the one committed instead.
+		RootMismatch { expected: MerkleHash, committed: MerkleHash },
+		/// A ballot counted for a different vote than it decrypts to; `evidence_uri` locates
+		/// the decryption.
+		InvalidDecryption { leaf: MerkleHash, evidence_uri: BoundedVec<u8, ConstU32<256>> },
+	}
+
+	/// An open dispute against a submitted tally.
+	#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Eq, PartialEq, RuntimeDebug)]
+	pub struct Dispute<AccountId, Balance> {
+		/// Account that posted the dispute.
+		pub challenger: AccountId,
+		/// Bond held from the challenger.
+		pub bond: Balance,
+		/// Why the tally is wrong.
+		pub evidence: DisputeEvidence,
+	}
+
+	/// Who casts a ballot: whose quota a feeless ballot counts against, and who the validity
+	/// proof of a homomorphic ballot is made for (see `Pallet::ballot_context`).
+	#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Eq, PartialEq, RuntimeDebug)]
+	pub enum BallotSender<AccountId> {
+		/// A registered voter, through `cast_vote`.
+		Voter(AccountId),
+		/// An anonymous voter, through an unsigned `cast_anonymous_vote`.
+		Nullifier(Nullifier),
+	}
+
+	/// Trustees sharing the tally key of an election `threshold`-of-n, see `dkg`.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, CloneNoBound, PartialEqNoBound, DebugNoBound,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct TallyCommittee<T: Config> {
+		/// Trustee accounts with the X25519 keys their DKG shares are sealed to. The share
+		/// index of a trustee is its position here plus one.
+		pub trustees: BoundedVec<(T::AccountId, X25519PublicKey), T::MaxTrustees>,
+		/// Partial decryptions needed to decrypt the tally.
+		pub threshold: u32,
+		/// Block at which the current DKG run started.
+		pub dkg_start: BlockNumberFor<T>,
+		/// Commitments to the committee polynomial, lowest degree first, set once the DKG
+		/// completes. Evaluated at a share index, they give that trustee's verification key.
+		pub commitments: BoundedVec<GroupElement, T::MaxTrustees>,
+		/// Combined tally public key (Ristretto255), set once the DKG completes.
+		pub public_key: Option<GroupElement>,
+	}
+
+	/// What a trustee dealt in the DKG of a tally committee.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, CloneNoBound, PartialEqNoBound, DebugNoBound,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct DkgDeal<T: Config> {
+		/// Feldman commitments to the dealer's polynomial, lowest degree first.
+		pub commitments: BoundedVec<GroupElement, T::MaxTrustees>,
+		/// Shares for each trustee in committee order, sealed to its X25519 key.
+		pub encrypted_shares: BoundedVec<EncryptedShare, T::MaxTrustees>,
+	}
 
 	/// Election metadata stored on-chain.
 	#[derive(
@@ -36,18 +412,29 @@ pub mod pallet {
 	pub struct Election<T: Config> {
 		/// Admin account that created this election.
 		pub admin: T::AccountId,
+		/// Deposit held from the admin until the election is finalized or cancelled.
+		pub deposit: BalanceOf<T>,
 		/// Tally authority account that can submit final results.
 		pub tally_authority: T::AccountId,
 		/// Block number after which voter registration closes.
 		pub registration_deadline: BlockNumberFor<T>,
 		/// Block number after which voting closes.
 		pub voting_deadline: BlockNumberFor<T>,
-		/// Merkle root hash of input ballots (set after mix phase).
+		/// How repeated ballots from the same voter are handled.
+		pub revote_policy: RevotePolicy,
+		/// How ballots are counted.
+		pub tally_mode: TallyMode,
+		/// Mix cascade and tally public keys (fixed once voting starts).
+		pub keys: Option<ElectionKeys>,
+		/// Tally committee, if the tally needs `threshold` trustees instead of the tally
+		/// authority alone.
+		pub committee: Option<TallyCommittee<T>>,
+		/// Merkle root hash of input ballots (set after mix phase, equal to `InputRoots`).
 		pub commitment_input_root: Option<RootHash<T>>,
 		/// Merkle root hash of output ballots after mixing (set after mix phase).
 		pub commitment_output_root: Option<RootHash<T>>,
-		/// Whether the tally has been finalized.
-		pub finalized: bool,
+		/// Current lifecycle phase.
+		pub phase: ElectionPhase,
 	}
 
 	/// Tally result metadata.
@@ -60,6 +447,10 @@ pub mod pallet {
 		pub result_uri: BoundedVec<u8, ConstU32<256>>,
 		/// Hash of the full tally JSON/result data.
 		pub result_hash: RootHash<T>,
+		/// Last block in which the tally can be disputed.
+		pub challenge_deadline: BlockNumberFor<T>,
+		/// Bond held from the tally authority until the tally is final.
+		pub bond: BalanceOf<T>,
 	}
 
 	/// Storage: Elections mapping ElectionId → Election metadata.
@@ -100,12 +491,239 @@ pub mod pallet {
 	#[pallet::getter(fn ballot_count)]
 	pub type BallotCount<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, BallotIndex, ValueQuery>;
 
+	/// Storage: Current ballot of each voter.
+	/// Double map (ElectionId, AccountId) → BallotIndex of the voter's valid ballot.
+	#[pallet::storage]
+	#[pallet::getter(fn voter_ballot)]
+	pub type VoterBallot<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ElectionId,
+		Blake2_128Concat,
+		T::AccountId,
+		BallotIndex,
+	>;
+
+	/// Storage: Anonymous eligibility of an election, replacing `Voters`.
+	/// Map ElectionId → Merkle root of the voters' identity commitments.
+	#[pallet::storage]
+	#[pallet::getter(fn voter_allowlist)]
+	pub type VoterAllowlists<T: Config> =
+		StorageMap<_, Blake2_128Concat, ElectionId, FieldElement, OptionQuery>;
+
+	/// Storage: Current ballot of each anonymous voter.
+	/// Double map (ElectionId, Nullifier) → BallotIndex of the voter's valid ballot.
+	#[pallet::storage]
+	#[pallet::getter(fn nullifier_ballot)]
+	pub type Nullifiers<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, ElectionId, Blake2_128Concat, Nullifier, BallotIndex>;
+
+	/// Storage: Feeless ballots paid by the funding pot of an election.
+	/// Double map (ElectionId, BallotSender) → count, bounded by `MaxFeelessBallots`.
+	#[pallet::storage]
+	#[pallet::getter(fn feeless_ballots)]
+	pub type FeelessBallots<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ElectionId,
+		Blake2_128Concat,
+		BallotSender<T::AccountId>,
+		u32,
+		ValueQuery,
+	>;
+
+	/// Storage: Ballots that were superseded by a later ballot of the same voter.
+	/// Double map (ElectionId, BallotIndex) → bool (replaced or not).
+	///
+	/// Replaced ballots stay in `Ballots` so indices remain stable, but they must be skipped
+	/// when building the mix input and `commitment_input_root`.
+	#[pallet::storage]
+	#[pallet::getter(fn replaced_ballots)]
+	pub type ReplacedBallots<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ElectionId,
+		Blake2_128Concat,
+		BallotIndex,
+		bool,
+		ValueQuery,
+	>;
+
+	/// Storage: Count of valid (not replaced) ballots per election.
+	#[pallet::storage]
+	#[pallet::getter(fn valid_ballot_count)]
+	pub type ValidBallotCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, ElectionId, BallotIndex, ValueQuery>;
+
 	/// Storage: Final tally results.
 	/// Map ElectionId → TallyResult.
 	#[pallet::storage]
 	#[pallet::getter(fn tally_results)]
 	pub type TallyResults<T: Config> = StorageMap<_, Blake2_128Concat, ElectionId, TallyResult<T>>;
 
+	/// Storage: Open disputes against the submitted tally of an election.
+	#[pallet::storage]
+	#[pallet::getter(fn disputes)]
+	pub type Disputes<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		ElectionId,
+		BoundedVec<DisputeOf<T>, T::MaxDisputes>,
+		ValueQuery,
+	>;
+
+	/// Storage: DKG deals of the tally committee of an election.
+	/// Double map (ElectionId, dealer) → DkgDeal.
+	#[pallet::storage]
+	#[pallet::getter(fn dkg_deals)]
+	pub type DkgDeals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ElectionId,
+		Blake2_128Concat,
+		T::AccountId,
+		DkgDeal<T>,
+		OptionQuery,
+	>;
+
+	/// Storage: DKG complaints of the tally committee of an election.
+	/// Double map (ElectionId, (dealer, complainer)) → bool (answered with a valid share or not).
+	#[pallet::storage]
+	#[pallet::getter(fn dkg_complaints)]
+	pub type DkgComplaints<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ElectionId,
+		Blake2_128Concat,
+		(T::AccountId, T::AccountId),
+		bool,
+		OptionQuery,
+	>;
+
+	/// Storage: Tally result hash each trustee attested to by publishing its partial
+	/// decryption.
+	#[pallet::storage]
+	#[pallet::getter(fn tally_attestations)]
+	pub type TallyAttestations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ElectionId,
+		Blake2_128Concat,
+		T::AccountId,
+		RootHash<T>,
+		OptionQuery,
+	>;
+
+	/// Storage: Sum of the homomorphic ballots of an election that were not replaced.
+	#[pallet::storage]
+	#[pallet::getter(fn encrypted_tallies)]
+	pub type EncryptedTallies<T: Config> =
+		StorageMap<_, Blake2_128Concat, ElectionId, ElGamalBallot, OptionQuery>;
+
+	/// Storage: Checked partial decryptions of the encrypted tally, one share per option.
+	/// Double map (ElectionId, trustee) → shares.
+	#[pallet::storage]
+	#[pallet::getter(fn partial_decryptions)]
+	pub type PartialDecryptions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ElectionId,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<GroupElement, ConstU32<{ elgamal::MAX_OPTIONS }>>,
+		OptionQuery,
+	>;
+
+	/// Storage: Option counts of a homomorphic election, checked against the encrypted tally.
+	#[pallet::storage]
+	#[pallet::getter(fn decrypted_counts)]
+	pub type DecryptedCounts<T: Config> =
+		StorageMap<_, Blake2_128Concat, ElectionId, OptionCounts, OptionQuery>;
+
+	/// Storage: Scheduled automatic phase transitions.
+	/// Map BlockNumber → elections whose phase must be re-evaluated at that block.
+	#[pallet::storage]
+	#[pallet::getter(fn phase_schedule)]
+	pub type PhaseSchedule<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<ElectionId, T::MaxPhaseTransitionsPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Storage: Input roots being computed for elections in the `Mixing` phase.
+	#[pallet::storage]
+	pub type InputRootProgress<T: Config> =
+		StorageMap<_, Blake2_128Concat, ElectionId, InputRootBuilder, OptionQuery>;
+
+	/// Storage: Keccak-256 Merkle root of the valid ballots of an election, computed on-chain
+	/// once voting closes. See `merkle` for the tree layout.
+	#[pallet::storage]
+	#[pallet::getter(fn input_roots)]
+	pub type InputRoots<T: Config> =
+		StorageMap<_, Blake2_128Concat, ElectionId, MerkleHash, OptionQuery>;
+
+	/// Storage: Nodes of the input tree of an election by `(level, index)`, leaves at level 0.
+	///
+	/// Only nodes over a whole power of two of leaves are kept, as the root is built. They let
+	/// `ballot_proof` read the path of one leaf instead of every ballot.
+	#[pallet::storage]
+	pub type InputTreeNodes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ElectionId,
+		Twox64Concat,
+		(u8, u32),
+		MerkleHash,
+		OptionQuery,
+	>;
+
+	/// Storage: Position in the input tree of each valid ballot of an election.
+	#[pallet::storage]
+	pub type InputLeafPositions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ElectionId,
+		Twox64Concat,
+		BallotIndex,
+		u32,
+		OptionQuery,
+	>;
+
+	/// Storage: Commitments of each mix hop of an election.
+	#[pallet::storage]
+	#[pallet::getter(fn shuffle_hops)]
+	pub type ShuffleHops<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ElectionId,
+		Twox64Concat,
+		HopIndex,
+		HopCommitment,
+		OptionQuery,
+	>;
+
+	/// Storage: Links each mix hop of an election must open.
+	#[pallet::storage]
+	#[pallet::getter(fn shuffle_challenges)]
+	pub type ShuffleChallenges<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ElectionId,
+		Twox64Concat,
+		HopIndex,
+		BoundedVec<LinkChallenge, T::ShuffleChallengesPerPair>,
+		ValueQuery,
+	>;
+
+	/// Storage: Progress of the shuffle proof of an election, absent until its first hop is
+	/// committed.
+	#[pallet::storage]
+	#[pallet::getter(fn shuffle_stages)]
+	pub type ShuffleStages<T: Config> =
+		StorageMap<_, Blake2_128Concat, ElectionId, ShuffleStage<BlockNumberFor<T>>, OptionQuery>;
+
 	/// Pallets use events to inform users when important changes are made.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -114,12 +732,94 @@ pub mod pallet {
 		ElectionCreated { election_id: ElectionId },
 		/// A voter registered for an election.
 		VoterRegistered { election_id: ElectionId, voter: T::AccountId },
-		/// A ballot was cast.
-		BallotCast { election_id: ElectionId, voter: T::AccountId, index: BallotIndex },
+		/// A ballot was cast. `ciphertext_hash` is its leaf in the input root.
+		BallotCast {
+			election_id: ElectionId,
+			voter: T::AccountId,
+			index: BallotIndex,
+			ciphertext_hash: MerkleHash,
+		},
+		/// A ballot replaced the voter's earlier ballot (last vote wins).
+		BallotReplaced {
+			election_id: ElectionId,
+			voter: T::AccountId,
+			old_index: BallotIndex,
+			new_index: BallotIndex,
+		},
 		/// Mix commitments were set for an election.
 		MixCommitmentsSet { election_id: ElectionId },
 		/// Tally results were submitted for an election.
 		TallySubmitted { election_id: ElectionId },
+		/// A submitted tally was disputed.
+		TallyDisputed { election_id: ElectionId, challenger: T::AccountId },
+		/// A dispute was rejected and the challenger's bond paid to the tally authority.
+		DisputeRejected { election_id: ElectionId, challenger: T::AccountId, bond: BalanceOf<T> },
+		/// A dispute was upheld: the tally authority's bond was paid to the challenger and the
+		/// election moved back to `Tallying`.
+		DisputeUpheld { election_id: ElectionId, challenger: T::AccountId, slashed: BalanceOf<T> },
+		/// A tally committee was set for an election and its DKG started.
+		TallyCommitteeSet { election_id: ElectionId, threshold: u32 },
+		/// A trustee dealt its DKG shares.
+		DkgDealt { election_id: ElectionId, dealer: T::AccountId },
+		/// A trustee complained about the share a dealer sealed to it.
+		DkgComplained { election_id: ElectionId, dealer: T::AccountId, complainer: T::AccountId },
+		/// A dealer revealed a valid share in answer to a complaint.
+		DkgComplaintAnswered {
+			election_id: ElectionId,
+			dealer: T::AccountId,
+			complainer: T::AccountId,
+		},
+		/// The DKG completed and the combined tally public key was published.
+		DkgCompleted { election_id: ElectionId, public_key: GroupElement },
+		/// Fewer than `threshold` dealers qualified; the DKG starts over.
+		DkgFailed { election_id: ElectionId, qualified: u32 },
+		/// A trustee attested to a tally result by publishing its partial decryption.
+		TallyAttested { election_id: ElectionId, trustee: T::AccountId, result_hash: RootHash<T> },
+		/// A trustee published a checked partial decryption of the encrypted tally.
+		PartialDecryptionSubmitted { election_id: ElectionId, trustee: T::AccountId },
+		/// The option counts of a homomorphic election were checked against the encrypted
+		/// tally.
+		CountsVerified { election_id: ElectionId, counts: OptionCounts },
+		/// The admin committed to the voters of an anonymous election.
+		VoterAllowlistSet { election_id: ElectionId, root: membership::FieldElement },
+		/// An anonymous ballot was cast, replacing the ballot at `replaced` if any.
+		/// `ciphertext_hash` is its leaf in the input root.
+		AnonymousBallotCast {
+			election_id: ElectionId,
+			nullifier: Nullifier,
+			index: BallotIndex,
+			ciphertext_hash: MerkleHash,
+			replaced: Option<BallotIndex>,
+		},
+		/// The funding pot of an election received `amount` for feeless ballots.
+		ElectionFunded { election_id: ElectionId, funder: T::AccountId, amount: BalanceOf<T> },
+		/// What was left in the funding pot of a closed election went back to its admin.
+		ElectionFundsReclaimed { election_id: ElectionId, amount: BalanceOf<T> },
+		/// A ballot the funding pot paid for was rejected with `error`. The pot's charge and
+		/// `sender`'s feeless ballot stand.
+		FeelessBallotRejected {
+			election_id: ElectionId,
+			sender: BallotSender<T::AccountId>,
+			error: DispatchError,
+		},
+		/// An election moved to a new phase.
+		PhaseChanged { election_id: ElectionId, from: ElectionPhase, to: ElectionPhase },
+		/// An election was cancelled by its admin.
+		ElectionCancelled { election_id: ElectionId },
+		/// Mix cascade and tally public keys were published for an election.
+		ElectionKeysSet { election_id: ElectionId },
+		/// The input root of an election was computed from its valid ballots.
+		InputRootComputed { election_id: ElectionId, root: MerkleHash },
+		/// A mix hop of an election committed to its shuffle.
+		ShuffleHopCommitted { election_id: ElectionId, hop: HopIndex },
+		/// Shuffle challenges of an election will be drawn from the hash of `seed_block`.
+		ShuffleSeedBlockSet { election_id: ElectionId, seed_block: BlockNumberFor<T> },
+		/// Shuffle challenges were drawn for an election; see `ShuffleChallenges`.
+		ShuffleChallengesDrawn { election_id: ElectionId, challenges: u32 },
+		/// Links of a mix hop were opened and checked.
+		ShuffleLinksProven { election_id: ElectionId, hop: HopIndex, count: u32 },
+		/// Every shuffle challenge of an election was answered.
+		ShuffleProven { election_id: ElectionId },
 	}
 
 	/// Errors inform users that something went wrong.
@@ -131,8 +831,105 @@ pub mod pallet {
 		AlreadyRegistered,
 		/// Voter is not registered.
 		NotRegistered,
-		/// Voting period has closed.
-		VotingClosed,
+		/// Deadlines must be in the future and registration must end before voting.
+		InvalidDeadlines,
+		/// Election is not in the registration phase.
+		NotInRegistrationPhase,
+		/// Election is not in the voting phase.
+		NotInVotingPhase,
+		/// Election is not in the mixing phase.
+		NotInMixingPhase,
+		/// Election is not in the tallying phase.
+		NotInTallyingPhase,
+		/// Election has no tally open to disputes.
+		NotDisputable,
+		/// The challenge window of the tally has closed.
+		ChallengePeriodOver,
+		/// The evidence does not refer to a valid ballot of the election.
+		InvalidEvidence,
+		/// The tally already has the maximum number of open disputes.
+		TooManyDisputes,
+		/// No open dispute at this index.
+		DisputeNotFound,
+		/// The threshold must be between 1 and the number of distinct trustees.
+		InvalidCommittee,
+		/// Too many trustees for a single committee.
+		TooManyTrustees,
+		/// The election has no tally committee.
+		NoCommittee,
+		/// The tally committee key is already established.
+		CommitteeKeyEstablished,
+		/// The tally committee key is not established yet.
+		CommitteeKeyPending,
+		/// Not authorized: caller is not a trustee of the tally committee.
+		NotTrustee,
+		/// The DKG round for this action is not open.
+		DkgRoundClosed,
+		/// The DKG answer round has not ended yet.
+		DkgInProgress,
+		/// The trustee has already dealt.
+		AlreadyDealt,
+		/// A deal needs `threshold` valid commitments and one sealed share per trustee.
+		InvalidDeal,
+		/// The dealer has not dealt.
+		NoDeal,
+		/// The trustee has already complained about this dealer.
+		AlreadyComplained,
+		/// No open complaint from this trustee against the dealer.
+		ComplaintNotFound,
+		/// The revealed share does not match the dealer's commitments.
+		InvalidShare,
+		/// Fewer than `threshold` trustees attested to the tally result.
+		NotEnoughAttestations,
+		/// The tally key of the election is not the key of its tally committee.
+		TallyKeyNotCommitteeKey,
+		/// A homomorphic election needs between 2 and `elgamal::MAX_OPTIONS` options.
+		InvalidTallyMode,
+		/// The election does not use the homomorphic tally mode.
+		NotHomomorphic,
+		/// The ciphertext is not an ElGamal ciphertext per option of the election.
+		InvalidBallot,
+		/// The ballot does not prove that it chooses exactly one option.
+		InvalidBallotProof,
+		/// The homomorphic election has no ballots to decrypt.
+		NoBallots,
+		/// A partial decryption does not match the encrypted tally or its proof is invalid.
+		InvalidPartialDecryption,
+		/// Fewer than `threshold` trustees published partial decryptions.
+		NotEnoughPartialDecryptions,
+		/// The counts do not match the decrypted tally.
+		InvalidCounts,
+		/// The counts of the homomorphic election have not been checked yet.
+		CountsNotVerified,
+		/// The result hash is not the hash of the checked counts.
+		ResultHashMismatch,
+		/// The election takes anonymous ballots only.
+		AnonymousElection,
+		/// The election has no voter allowlist.
+		NoAllowlist,
+		/// The runtime pins no membership verifying key, so elections cannot be anonymous.
+		AnonymousVotingDisabled,
+		/// Membership proof exceeds maximum length.
+		ProofTooLong,
+		/// The membership proof is invalid.
+		InvalidMembershipProof,
+		/// The membership proof is for another allowlist root than the election's.
+		AllowlistRootMismatch,
+		/// The election is neither finalized nor cancelled.
+		ElectionNotClosed,
+		/// The funding pot of the election is empty.
+		NothingToReclaim,
+		/// Election is already finalized or cancelled.
+		ElectionClosed,
+		/// Too many phase transitions are already scheduled for a deadline block and the
+		/// blocks after it.
+		TooManyScheduledTransitions,
+		/// At least one mix node key is required.
+		NoMixKeys,
+		/// Too many mix node keys for a single cascade.
+		TooManyMixKeys,
+		/// The number of ML-KEM keys does not match the number of mix node keys.
+		MixKeyCountMismatch,
 		/// Not authorized: caller is not the election admin.
 		NotAdmin,
 		/// Not authorized: caller is not the tally authority.
@@ -141,10 +938,50 @@ pub mod pallet {
 		ElectionAlreadyExists,
 		/// Ciphertext exceeds maximum allowed length.
 		CiphertextTooLong,
+		/// Voter has already cast a ballot and the election does not allow revoting.
+		AlreadyVoted,
+		/// The input root of the election is still being computed.
+		InputRootPending,
+		/// The input root does not match the one computed from the stored ballots.
+		InputRootMismatch,
+		/// The election has no published mix cascade to prove.
+		NoMixCascade,
+		/// The hop is not the next one of the cascade to commit.
+		UnexpectedHop,
+		/// The hop input is not the previous hop output, or the input root for the first hop.
+		HopInputMismatch,
+		/// The last hop output is not `commitment_output_root`.
+		HopOutputMismatch,
+		/// The shuffle is not waiting for its challenges to be drawn.
+		NotChallenging,
+		/// The block seeding the shuffle challenges is not sealed yet.
+		ChallengeSeedPending,
+		/// The shuffle is not waiting for challenge answers.
+		NotProving,
+		/// A link opening does not match the hop commitments.
+		InvalidLinkOpening,
+		/// A link opening answers no open challenge of the hop.
+		UnchallengedLink,
+		/// Not every mix hop of the election has proven its shuffle.
+		ShuffleNotProven,
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let due = PhaseSchedule::<T>::take(now);
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+			// Bounded by `MaxPhaseTransitionsPerBlock`. Each check reads and writes the election
+			// and, if it closes, the admin's deposit.
+			for election_id in due {
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 2));
+				Self::advance_phase(election_id, now);
+			}
+
+			weight.saturating_add(Self::build_input_roots(T::MaxBallotsHashedPerBlock::get()))
+		}
+	}
 
 	/// Dispatchable functions allows users to interact with the pallet and invoke state changes.
 	/// These functions materialize as "extrinsics", which are often compared to transactions.
@@ -152,44 +989,67 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		/// Create a new election.
 		///
-		/// Only the admin account that signs this transaction can create an election.
-		/// The election ID must be unique.
+		/// Only the admin account that signs this transaction can create an election, holding
+		/// `ElectionDeposit` until it is finalized or cancelled.
+		/// The election ID must be unique. `revote_policy` decides whether a voter may
+		/// replace their ballot while voting is open, and `tally_mode` how ballots are counted;
+		/// homomorphic elections also need a tally committee (see `set_tally_committee`) whose
+		/// key is established by the end of registration, or they are cancelled then.
 		#[pallet::call_index(0)]
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::create_election())]
 		pub fn create_election(
 			origin: OriginFor<T>,
 			election_id: ElectionId,
 			tally_authority: T::AccountId,
 			registration_deadline: BlockNumberFor<T>,
 			voting_deadline: BlockNumberFor<T>,
+			revote_policy: RevotePolicy,
+			tally_mode: TallyMode,
 		) -> DispatchResult {
 			let admin = ensure_signed(origin)?;
 
 			// Ensure election doesn't already exist
 			ensure!(!Elections::<T>::contains_key(election_id), Error::<T>::ElectionAlreadyExists);
 
+			if let TallyMode::Homomorphic { options } = tally_mode {
+				ensure!(
+					(2..=elgamal::MAX_OPTIONS).contains(&u32::from(options)),
+					Error::<T>::InvalidTallyMode
+				);
+			}
+
 			// Ensure deadlines are valid
-			ensure!(
-				registration_deadline < voting_deadline,
-				Error::<T>::VotingClosed
-			);
+			ensure!(registration_deadline < voting_deadline, Error::<T>::InvalidDeadlines);
 
 			let now = <frame_system::Pallet<T>>::block_number();
-			ensure!(registration_deadline > now, Error::<T>::VotingClosed);
+			ensure!(registration_deadline > now, Error::<T>::InvalidDeadlines);
+
+			// Schedule the automatic transitions right after each deadline
+			Self::schedule_transition(election_id, registration_deadline.saturating_add(One::one()))?;
+			Self::schedule_transition(election_id, voting_deadline.saturating_add(One::one()))?;
+
+			let deposit = T::ElectionDeposit::get();
+			T::Currency::hold(&HoldReason::ElectionDeposit.into(), &admin, deposit)?;
 
 			// Create election
 			let election = Election {
 				admin,
+				deposit,
 				tally_authority,
 				registration_deadline,
 				voting_deadline,
+				revote_policy,
+				tally_mode,
+				keys: None,
+				committee: None,
 				commitment_input_root: None,
 				commitment_output_root: None,
-				finalized: false,
+				phase: ElectionPhase::Registration,
 			};
 
 			Elections::<T>::insert(election_id, &election);
 			BallotCount::<T>::insert(election_id, 0);
+			ValidBallotCount::<T>::insert(election_id, 0);
 
 			Self::deposit_event(Event::ElectionCreated { election_id });
 
@@ -199,9 +1059,9 @@ pub mod pallet {
 		/// Register a voter for an election.
 		///
 		/// Only the election admin can register voters.
-		/// Registration must occur before the registration deadline.
+		/// The election must be in the `Registration` phase.
 		#[pallet::call_index(1)]
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::register_voter())]
 		pub fn register_voter(
 			origin: OriginFor<T>,
 			election_id: ElectionId,
@@ -214,12 +1074,17 @@ pub mod pallet {
 			// Only admin can register voters
 			ensure!(who == election.admin, Error::<T>::NotAdmin);
 
-			// Ensure election is not finalized
-			ensure!(!election.finalized, Error::<T>::VotingClosed);
+			// Ensure registration is still open
+			ensure!(
+				election.phase == ElectionPhase::Registration,
+				Error::<T>::NotInRegistrationPhase
+			);
 
-			// Ensure we're still before or at registration deadline
-			let now = <frame_system::Pallet<T>>::block_number();
-			ensure!(now <= election.registration_deadline, Error::<T>::VotingClosed);
+			// Anonymous voters are committed to in the allowlist instead
+			ensure!(
+				!VoterAllowlists::<T>::contains_key(election_id),
+				Error::<T>::AnonymousElection
+			);
 
 			// Ensure voter is not already registered
 			ensure!(
@@ -236,24 +1101,37 @@ pub mod pallet {
 
 		/// Cast an encrypted ballot.
 		///
-		/// The voter must be registered for this election.
-		/// The ballot must be cast before the voting deadline.
+		/// The voter must be registered for this election, which must not be anonymous (see
+		/// `cast_anonymous_vote`).
+		/// The election must be in the `Voting` phase.
+		/// In homomorphic elections, `ciphertext` is a SCALE-encoded `elgamal::ProvenBallot`
+		/// under the committee key, proven for `ballot_context` of the voter. Its validity proof
+		/// is checked before it is stored and added to `EncryptedTallies`.
+		/// A second ballot from the same voter is rejected or replaces the first one,
+		/// depending on the election's `RevotePolicy`.
+		/// While the election's funding pot can pay and the voter has feeless ballots left
+		/// (see `MaxFeelessBallots`), the pot pays `FeelessBallotFee` and the voter no fee. A
+		/// ballot the pot paid for is not refunded if it turns out invalid: the call succeeds
+		/// with `FeelessBallotRejected` instead.
 		#[pallet::call_index(2)]
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::cast_vote(elgamal::MAX_OPTIONS))]
+		#[pallet::feeless_if(|origin: &OriginFor<T>, election_id: &ElectionId, ciphertext: &sp_std::vec::Vec<u8>| -> bool {
+			ciphertext.len() <= Ciphertext::bound() && Pallet::<T>::sponsors_vote(origin, *election_id)
+		})]
 		pub fn cast_vote(
 			origin: OriginFor<T>,
 			election_id: ElectionId,
 			ciphertext: sp_std::vec::Vec<u8>,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let voter = ensure_signed(origin)?;
 
 			let election = Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+			Self::ensure_voting(&election)?;
 
-			// Ensure election is not finalized and we are in the voting phase
-			ensure!(!election.finalized, Error::<T>::VotingClosed);
-
-			let now = <frame_system::Pallet<T>>::block_number();
-			ensure!(now <= election.voting_deadline, Error::<T>::VotingClosed);
+			ensure!(
+				!VoterAllowlists::<T>::contains_key(election_id),
+				Error::<T>::AnonymousElection
+			);
 
 			// Ensure the signer is registered
 			ensure!(
@@ -261,31 +1139,64 @@ pub mod pallet {
 				Error::<T>::NotRegistered
 			);
 
+			// Enforce one valid ballot per voter
+			let previous = VoterBallot::<T>::get(election_id, &voter);
+			if previous.is_some() {
+				ensure!(
+					election.revote_policy == RevotePolicy::LastVoteWins,
+					Error::<T>::AlreadyVoted
+				);
+			}
+
 			// Convert Vec<u8> to bounded Ciphertext
 			let bounded: Ciphertext = ciphertext
 				.try_into()
 				.map_err(|_| Error::<T>::CiphertextTooLong)?;
 
-			// Get current ballot index
-			let index = BallotCount::<T>::get(election_id);
+			// Same condition as `feeless_if`, which waived the voter's fee
+			let sender = BallotSender::Voter(voter.clone());
+			let feeless = Self::pot_can_pay(election_id, &sender);
+			if feeless {
+				Self::charge_pot(election_id, &sender)?;
+			}
 
-			// Store ballot
-			Ballots::<T>::insert(election_id, index, &bounded);
+			let ciphertext_hash = merkle::hash_leaf(&bounded);
+			let Some((index, options)) = Self::store_ballot_paid(
+				election_id,
+				&election,
+				&sender,
+				&bounded,
+				previous,
+				feeless,
+			)?
+			else {
+				return Ok(().into())
+			};
+			VoterBallot::<T>::insert(election_id, &voter, index);
 
-			// Increment ballot count
-			BallotCount::<T>::insert(election_id, index.saturating_add(1));
+			if let Some(old_index) = previous {
+				Self::deposit_event(Event::BallotReplaced {
+					election_id,
+					voter: voter.clone(),
+					old_index,
+					new_index: index,
+				});
+			}
 
-			Self::deposit_event(Event::BallotCast { election_id, voter, index });
+			Self::deposit_event(Event::BallotCast { election_id, voter, index, ciphertext_hash });
 
-			Ok(())
+			Ok(Some(T::WeightInfo::cast_vote(options.into())).into())
 		}
 
 		/// Set mix commitments for an election.
 		///
 		/// Only the tally authority can set mix commitments.
-		/// This should be called after the mixing phase completes.
+		/// The election must be in the `Mixing` phase, i.e. past its voting deadline.
+		/// Moves the election to `Tallying`.
+		/// `commitment_input_root` must equal the root computed on-chain over the ballots not
+		/// marked in `ReplacedBallots` (see `InputRoots`).
 		#[pallet::call_index(3)]
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::set_mix_commitments())]
 		pub fn set_mix_commitments(
 			origin: OriginFor<T>,
 			election_id: ElectionId,
@@ -300,26 +1211,30 @@ pub mod pallet {
 			// Only tally authority can set commitments
 			ensure!(who == election.tally_authority, Error::<T>::NotTallyAuthority);
 
-			// Ensure election is not finalized
-			ensure!(!election.finalized, Error::<T>::VotingClosed);
-
-			// Update commitments
-			election.commitment_input_root = Some(commitment_input_root);
-			election.commitment_output_root = Some(commitment_output_root);
+			Self::do_set_mix_commitments(
+				election_id,
+				&mut election,
+				commitment_input_root,
+				commitment_output_root,
+			)?;
 
 			Elections::<T>::insert(election_id, &election);
 
-			Self::deposit_event(Event::MixCommitmentsSet { election_id });
-
 			Ok(())
 		}
 
 		/// Submit final tally results for an election.
 		///
 		/// Only the tally authority can submit tally results.
-		/// This finalizes the election and prevents further modifications.
+		/// The election must be in the `Tallying` phase and, if it has a published mix cascade,
+		/// its shuffle must be proven (see `commit_shuffle_hop`). With a tally committee,
+		/// `threshold` trustees must have attested to `result_hash` (see `attest_tally`).
+		/// A homomorphic election's `result_hash` must be the hash of its verified counts (see
+		/// `submit_decrypted_counts`).
+		/// Holds `TallyBond` from the tally authority and moves the election to `Disputable`;
+		/// it is finalized once `ChallengePeriod` blocks pass without an open dispute.
 		#[pallet::call_index(4)]
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::submit_tally(T::MaxTrustees::get()))]
 		pub fn submit_tally(
 			origin: OriginFor<T>,
 			election_id: ElectionId,
@@ -334,36 +1249,1742 @@ pub mod pallet {
 			// Only tally authority can submit tally
 			ensure!(who == election.tally_authority, Error::<T>::NotTallyAuthority);
 
-			// Ensure election is not finalized
-			ensure!(!election.finalized, Error::<T>::VotingClosed);
+			// Tallying is only reached once the mix commitments are set
+			ensure!(election.phase == ElectionPhase::Tallying, Error::<T>::NotInTallyingPhase);
 
-			// Ensure commitments have been set
-			ensure!(
-				election.commitment_input_root.is_some() &&
-					election.commitment_output_root.is_some(),
-				Error::<T>::VotingClosed
-			);
+			match election.tally_mode {
+				TallyMode::Mixnet => {
+					// With a published cascade, every hop must have answered its shuffle
+					// challenges
+					if election.keys.is_some() {
+						ensure!(
+							ShuffleStages::<T>::get(election_id) == Some(ShuffleStage::Proven),
+							Error::<T>::ShuffleNotProven
+						);
+					}
+
+					// A committee tally needs `threshold` trustees to have decrypted this result
+					if let Some(committee) = &election.committee {
+						let attestations = committee
+							.trustees
+							.iter()
+							.filter(|(trustee, _)| {
+								TallyAttestations::<T>::get(election_id, trustee) ==
+									Some(result_hash)
+							})
+							.count();
+						ensure!(
+							attestations >= committee.threshold as usize,
+							Error::<T>::NotEnoughAttestations
+						);
+					}
+				},
+				TallyMode::Homomorphic { .. } => {
+					// The result must be the counts checked by `submit_decrypted_counts`
+					let counts = DecryptedCounts::<T>::get(election_id)
+						.ok_or(Error::<T>::CountsNotVerified)?;
+					ensure!(
+						T::Hashing::hash_of(&counts) == result_hash,
+						Error::<T>::ResultHashMismatch
+					);
+				},
+			}
 
 			// Convert result_uri to bounded vec
 			let bounded_uri: BoundedVec<u8, ConstU32<256>> = result_uri
 				.try_into()
 				.map_err(|_| Error::<T>::CiphertextTooLong)?;
 
+			// Open the challenge window, closed right after its last block
+			let now = <frame_system::Pallet<T>>::block_number();
+			let challenge_deadline = now.saturating_add(T::ChallengePeriod::get());
+			Self::schedule_transition(election_id, challenge_deadline.saturating_add(One::one()))?;
+
+			let bond = T::TallyBond::get();
+			T::Currency::hold(&HoldReason::TallyBond.into(), &who, bond)?;
+
 			// Store tally result
 			let tally_result = TallyResult {
 				result_uri: bounded_uri,
 				result_hash,
+				challenge_deadline,
+				bond,
 			};
 			TallyResults::<T>::insert(election_id, &tally_result);
 
-			// Finalize election
-			election.finalized = true;
-			Elections::<T>::insert(election_id, &election);
-
 			Self::deposit_event(Event::TallySubmitted { election_id });
 
+			Self::set_phase(election_id, &mut election, ElectionPhase::Disputable);
+			Elections::<T>::insert(election_id, &election);
+
 			Ok(())
 		}
+
+		/// Cancel an election.
+		///
+		/// Only the election admin can cancel, and only while the election is still open.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::cancel_election(T::MaxDisputes::get()))]
+		pub fn cancel_election(origin: OriginFor<T>, election_id: ElectionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut election =
+				Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+
+			ensure!(who == election.admin, Error::<T>::NotAdmin);
+			ensure!(!election.phase.is_closed(), Error::<T>::ElectionClosed);
+
+			if election.phase == ElectionPhase::Disputable {
+				Self::release_tally_bonds(election_id, &election);
+			}
+			Self::release_election_deposit(&election);
+
+			Self::set_phase(election_id, &mut election, ElectionPhase::Cancelled);
+			Elections::<T>::insert(election_id, &election);
+			InputRootProgress::<T>::remove(election_id);
+
+			Self::deposit_event(Event::ElectionCancelled { election_id });
+
+			Ok(())
+		}
+
+		/// Publish the onion keys for an election.
+		///
+		/// Only the election admin can set keys, and only during the `Registration` phase, so
+		/// the keys are fixed by the time voting starts. `mix_keys` are the X25519 keys of the
+		/// mix cascade in peeling order; `pq_mix_keys`, if given, must list one ML-KEM-768 key
+		/// per mix node in the same order. Once a tally committee key is established,
+		/// `tally_key` must be that key.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::set_election_keys(mix_keys.len() as u32))]
+		pub fn set_election_keys(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			mix_keys: sp_std::vec::Vec<X25519PublicKey>,
+			pq_mix_keys: Option<sp_std::vec::Vec<MlKemPublicKey>>,
+			tally_key: X25519PublicKey,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut election =
+				Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+
+			ensure!(who == election.admin, Error::<T>::NotAdmin);
+
+			// Keys are locked once voting starts
+			ensure!(
+				election.phase == ElectionPhase::Registration,
+				Error::<T>::NotInRegistrationPhase
+			);
+
+			ensure!(!mix_keys.is_empty(), Error::<T>::NoMixKeys);
+			if let Some(public_key) = election.committee.as_ref().and_then(|c| c.public_key) {
+				ensure!(tally_key == public_key, Error::<T>::TallyKeyNotCommitteeKey);
+			}
+			if let Some(pq_keys) = &pq_mix_keys {
+				ensure!(pq_keys.len() == mix_keys.len(), Error::<T>::MixKeyCountMismatch);
+			}
+
+			let mix_keys: MixCascade<X25519PublicKey> =
+				mix_keys.try_into().map_err(|_| Error::<T>::TooManyMixKeys)?;
+			let pq_mix_keys: Option<MixCascade<MlKemPublicKey>> = pq_mix_keys
+				.map(|keys| keys.try_into())
+				.transpose()
+				.map_err(|_| Error::<T>::TooManyMixKeys)?;
+
+			election.keys = Some(ElectionKeys { mix_keys, pq_mix_keys, tally_key });
+			Elections::<T>::insert(election_id, &election);
+
+			Self::deposit_event(Event::ElectionKeysSet { election_id });
+
+			Ok(())
+		}
+
+		/// Commit to the shuffle of one mix hop.
+		///
+		/// Only the tally authority can commit, on behalf of the mix nodes of the published
+		/// cascade, once the mix commitments are set. Hops are committed in peeling order: the
+		/// first hop takes the input root as input, each later hop the previous hop output, and
+		/// the last hop must output `commitment_output_root`. Committing the last hop fixes the
+		/// block whose hash seeds the challenges.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::commit_shuffle_hop())]
+		pub fn commit_shuffle_hop(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			hop: HopIndex,
+			commitment: HopCommitment,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let election = Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+
+			ensure!(who == election.tally_authority, Error::<T>::NotTallyAuthority);
+			ensure!(election.phase == ElectionPhase::Tallying, Error::<T>::NotInTallyingPhase);
+
+			let hops = election.keys.as_ref().ok_or(Error::<T>::NoMixCascade)?.mix_keys.len();
+
+			// Hops are committed one after the other
+			let committed = match ShuffleStages::<T>::get(election_id) {
+				None => 0,
+				Some(ShuffleStage::Committing { committed }) => committed,
+				Some(_) => return Err(Error::<T>::UnexpectedHop.into()),
+			};
+			ensure!(hop == committed, Error::<T>::UnexpectedHop);
+
+			let expected_input = match hop.checked_sub(1) {
+				None => InputRoots::<T>::get(election_id),
+				Some(previous) =>
+					ShuffleHops::<T>::get(election_id, previous).map(|c| c.output_root),
+			};
+			ensure!(expected_input == Some(commitment.input_root), Error::<T>::HopInputMismatch);
+
+			ShuffleHops::<T>::insert(election_id, hop, commitment);
+			Self::deposit_event(Event::ShuffleHopCommitted { election_id, hop });
+
+			let committed = hop.saturating_add(1);
+			if usize::from(committed) < hops {
+				ShuffleStages::<T>::insert(election_id, ShuffleStage::Committing { committed });
+				return Ok(())
+			}
+
+			// The cascade must end in the committed mix output
+			ensure!(
+				election
+					.commitment_output_root
+					.is_some_and(|root| root.as_ref() == &commitment.output_root[..]),
+				Error::<T>::HopOutputMismatch
+			);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			Self::set_seed_block(election_id, now.saturating_add(One::one()));
+
+			Ok(())
+		}
+
+		/// Draw the shuffle challenges of an election.
+		///
+		/// Anyone can draw once the seed block is sealed. If its hash is no longer kept by
+		/// `frame_system`, a new seed block is set instead.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::draw_shuffle_challenges(
+			<MixCascade<X25519PublicKey>>::bound() as u32,
+		))]
+		pub fn draw_shuffle_challenges(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let election = Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+			ensure!(election.phase == ElectionPhase::Tallying, Error::<T>::NotInTallyingPhase);
+
+			let Some(ShuffleStage::Challenging { seed_block }) =
+				ShuffleStages::<T>::get(election_id)
+			else {
+				return Err(Error::<T>::NotChallenging.into())
+			};
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now > seed_block, Error::<T>::ChallengeSeedPending);
+
+			let seed_hash = <frame_system::Pallet<T>>::block_hash(seed_block);
+			if seed_hash == Default::default() {
+				Self::set_seed_block(election_id, now.saturating_add(One::one()));
+				return Ok(Some(T::WeightInfo::draw_shuffle_challenges(0)).into())
+			}
+
+			let hops = election.keys.map_or(0, |keys| keys.mix_keys.len() as HopIndex);
+			let seed = blake2_256(&(seed_hash, election_id).encode());
+			let drawn = shuffle::draw_challenges(
+				&seed,
+				hops,
+				ValidBallotCount::<T>::get(election_id),
+				T::ShuffleChallengesPerPair::get(),
+			);
+
+			let mut open = 0u32;
+			for (hop, challenges) in drawn.into_iter().enumerate() {
+				open.saturating_accrue(challenges.len() as u32);
+				// At most `ShuffleChallengesPerPair` challenges are drawn for a hop.
+				let challenges = BoundedVec::truncate_from(challenges);
+				ShuffleChallenges::<T>::insert(election_id, hop as HopIndex, challenges);
+			}
+
+			Self::deposit_event(Event::ShuffleChallengesDrawn { election_id, challenges: open });
+			Self::set_open_challenges(election_id, open);
+
+			Ok(Some(T::WeightInfo::draw_shuffle_challenges(hops.into())).into())
+		}
+
+		/// Answer shuffle challenges of a mix hop by opening the challenged links.
+		///
+		/// Anyone can submit openings while the election is `Tallying`, as they are checked
+		/// against the hop commitments and by `Config::LinkVerifier`.
+		/// Each opening must answer an open challenge of the hop.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::prove_shuffle_links(openings.len() as u32))]
+		pub fn prove_shuffle_links(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			hop: HopIndex,
+			openings: BoundedVec<LinkOpening, T::MaxLinksPerProof>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let election = Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+			ensure!(election.phase == ElectionPhase::Tallying, Error::<T>::NotInTallyingPhase);
+
+			let Some(ShuffleStage::Proving { open }) = ShuffleStages::<T>::get(election_id) else {
+				return Err(Error::<T>::NotProving.into())
+			};
+
+			let commitment =
+				ShuffleHops::<T>::get(election_id, hop).ok_or(Error::<T>::UnexpectedHop)?;
+			let mut challenges = ShuffleChallenges::<T>::get(election_id, hop);
+			let size = ValidBallotCount::<T>::get(election_id);
+
+			// Bounded by `MaxLinksPerProof` openings of 3 Merkle paths each.
+			for opening in openings.iter() {
+				ensure!(
+					shuffle::check_opening(&commitment, size, opening) &&
+						T::LinkVerifier::verify(
+							election_id,
+							hop,
+							&opening.input_leaf,
+							&opening.output_leaf,
+							&opening.evidence,
+						),
+					Error::<T>::InvalidLinkOpening
+				);
+
+				let challenge = challenges
+					.iter_mut()
+					.find(|challenge| !challenge.answered && shuffle::answers(challenge, opening))
+					.ok_or(Error::<T>::UnchallengedLink)?;
+				challenge.answered = true;
+			}
+
+			let count = openings.len() as u32;
+			ShuffleChallenges::<T>::insert(election_id, hop, challenges);
+
+			Self::deposit_event(Event::ShuffleLinksProven { election_id, hop, count });
+			Self::set_open_challenges(election_id, open.saturating_sub(count));
+
+			Ok(())
+		}
+
+		/// Dispute the submitted tally of an election.
+		///
+		/// Anyone can dispute until the challenge window closes, holding `DisputeBond` until
+		/// `DisputeOrigin` rejects or upholds the dispute. The election is not finalized while
+		/// a dispute is open.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::dispute_tally())]
+		pub fn dispute_tally(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			evidence: DisputeEvidence,
+		) -> DispatchResult {
+			let challenger = ensure_signed(origin)?;
+
+			let election = Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+			ensure!(election.phase == ElectionPhase::Disputable, Error::<T>::NotDisputable);
+
+			let tally = TallyResults::<T>::get(election_id).ok_or(Error::<T>::NotDisputable)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now <= tally.challenge_deadline, Error::<T>::ChallengePeriodOver);
+
+			// A missing ballot must be one that was mixed
+			if let DisputeEvidence::MissingBallot { index } = evidence {
+				ensure!(
+					index < BallotCount::<T>::get(election_id) &&
+						!ReplacedBallots::<T>::get(election_id, index),
+					Error::<T>::InvalidEvidence
+				);
+			}
+
+			let bond = T::DisputeBond::get();
+			let dispute = Dispute { challenger: challenger.clone(), bond, evidence };
+			Disputes::<T>::try_mutate(election_id, |disputes| {
+				disputes.try_push(dispute).map_err(|_| Error::<T>::TooManyDisputes)
+			})?;
+			T::Currency::hold(&HoldReason::DisputeBond.into(), &challenger, bond)?;
+
+			Self::deposit_event(Event::TallyDisputed { election_id, challenger });
+
+			Ok(())
+		}
+
+		/// Reject an open dispute, confirming the tally against it.
+		///
+		/// Only `DisputeOrigin` can call this. The challenger's bond is paid to the tally
+		/// authority, and the election is finalized if this was the last open dispute and the
+		/// challenge window has closed. `index` is the position of the dispute in `Disputes`.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::reject_dispute())]
+		pub fn reject_dispute(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			index: u32,
+		) -> DispatchResult {
+			T::DisputeOrigin::ensure_origin(origin)?;
+
+			let mut election =
+				Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+			ensure!(election.phase == ElectionPhase::Disputable, Error::<T>::NotDisputable);
+
+			let dispute = Self::take_dispute(election_id, index)?;
+			let bond = T::Currency::transfer_on_hold(
+				&HoldReason::DisputeBond.into(),
+				&dispute.challenger,
+				&election.tally_authority,
+				dispute.bond,
+				Precision::BestEffort,
+				tokens::Restriction::Free,
+				Fortitude::Polite,
+			)?;
+
+			Self::deposit_event(Event::DisputeRejected {
+				election_id,
+				challenger: dispute.challenger,
+				bond,
+			});
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			if Self::can_finalize(election_id, now) {
+				Self::finalize(election_id, &mut election);
+				Elections::<T>::insert(election_id, &election);
+			}
+
+			Ok(())
+		}
+
+		/// Uphold an open dispute, rejecting the tally.
+		///
+		/// Only `DisputeOrigin` can call this. The tally authority's bond is paid to the
+		/// challenger, every dispute bond is released, and the tally is discarded along with the
+		/// trustee attestations, verified counts and shuffle proof it was accepted on. A missing
+		/// ballot or a wrong root in a mixnet election also discards the mix commitments and
+		/// moves the election back to `Mixing`; otherwise it moves back to `Tallying`. `index` is
+		/// the position of the dispute in `Disputes`.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::uphold_dispute(T::MaxDisputes::get()))]
+		pub fn uphold_dispute(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			index: u32,
+		) -> DispatchResult {
+			T::DisputeOrigin::ensure_origin(origin)?;
+
+			let mut election =
+				Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+			ensure!(election.phase == ElectionPhase::Disputable, Error::<T>::NotDisputable);
+
+			let dispute = Self::take_dispute(election_id, index)?;
+			let remix = election.tally_mode == TallyMode::Mixnet &&
+				matches!(
+					dispute.evidence,
+					DisputeEvidence::MissingBallot { .. } | DisputeEvidence::RootMismatch { .. }
+				);
+			let tally = TallyResults::<T>::take(election_id).ok_or(Error::<T>::NotDisputable)?;
+			let slashed = T::Currency::transfer_on_hold(
+				&HoldReason::TallyBond.into(),
+				&election.tally_authority,
+				&dispute.challenger,
+				tally.bond,
+				Precision::BestEffort,
+				tokens::Restriction::Free,
+				Fortitude::Polite,
+			)?;
+			T::Currency::release(
+				&HoldReason::DisputeBond.into(),
+				&dispute.challenger,
+				dispute.bond,
+				Precision::BestEffort,
+			)?;
+			Self::release_dispute_bonds(election_id);
+
+			Self::deposit_event(Event::DisputeUpheld {
+				election_id,
+				challenger: dispute.challenger,
+				slashed,
+			});
+
+			Self::clear_tally(election_id);
+			if remix {
+				// The mix itself was wrong, so it is redone from new commitments
+				election.commitment_input_root = None;
+				election.commitment_output_root = None;
+				Self::set_phase(election_id, &mut election, ElectionPhase::Mixing);
+			} else {
+				Self::set_phase(election_id, &mut election, ElectionPhase::Tallying);
+			}
+			Elections::<T>::insert(election_id, &election);
+
+			Ok(())
+		}
+
+		/// Hand the tally of an election to a committee of trustees and start its DKG.
+		///
+		/// Only the election admin can set the committee, during the `Registration` phase and
+		/// until the DKG completes; setting it again restarts the DKG. `trustees` pairs each
+		/// trustee account with the X25519 key its shares are sealed to, and `threshold`
+		/// trustees are then needed to decrypt the tally.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::set_tally_committee(trustees.len() as u32))]
+		pub fn set_tally_committee(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			trustees: sp_std::vec::Vec<(T::AccountId, X25519PublicKey)>,
+			threshold: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut election =
+				Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+
+			ensure!(who == election.admin, Error::<T>::NotAdmin);
+			ensure!(
+				election.phase == ElectionPhase::Registration,
+				Error::<T>::NotInRegistrationPhase
+			);
+			ensure!(
+				election.committee.as_ref().map_or(true, |c| c.public_key.is_none()),
+				Error::<T>::CommitteeKeyEstablished
+			);
+
+			// Bound the trustees before the quadratic check for duplicates
+			let trustees: BoundedVec<_, T::MaxTrustees> =
+				trustees.try_into().map_err(|_| Error::<T>::TooManyTrustees)?;
+
+			let distinct = trustees
+				.iter()
+				.enumerate()
+				.all(|(i, (trustee, _))| trustees[..i].iter().all(|(other, _)| other != trustee));
+			ensure!(
+				distinct && threshold > 0 && threshold as usize <= trustees.len(),
+				Error::<T>::InvalidCommittee
+			);
+
+			Self::clear_dkg(election_id);
+			election.committee = Some(TallyCommittee {
+				trustees,
+				threshold,
+				dkg_start: <frame_system::Pallet<T>>::block_number(),
+				commitments: BoundedVec::new(),
+				public_key: None,
+			});
+			Elections::<T>::insert(election_id, &election);
+
+			Self::deposit_event(Event::TallyCommitteeSet { election_id, threshold });
+
+			Ok(())
+		}
+
+		/// Deal the DKG shares of a trustee.
+		///
+		/// Each trustee deals once during the dealing round: `threshold` commitments to its
+		/// polynomial, lowest degree first, and one sealed share per trustee in committee order.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::dkg_deal(
+			encrypted_shares.len() as u32,
+			commitments.len() as u32,
+		))]
+		pub fn dkg_deal(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			commitments: sp_std::vec::Vec<GroupElement>,
+			encrypted_shares: sp_std::vec::Vec<sp_std::vec::Vec<u8>>,
+		) -> DispatchResult {
+			let dealer = ensure_signed(origin)?;
+
+			let committee = Self::pending_committee(election_id)?;
+			Self::trustee_index(&committee, &dealer).ok_or(Error::<T>::NotTrustee)?;
+
+			let (dealing_end, _, _) = Self::dkg_deadlines(committee.dkg_start);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now <= dealing_end, Error::<T>::DkgRoundClosed);
+			ensure!(!DkgDeals::<T>::contains_key(election_id, &dealer), Error::<T>::AlreadyDealt);
+
+			ensure!(
+				commitments.len() == committee.threshold as usize &&
+					commitments.iter().all(dkg::is_valid_element) &&
+					encrypted_shares.len() == committee.trustees.len(),
+				Error::<T>::InvalidDeal
+			);
+
+			let encrypted_shares = encrypted_shares
+				.into_iter()
+				.map(EncryptedShare::try_from)
+				.collect::<Result<sp_std::vec::Vec<_>, _>>()
+				.map_err(|_| Error::<T>::InvalidDeal)?;
+			let deal = DkgDeal::<T> {
+				commitments: commitments.try_into().map_err(|_| Error::<T>::InvalidDeal)?,
+				encrypted_shares: encrypted_shares
+					.try_into()
+					.map_err(|_| Error::<T>::InvalidDeal)?,
+			};
+			DkgDeals::<T>::insert(election_id, &dealer, deal);
+
+			Self::deposit_event(Event::DkgDealt { election_id, dealer });
+
+			Ok(())
+		}
+
+		/// Complain that the share a dealer sealed to the caller does not match its
+		/// commitments.
+		///
+		/// Trustees complain during the complaint round, after dealing closes. The dealer is
+		/// disqualified unless it answers with `dkg_answer_complaint`.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::dkg_complain(T::MaxTrustees::get()))]
+		pub fn dkg_complain(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			dealer: T::AccountId,
+		) -> DispatchResult {
+			let complainer = ensure_signed(origin)?;
+
+			let committee = Self::pending_committee(election_id)?;
+			Self::trustee_index(&committee, &complainer).ok_or(Error::<T>::NotTrustee)?;
+
+			let (dealing_end, complaint_end, _) = Self::dkg_deadlines(committee.dkg_start);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now > dealing_end && now <= complaint_end, Error::<T>::DkgRoundClosed);
+
+			ensure!(DkgDeals::<T>::contains_key(election_id, &dealer), Error::<T>::NoDeal);
+			let key = (dealer.clone(), complainer.clone());
+			ensure!(
+				!DkgComplaints::<T>::contains_key(election_id, &key),
+				Error::<T>::AlreadyComplained
+			);
+			DkgComplaints::<T>::insert(election_id, key, false);
+
+			Self::deposit_event(Event::DkgComplained { election_id, dealer, complainer });
+
+			Ok(())
+		}
+
+		/// Answer a complaint by revealing the share the dealer owes the complainer.
+		///
+		/// The dealer answers until the answer round closes. The share must match the
+		/// dealer's commitments at the complainer's share index.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::dkg_answer_complaint(
+			T::MaxTrustees::get(),
+			T::MaxTrustees::get(),
+		))]
+		pub fn dkg_answer_complaint(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			complainer: T::AccountId,
+			share: ShareScalar,
+		) -> DispatchResult {
+			let dealer = ensure_signed(origin)?;
+
+			let committee = Self::pending_committee(election_id)?;
+			let index =
+				Self::trustee_index(&committee, &complainer).ok_or(Error::<T>::NotTrustee)?;
+
+			let (_, _, answer_end) = Self::dkg_deadlines(committee.dkg_start);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now <= answer_end, Error::<T>::DkgRoundClosed);
+
+			let key = (dealer.clone(), complainer.clone());
+			ensure!(
+				DkgComplaints::<T>::get(election_id, &key) == Some(false),
+				Error::<T>::ComplaintNotFound
+			);
+
+			let deal = DkgDeals::<T>::get(election_id, &dealer).ok_or(Error::<T>::NoDeal)?;
+			ensure!(dkg::verify_share(&deal.commitments, index, &share), Error::<T>::InvalidShare);
+			DkgComplaints::<T>::insert(election_id, key, true);
+
+			Self::deposit_event(Event::DkgComplaintAnswered { election_id, dealer, complainer });
+
+			Ok(())
+		}
+
+		/// Close the DKG of a tally committee once its answer round has ended.
+		///
+		/// Anyone can call this. Dealers with an unanswered complaint are disqualified; if at
+		/// least `threshold` dealers qualify, the sum of their constant commitments becomes the
+		/// committee key, otherwise the DKG starts over. The committee key also replaces the
+		/// published tally key: no ballot is taken before the committee key is established, so
+		/// none was sealed to the old one.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::finish_dkg(
+			T::MaxTrustees::get(),
+			T::MaxTrustees::get(),
+			T::MaxTrustees::get().saturating_mul(T::MaxTrustees::get()),
+		))]
+		pub fn finish_dkg(origin: OriginFor<T>, election_id: ElectionId) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let mut election =
+				Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+			let Some(committee) = election.committee.as_mut() else {
+				return Err(Error::<T>::NoCommittee.into())
+			};
+			ensure!(committee.public_key.is_none(), Error::<T>::CommitteeKeyEstablished);
+
+			let (_, _, answer_end) = Self::dkg_deadlines(committee.dkg_start);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now > answer_end, Error::<T>::DkgInProgress);
+
+			// Bounded by `MaxTrustees` squared complaints and `MaxTrustees` deals.
+			let disqualified = DkgComplaints::<T>::iter_prefix(election_id)
+				.filter(|(_, answered)| !answered)
+				.map(|((dealer, _), _)| dealer)
+				.collect::<sp_std::vec::Vec<_>>();
+			let polynomials = DkgDeals::<T>::iter_prefix(election_id)
+				.filter(|(dealer, _)| !disqualified.contains(dealer))
+				.map(|(_, deal)| deal.commitments)
+				.collect::<sp_std::vec::Vec<_>>();
+
+			let qualified = polynomials.len() as u32;
+			let commitments = dkg::combine_commitments(
+				polynomials.iter().map(|commitments| &commitments[..]),
+				committee.threshold as usize,
+			)
+			.filter(|_| qualified >= committee.threshold)
+			.and_then(|commitments| BoundedVec::try_from(commitments).ok());
+			committee.commitments = commitments.unwrap_or_default();
+			Self::clear_dkg(election_id);
+
+			match committee.commitments.first().copied() {
+				Some(public_key) => {
+					committee.public_key = Some(public_key);
+					Self::deposit_event(Event::DkgCompleted { election_id, public_key });
+
+					if let Some(keys) = election.keys.as_mut() {
+						keys.tally_key = public_key;
+						Self::deposit_event(Event::ElectionKeysSet { election_id });
+					}
+				},
+				None => {
+					committee.dkg_start = now;
+					Self::deposit_event(Event::DkgFailed { election_id, qualified });
+				},
+			}
+			Elections::<T>::insert(election_id, &election);
+
+			Ok(())
+		}
+
+		/// Attest to a tally result as a trustee of the tally committee.
+		///
+		/// A trustee attests once it has published its partial decryption of the tally, so that
+		/// `result_hash` can be recomputed from any `threshold` of them. A later attestation
+		/// replaces an earlier one. In a mixnet election, the ballots must be sealed to the
+		/// committee: its published tally key must be the committee key, as otherwise the tally
+		/// authority alone can decrypt them and attestations prove nothing.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::attest_tally(T::MaxTrustees::get()))]
+		pub fn attest_tally(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			result_hash: RootHash<T>,
+		) -> DispatchResult {
+			let trustee = ensure_signed(origin)?;
+
+			let election = Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+			ensure!(election.phase == ElectionPhase::Tallying, Error::<T>::NotInTallyingPhase);
+
+			let committee = election.committee.ok_or(Error::<T>::NoCommittee)?;
+			let public_key = committee.public_key.ok_or(Error::<T>::CommitteeKeyPending)?;
+			Self::trustee_index(&committee, &trustee).ok_or(Error::<T>::NotTrustee)?;
+
+			if election.tally_mode == TallyMode::Mixnet {
+				ensure!(
+					election.keys.is_some_and(|keys| keys.tally_key == public_key),
+					Error::<T>::TallyKeyNotCommitteeKey
+				);
+			}
+
+			TallyAttestations::<T>::insert(election_id, &trustee, result_hash);
+
+			Self::deposit_event(Event::TallyAttested { election_id, trustee, result_hash });
+
+			Ok(())
+		}
+
+		/// Publish a trustee's partial decryption of the encrypted tally of a homomorphic
+		/// election.
+		///
+		/// `partials` holds one share per option with its Chaum-Pedersen proof against the
+		/// trustee's verification key, made for the context `election_id.encode()`. A later
+		/// submission replaces an earlier one.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::submit_partial_decryption(
+			partials.len() as u32,
+			T::MaxTrustees::get(),
+		))]
+		pub fn submit_partial_decryption(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			partials: sp_std::vec::Vec<PartialDecryption>,
+		) -> DispatchResult {
+			let trustee = ensure_signed(origin)?;
+
+			let election = Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+			ensure!(election.phase == ElectionPhase::Tallying, Error::<T>::NotInTallyingPhase);
+			ensure!(
+				matches!(election.tally_mode, TallyMode::Homomorphic { .. }),
+				Error::<T>::NotHomomorphic
+			);
+
+			let committee = election.committee.ok_or(Error::<T>::NoCommittee)?;
+			let index = Self::trustee_index(&committee, &trustee).ok_or(Error::<T>::NotTrustee)?;
+			let total = EncryptedTallies::<T>::get(election_id).ok_or(Error::<T>::NoBallots)?;
+
+			// Bounded by `elgamal::MAX_OPTIONS` proofs.
+			let verification_key = dkg::evaluate(&committee.commitments, index)
+				.ok_or(Error::<T>::InvalidPartialDecryption)?;
+			ensure!(
+				partials.len() == total.len() &&
+					total.iter().zip(&partials).all(|(ciphertext, partial)| {
+						elgamal::verify_partial(
+							&election_id.encode(),
+							&verification_key,
+							ciphertext,
+							partial,
+						)
+					}),
+				Error::<T>::InvalidPartialDecryption
+			);
+
+			let shares = BoundedVec::truncate_from(
+				partials.iter().map(|partial| partial.share).collect::<sp_std::vec::Vec<_>>(),
+			);
+			PartialDecryptions::<T>::insert(election_id, &trustee, shares);
+
+			Self::deposit_event(Event::PartialDecryptionSubmitted { election_id, trustee });
+
+			Ok(())
+		}
+
+		/// Submit the option counts of a homomorphic election.
+		///
+		/// Only the tally authority can submit counts. They are checked against the encrypted
+		/// tally decrypted with the partial decryptions of `threshold` trustees, so no ballot is
+		/// ever decrypted on its own. Without ballots, every count must be zero. Finding the
+		/// counts is a discrete-logarithm search left to the tally authority; checking one takes a
+		/// single multiplication of the generator, whatever its size.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::submit_decrypted_counts(
+			counts.len() as u32,
+			T::MaxTrustees::get(),
+		))]
+		pub fn submit_decrypted_counts(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			counts: sp_std::vec::Vec<u64>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let election = Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+			ensure!(who == election.tally_authority, Error::<T>::NotTallyAuthority);
+			ensure!(election.phase == ElectionPhase::Tallying, Error::<T>::NotInTallyingPhase);
+
+			let TallyMode::Homomorphic { options } = election.tally_mode else {
+				return Err(Error::<T>::NotHomomorphic.into())
+			};
+			ensure!(counts.len() == options as usize, Error::<T>::InvalidCounts);
+
+			match EncryptedTallies::<T>::get(election_id) {
+				None => ensure!(counts.iter().all(|count| *count == 0), Error::<T>::InvalidCounts),
+				Some(total) => {
+					let committee = election.committee.ok_or(Error::<T>::NoCommittee)?;
+
+					// Any `threshold` trustees that published their partial decryption
+					let partials = committee
+						.trustees
+						.iter()
+						.zip(1u32..)
+						.filter_map(|((trustee, _), index)| {
+							PartialDecryptions::<T>::get(election_id, trustee)
+								.map(|shares| (index, shares))
+						})
+						.take(committee.threshold as usize)
+						.collect::<sp_std::vec::Vec<_>>();
+					ensure!(
+						partials.len() == committee.threshold as usize,
+						Error::<T>::NotEnoughPartialDecryptions
+					);
+
+					for (option, (ciphertext, count)) in total.iter().zip(&counts).enumerate() {
+						let shares = partials
+							.iter()
+							.map(|(index, shares)| shares.get(option).map(|share| (*index, *share)))
+							.collect::<Option<sp_std::vec::Vec<_>>>()
+							.ok_or(Error::<T>::InvalidCounts)?;
+						ensure!(
+							elgamal::check_count(&ciphertext.b, &shares, *count),
+							Error::<T>::InvalidCounts
+						);
+					}
+				},
+			}
+
+			let counts = OptionCounts::truncate_from(counts);
+			DecryptedCounts::<T>::insert(election_id, &counts);
+
+			Self::deposit_event(Event::CountsVerified { election_id, counts });
+
+			Ok(())
+		}
+
+		/// Make an election anonymous by committing to its voters.
+		///
+		/// Only the admin can set the allowlist, while registration is open, and can replace it
+		/// until then. `root` is the Merkle root of the voters' identity commitments; proofs of
+		/// membership are checked under the verifying key the runtime pins (see `membership`).
+		/// Voters then cast through `cast_anonymous_vote` instead of registering.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::set_voter_allowlist())]
+		pub fn set_voter_allowlist(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			root: FieldElement,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let election = Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+			ensure!(who == election.admin, Error::<T>::NotAdmin);
+			ensure!(
+				election.phase == ElectionPhase::Registration,
+				Error::<T>::NotInRegistrationPhase
+			);
+
+			ensure!(T::MembershipVerifier::ENABLED, Error::<T>::AnonymousVotingDisabled);
+
+			VoterAllowlists::<T>::insert(election_id, root);
+
+			Self::deposit_event(Event::VoterAllowlistSet { election_id, root });
+
+			Ok(())
+		}
+
+		/// Cast a ballot in an anonymous election.
+		///
+		/// Any account can submit it: eligibility comes from `proof`, a membership proof for
+		/// `nullifier` and `ciphertext` against `root`, which must be the election's allowlist
+		/// root. `ciphertext` is
+		/// checked as in `cast_vote`, the nullifier standing in for the voter. A second ballot
+		/// with the same nullifier is rejected or replaces the first one, depending on the
+		/// election's `RevotePolicy`.
+		///
+		/// It can also be submitted unsigned, so no account is linked to the ballot at all. The
+		/// election's funding pot then pays `FeelessBallotFee`, up to `MaxFeelessBallots` per
+		/// nullifier (see `validate_unsigned`), and is not refunded if the ballot is rejected.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::cast_anonymous_vote(elgamal::MAX_OPTIONS))]
+		pub fn cast_anonymous_vote(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			root: FieldElement,
+			nullifier: Nullifier,
+			proof: sp_std::vec::Vec<u8>,
+			ciphertext: sp_std::vec::Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			// Unsigned ballots were accepted by `validate_unsigned`, signed ones pay their fee
+			let unsigned = ensure_none(origin.clone()).is_ok();
+			if !unsigned {
+				ensure_signed(origin)?;
+			}
+
+			let election = Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+			Self::ensure_voting(&election)?;
+
+			let allowlist_root =
+				VoterAllowlists::<T>::get(election_id).ok_or(Error::<T>::NoAllowlist)?;
+			ensure!(root == allowlist_root, Error::<T>::AllowlistRootMismatch);
+
+			// Enforce one valid ballot per nullifier
+			let previous = Nullifiers::<T>::get(election_id, nullifier);
+			if previous.is_some() {
+				ensure!(
+					election.revote_policy == RevotePolicy::LastVoteWins,
+					Error::<T>::AlreadyVoted
+				);
+			}
+
+			let proof: MembershipProof = proof.try_into().map_err(|_| Error::<T>::ProofTooLong)?;
+			let bounded: Ciphertext =
+				ciphertext.try_into().map_err(|_| Error::<T>::CiphertextTooLong)?;
+
+			let public_inputs = membership::public_inputs(election_id, &root, &nullifier, &bounded);
+			ensure!(
+				T::MembershipVerifier::verify(&public_inputs, &proof),
+				Error::<T>::InvalidMembershipProof
+			);
+
+			let sender = BallotSender::Nullifier(nullifier);
+			if unsigned {
+				Self::charge_pot(election_id, &sender)?;
+			}
+
+			let ciphertext_hash = merkle::hash_leaf(&bounded);
+			let Some((index, options)) = Self::store_ballot_paid(
+				election_id,
+				&election,
+				&sender,
+				&bounded,
+				previous,
+				unsigned,
+			)?
+			else {
+				return Ok(().into())
+			};
+			Nullifiers::<T>::insert(election_id, nullifier, index);
+
+			Self::deposit_event(Event::AnonymousBallotCast {
+				election_id,
+				nullifier,
+				index,
+				ciphertext_hash,
+				replaced: previous,
+			});
+
+			Ok(Some(T::WeightInfo::cast_anonymous_vote(options.into())).into())
+		}
+
+		/// Add `amount` to the funding pot that pays for the feeless ballots of an election.
+		///
+		/// Anyone can fund an election until voting closes.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::fund_election())]
+		pub fn fund_election(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let funder = ensure_signed(origin)?;
+
+			let election = Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+			ensure!(
+				matches!(election.phase, ElectionPhase::Registration | ElectionPhase::Voting),
+				Error::<T>::NotInVotingPhase
+			);
+
+			T::Currency::transfer(
+				&funder,
+				&Self::pot_account(election_id),
+				amount,
+				Preservation::Preserve,
+			)?;
+
+			Self::deposit_event(Event::ElectionFunded { election_id, funder, amount });
+
+			Ok(())
+		}
+
+		/// Send what is left in the funding pot of a finalized or cancelled election to its
+		/// admin.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::reclaim_election_funds())]
+		pub fn reclaim_election_funds(
+			origin: OriginFor<T>,
+			election_id: ElectionId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let election = Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+			ensure!(who == election.admin, Error::<T>::NotAdmin);
+			ensure!(election.phase.is_closed(), Error::<T>::ElectionNotClosed);
+
+			let pot = Self::pot_account(election_id);
+			let amount =
+				T::Currency::reducible_balance(&pot, Preservation::Expendable, Fortitude::Polite);
+			ensure!(!amount.is_zero(), Error::<T>::NothingToReclaim);
+			T::Currency::transfer(&pot, &election.admin, amount, Preservation::Expendable)?;
+
+			Self::deposit_event(Event::ElectionFundsReclaimed { election_id, amount });
+
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		/// Accept unsigned anonymous ballots the election's funding pot pays for.
+		///
+		/// The membership proof, and the validity proof of a homomorphic ballot, are checked
+		/// here, so only eligible voters reach the pool and each nullifier is held to
+		/// `MaxFeelessBallots`. Storage lookups, sizes and the ballot's encoding are checked
+		/// first, so a stale or malformed ballot costs no pairing. One ballot per nullifier is
+		/// pooled at a time, for at most `UnsignedLongevity` blocks and not past voting.
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::cast_anonymous_vote { election_id, root, nullifier, proof, ciphertext } =
+				call
+			else {
+				return InvalidTransaction::Call.into()
+			};
+			let election = Self::check_unsigned_ballot(*election_id, root, nullifier)?;
+
+			if proof.len() > MembershipProof::bound() || ciphertext.len() > Ciphertext::bound() {
+				return InvalidTransaction::ExhaustsResources.into()
+			}
+			let ballot = match election.tally_mode {
+				TallyMode::Mixnet => None,
+				TallyMode::Homomorphic { options } => Some(
+					elgamal::decode_ballot(ciphertext, options).ok_or(InvalidTransaction::BadProof)?,
+				),
+			};
+
+			let public_inputs = membership::public_inputs(*election_id, root, nullifier, ciphertext);
+			if !T::MembershipVerifier::verify(&public_inputs, proof) {
+				return InvalidTransaction::BadProof.into()
+			}
+			if let Some(ballot) = ballot {
+				let context =
+					Self::ballot_context(*election_id, &BallotSender::Nullifier(*nullifier));
+				let valid = election
+					.committee
+					.as_ref()
+					.and_then(|committee| committee.public_key)
+					.is_some_and(|key| elgamal::verify_ballot(&key, &context, &ballot));
+				if !valid {
+					return InvalidTransaction::BadProof.into()
+				}
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let longevity = election.voting_deadline.saturating_sub(now).saturated_into::<u64>();
+			ValidTransaction::with_tag_prefix("DaomixVoting")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((election_id, nullifier))
+				.longevity(longevity.clamp(1, T::UnsignedLongevity::get().max(1)))
+				.propagate(true)
+				.build()
+		}
+
+		/// Only the cheap checks: the call verifies the proofs itself.
+		fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+			let Call::cast_anonymous_vote { election_id, root, nullifier, .. } = call else {
+				return Err(InvalidTransaction::Call.into())
+			};
+			Self::check_unsigned_ballot(*election_id, root, nullifier).map(|_| ())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Published onion keys of an election, if any.
+		pub fn election_keys(election_id: ElectionId) -> Option<ElectionKeys> {
+			Elections::<T>::get(election_id).and_then(|election| election.keys)
+		}
+
+		/// Ballots with index in `start..start + limit` that were not replaced, with their
+		/// indices.
+		///
+		/// `limit` is capped at `MAX_BALLOTS_PER_PAGE`.
+		pub fn ballots_page(
+			election_id: ElectionId,
+			start: BallotIndex,
+			limit: u32,
+		) -> sp_std::vec::Vec<(BallotIndex, Ciphertext)> {
+			let end = start
+				.saturating_add(limit.min(MAX_BALLOTS_PER_PAGE))
+				.min(BallotCount::<T>::get(election_id));
+
+			(start..end)
+				.filter(|index| !ReplacedBallots::<T>::get(election_id, index))
+				.filter_map(|index| {
+					Ballots::<T>::get(election_id, index).map(|ciphertext| (index, ciphertext))
+				})
+				.collect()
+		}
+
+		/// Inclusion proof of a ballot in the input root of its election.
+		///
+		/// `None` until the input root is computed, and for replaced or unknown ballots. Reads
+		/// at most two stored tree nodes per level (see `InputTreeNodes`).
+		pub fn ballot_proof(election_id: ElectionId, index: BallotIndex) -> Option<BallotProof> {
+			let root = InputRoots::<T>::get(election_id)?;
+			let position = InputLeafPositions::<T>::get(election_id, index)?;
+			let leaf_count = ValidBallotCount::<T>::get(election_id);
+
+			let node = |level: u8, index: u32| InputTreeNodes::<T>::get(election_id, (level, index));
+			let leaf = node(0, position)?;
+			let proof = merkle::stored_proof(position, leaf_count, node)?;
+			Some(BallotProof { index, leaf, position, leaf_count, proof, root })
+		}
+
+		/// Phase, deadlines and ballot counts of an election, if it exists.
+		pub fn election_status(
+			election_id: ElectionId,
+		) -> Option<ElectionStatus<BlockNumberFor<T>>> {
+			let election = Elections::<T>::get(election_id)?;
+			Some(ElectionStatus {
+				phase: election.phase,
+				tally_mode: election.tally_mode,
+				registration_deadline: election.registration_deadline,
+				voting_deadline: election.voting_deadline,
+				ballot_count: BallotCount::<T>::get(election_id),
+				valid_ballot_count: ValidBallotCount::<T>::get(election_id),
+				has_mix_commitments: election.commitment_input_root.is_some(),
+				challenge_deadline: TallyResults::<T>::get(election_id)
+					.map(|tally| tally.challenge_deadline),
+			})
+		}
+
+		/// Queue `election_id` for a phase check in `on_initialize` of block `at`, or of the
+		/// first of the next `MAX_SCHEDULE_SPILL` blocks with room if `at` is full.
+		///
+		/// A phase check compares the deadlines with the current block, so a late check moves
+		/// the election to the same phase as a timely one.
+		pub(crate) fn schedule_transition(
+			election_id: ElectionId,
+			mut at: BlockNumberFor<T>,
+		) -> DispatchResult {
+			for _ in 0..=MAX_SCHEDULE_SPILL {
+				if PhaseSchedule::<T>::try_mutate(at, |due| due.try_push(election_id)).is_ok() {
+					return Ok(())
+				}
+				at.saturating_inc();
+			}
+			Err(Error::<T>::TooManyScheduledTransitions.into())
+		}
+
+		/// Move an election to the phase implied by its deadlines at block `now`.
+		///
+		/// Only the deadline-driven transitions are handled here: the registration and voting
+		/// deadlines, and the end of the challenge window of a submitted tally. A homomorphic
+		/// election still without a committee key when registration ends is cancelled.
+		fn advance_phase(election_id: ElectionId, now: BlockNumberFor<T>) {
+			let Some(mut election) = Elections::<T>::get(election_id) else { return };
+
+			let mut next = match election.phase {
+				ElectionPhase::Registration if now > election.voting_deadline =>
+					ElectionPhase::Mixing,
+				ElectionPhase::Registration if now > election.registration_deadline =>
+					ElectionPhase::Voting,
+				ElectionPhase::Voting if now > election.voting_deadline => ElectionPhase::Mixing,
+				ElectionPhase::Disputable if Self::can_finalize(election_id, now) => {
+					Self::finalize(election_id, &mut election);
+					Elections::<T>::insert(election_id, &election);
+					return
+				},
+				_ => return,
+			};
+
+			// Homomorphic ballots are checked and summed under the committee key, so an election
+			// without one is cancelled instead of opening
+			let homomorphic = matches!(election.tally_mode, TallyMode::Homomorphic { .. });
+			let keyless = election.committee.as_ref().and_then(|c| c.public_key).is_none();
+			if election.phase == ElectionPhase::Registration && homomorphic && keyless {
+				Self::release_election_deposit(&election);
+				Self::set_phase(election_id, &mut election, ElectionPhase::Cancelled);
+				Elections::<T>::insert(election_id, &election);
+				Self::deposit_event(Event::ElectionCancelled { election_id });
+				return
+			}
+
+			let voting_closed = next == ElectionPhase::Mixing;
+			if voting_closed && election.tally_mode != TallyMode::Mixnet {
+				// Homomorphic ballots are tallied without mixing
+				next = ElectionPhase::Tallying;
+			}
+
+			Self::set_phase(election_id, &mut election, next);
+			Elections::<T>::insert(election_id, &election);
+
+			if voting_closed {
+				InputRootProgress::<T>::insert(election_id, InputRootBuilder::default());
+			}
+		}
+
+		/// Hash up to `limit` ballots into the input roots being computed, one election at a
+		/// time.
+		///
+		/// Ballots are final once voting closes, so each root is built in ballot index order
+		/// across as many blocks as needed.
+		fn build_input_roots(mut limit: u32) -> Weight {
+			let mut weight = Weight::zero();
+
+			while limit > 0 {
+				let Some((election_id, mut builder)) = InputRootProgress::<T>::iter().next() else {
+					break
+				};
+				let count = BallotCount::<T>::get(election_id);
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 1));
+
+				while builder.next_index < count && limit > 0 {
+					let index = builder.next_index;
+					builder.next_index.saturating_inc();
+					limit.saturating_dec();
+					weight = weight.saturating_add(T::WeightInfo::hash_ballot());
+
+					if ReplacedBallots::<T>::get(election_id, index) {
+						continue
+					}
+					if let Some(ballot) = Ballots::<T>::get(election_id, index) {
+						InputLeafPositions::<T>::insert(election_id, index, builder.leaf_count);
+						merkle::push_leaf(
+							&mut builder.peaks,
+							builder.leaf_count,
+							merkle::hash_leaf(&ballot),
+							|level, position, node| {
+								InputTreeNodes::<T>::insert(election_id, (level, position), node)
+							},
+						);
+						builder.leaf_count.saturating_inc();
+					}
+				}
+
+				if builder.next_index < count {
+					InputRootProgress::<T>::insert(election_id, builder);
+				} else {
+					let root = merkle::root(&builder.peaks);
+					InputRootProgress::<T>::remove(election_id);
+					InputRoots::<T>::insert(election_id, root);
+					Self::deposit_event(Event::InputRootComputed { election_id, root });
+				}
+			}
+
+			weight
+		}
+
+		/// Record mix commitments on an election in the `Mixing` phase and move it to
+		/// `Tallying`.
+		///
+		/// The caller is responsible for authorization and for writing the election back.
+		fn do_set_mix_commitments(
+			election_id: ElectionId,
+			election: &mut Election<T>,
+			commitment_input_root: RootHash<T>,
+			commitment_output_root: RootHash<T>,
+		) -> DispatchResult {
+			// Commitments can only be set once voting has closed
+			ensure!(election.phase == ElectionPhase::Mixing, Error::<T>::NotInMixingPhase);
+
+			let input_root =
+				InputRoots::<T>::get(election_id).ok_or(Error::<T>::InputRootPending)?;
+			ensure!(
+				commitment_input_root.as_ref() == &input_root[..],
+				Error::<T>::InputRootMismatch
+			);
+
+			election.commitment_input_root = Some(commitment_input_root);
+			election.commitment_output_root = Some(commitment_output_root);
+
+			Self::deposit_event(Event::MixCommitmentsSet { election_id });
+			Self::set_phase(election_id, election, ElectionPhase::Tallying);
+
+			Ok(())
+		}
+
+		/// Draw the shuffle challenges of an election from the hash of `seed_block`.
+		fn set_seed_block(election_id: ElectionId, seed_block: BlockNumberFor<T>) {
+			ShuffleStages::<T>::insert(election_id, ShuffleStage::Challenging { seed_block });
+			Self::deposit_event(Event::ShuffleSeedBlockSet { election_id, seed_block });
+		}
+
+		/// Record the unanswered shuffle challenges of an election, which is proven once none
+		/// are left.
+		fn set_open_challenges(election_id: ElectionId, open: u32) {
+			if open == 0 {
+				ShuffleStages::<T>::insert(election_id, ShuffleStage::Proven);
+				Self::deposit_event(Event::ShuffleProven { election_id });
+			} else {
+				ShuffleStages::<T>::insert(election_id, ShuffleStage::Proving { open });
+			}
+		}
+
+		/// Whether a `Disputable` election can be finalized at block `now`: its challenge window
+		/// is closed and no dispute is open.
+		fn can_finalize(election_id: ElectionId, now: BlockNumberFor<T>) -> bool {
+			Disputes::<T>::decode_len(election_id).unwrap_or(0) == 0 &&
+				TallyResults::<T>::get(election_id)
+					.is_some_and(|tally| now > tally.challenge_deadline)
+		}
+
+		/// Move an election to `Finalized` and release the tally authority's bond and the
+		/// admin's deposit.
+		///
+		/// The caller is responsible for writing the election back to storage.
+		fn finalize(election_id: ElectionId, election: &mut Election<T>) {
+			Self::release_tally_bonds(election_id, election);
+			Self::release_election_deposit(election);
+			Self::set_phase(election_id, election, ElectionPhase::Finalized);
+		}
+
+		/// Remove the open dispute at `index` of an election.
+		fn take_dispute(
+			election_id: ElectionId,
+			index: u32,
+		) -> Result<DisputeOf<T>, DispatchError> {
+			Disputes::<T>::try_mutate(election_id, |disputes| {
+				let index = index as usize;
+				ensure!(index < disputes.len(), Error::<T>::DisputeNotFound);
+				Ok(disputes.remove(index))
+			})
+		}
+
+		/// Release the bonds of every open dispute of an election.
+		fn release_dispute_bonds(election_id: ElectionId) {
+			for dispute in Disputes::<T>::take(election_id) {
+				let _ = T::Currency::release(
+					&HoldReason::DisputeBond.into(),
+					&dispute.challenger,
+					dispute.bond,
+					Precision::BestEffort,
+				);
+			}
+		}
+
+		/// Release the bonds of the tally authority and of every open dispute of an election.
+		fn release_tally_bonds(election_id: ElectionId, election: &Election<T>) {
+			Self::release_dispute_bonds(election_id);
+			if let Some(tally) = TallyResults::<T>::get(election_id) {
+				let _ = T::Currency::release(
+					&HoldReason::TallyBond.into(),
+					&election.tally_authority,
+					tally.bond,
+					Precision::BestEffort,
+				);
+			}
+		}
+
+		/// Release the deposit the admin of a closing election holds.
+		fn release_election_deposit(election: &Election<T>) {
+			let _ = T::Currency::release(
+				&HoldReason::ElectionDeposit.into(),
+				&election.admin,
+				election.deposit,
+				Precision::BestEffort,
+			);
+		}
+
+		/// Context the validity proof of a homomorphic ballot of `sender` is made for: the SCALE
+		/// encoding of `(election_id, sender)`.
+		pub fn ballot_context(
+			election_id: ElectionId,
+			sender: &BallotSender<T::AccountId>,
+		) -> sp_std::vec::Vec<u8> {
+			(election_id, sender).encode()
+		}
+
+		/// Account of the funding pot of an election.
+		pub fn pot_account(election_id: ElectionId) -> T::AccountId {
+			T::PalletId::get().into_sub_account_truncating(election_id)
+		}
+
+		/// Whether the funding pot of an election can pay for another feeless ballot of
+		/// `sender`.
+		pub fn pot_can_pay(election_id: ElectionId, sender: &BallotSender<T::AccountId>) -> bool {
+			let pot = Self::pot_account(election_id);
+			FeelessBallots::<T>::get(election_id, sender) < T::MaxFeelessBallots::get() &&
+				T::Currency::reducible_balance(&pot, Preservation::Expendable, Fortitude::Polite) >=
+					T::FeelessBallotFee::get()
+		}
+
+		/// Whether the funding pot pays for a `cast_vote` from `origin`: a registered voter who
+		/// can vote now and has feeless ballots left.
+		pub fn sponsors_vote(origin: &OriginFor<T>, election_id: ElectionId) -> bool {
+			let Ok(voter) = ensure_signed(origin.clone()) else { return false };
+			let Some(election) = Elections::<T>::get(election_id) else { return false };
+
+			Self::ensure_voting(&election).is_ok() &&
+				!VoterAllowlists::<T>::contains_key(election_id) &&
+				Voters::<T>::contains_key(election_id, &voter) &&
+				(election.revote_policy == RevotePolicy::LastVoteWins ||
+					!VoterBallot::<T>::contains_key(election_id, &voter)) &&
+				Self::pot_can_pay(election_id, &BallotSender::Voter(voter))
+		}
+
+		/// Burn `FeelessBallotFee` from the funding pot for a ballot of `sender`.
+		fn charge_pot(
+			election_id: ElectionId,
+			sender: &BallotSender<T::AccountId>,
+		) -> DispatchResult {
+			T::Currency::burn_from(
+				&Self::pot_account(election_id),
+				T::FeelessBallotFee::get(),
+				Preservation::Expendable,
+				Precision::Exact,
+				Fortitude::Polite,
+			)?;
+			FeelessBallots::<T>::mutate(election_id, sender, |count| {
+				*count = count.saturating_add(1)
+			});
+			Ok(())
+		}
+
+		/// Checks of an unsigned anonymous ballot that do not involve its proofs.
+		fn check_unsigned_ballot(
+			election_id: ElectionId,
+			root: &FieldElement,
+			nullifier: &Nullifier,
+		) -> Result<Election<T>, TransactionValidityError> {
+			let election = Elections::<T>::get(election_id)
+				.filter(|election| Self::ensure_voting(election).is_ok())
+				.ok_or(InvalidTransaction::Stale)?;
+			if VoterAllowlists::<T>::get(election_id).ok_or(InvalidTransaction::Call)? != *root {
+				return Err(InvalidTransaction::BadProof.into())
+			}
+
+			if election.revote_policy == RevotePolicy::RejectRevote &&
+				Nullifiers::<T>::contains_key(election_id, nullifier)
+			{
+				return Err(InvalidTransaction::Stale.into())
+			}
+			if !Self::pot_can_pay(election_id, &BallotSender::Nullifier(*nullifier)) {
+				return Err(InvalidTransaction::Payment.into())
+			}
+
+			Ok(election)
+		}
+
+		/// Ensure an election takes ballots: it is in the `Voting` phase and its committee key,
+		/// if any, is published and, in a mixnet election, is its tally key.
+		fn ensure_voting(election: &Election<T>) -> DispatchResult {
+			ensure!(election.phase == ElectionPhase::Voting, Error::<T>::NotInVotingPhase);
+
+			// Ballots of a committee election are sealed to the committee key
+			if let Some(committee) = &election.committee {
+				let public_key = committee.public_key.ok_or(Error::<T>::CommitteeKeyPending)?;
+				if election.tally_mode == TallyMode::Mixnet {
+					ensure!(
+						election.keys.as_ref().is_some_and(|keys| keys.tally_key == public_key),
+						Error::<T>::TallyKeyNotCommitteeKey
+					);
+				}
+			}
+			Ok(())
+		}
+
+		/// Store a ballot of `sender`, superseding its `previous` ballot if any.
+		///
+		/// Returns the index of the new ballot and the options of the election if it is
+		/// homomorphic, 0 otherwise.
+		fn store_ballot(
+			election_id: ElectionId,
+			election: &Election<T>,
+			sender: &BallotSender<T::AccountId>,
+			ciphertext: &Ciphertext,
+			previous: Option<BallotIndex>,
+		) -> Result<(BallotIndex, u8), DispatchError> {
+			let options = match election.tally_mode {
+				TallyMode::Mixnet => 0,
+				TallyMode::Homomorphic { options } => {
+					let public_key = election
+						.committee
+						.as_ref()
+						.and_then(|committee| committee.public_key)
+						.ok_or(Error::<T>::NoCommittee)?;
+					Self::add_to_encrypted_tally(
+						election_id,
+						&public_key,
+						&Self::ballot_context(election_id, sender),
+						options,
+						ciphertext,
+						previous,
+					)?;
+					options
+				},
+			};
+
+			// Get current ballot index
+			let index = BallotCount::<T>::get(election_id);
+
+			// Store ballot
+			Ballots::<T>::insert(election_id, index, ciphertext);
+
+			// Increment ballot count
+			BallotCount::<T>::insert(election_id, index.saturating_add(1));
+
+			match previous {
+				// Mark the superseded ballot so it is excluded from the mix input
+				Some(old_index) => ReplacedBallots::<T>::insert(election_id, old_index, true),
+				None => ValidBallotCount::<T>::mutate(election_id, |count| {
+					*count = count.saturating_add(1)
+				}),
+			}
+
+			Ok((index, options))
+		}
+
+		/// Store a ballot of `sender` as `store_ballot` does, `feeless` if the funding pot paid
+		/// for it.
+		///
+		/// A rejected feeless ballot does not fail the call, which would refund the pot and the
+		/// sender's feeless quota for a ballot nobody paid a fee for: it is reported in
+		/// `FeelessBallotRejected` instead, and `None` is returned.
+		fn store_ballot_paid(
+			election_id: ElectionId,
+			election: &Election<T>,
+			sender: &BallotSender<T::AccountId>,
+			ciphertext: &Ciphertext,
+			previous: Option<BallotIndex>,
+			feeless: bool,
+		) -> Result<Option<(BallotIndex, u8)>, DispatchError> {
+			let stored = with_storage_layer(|| {
+				Self::store_ballot(election_id, election, sender, ciphertext, previous)
+			});
+			match stored {
+				Ok(stored) => Ok(Some(stored)),
+				Err(error) if feeless => {
+					Self::deposit_event(Event::FeelessBallotRejected {
+						election_id,
+						sender: sender.clone(),
+						error,
+					});
+					Ok(None)
+				},
+				Err(error) => Err(error),
+			}
+		}
+
+		/// Check a homomorphic ballot under the committee key in `context` and add it to the
+		/// encrypted tally of its election, taking out the voter's `previous` ballot if it replaces
+		/// one.
+		fn add_to_encrypted_tally(
+			election_id: ElectionId,
+			public_key: &GroupElement,
+			context: &[u8],
+			options: u8,
+			ciphertext: &[u8],
+			previous: Option<BallotIndex>,
+		) -> DispatchResult {
+			let ballot =
+				elgamal::decode_ballot(ciphertext, options).ok_or(Error::<T>::InvalidBallot)?;
+			ensure!(
+				elgamal::verify_ballot(public_key, context, &ballot),
+				Error::<T>::InvalidBallotProof
+			);
+
+			let mut total = match EncryptedTallies::<T>::get(election_id) {
+				Some(total) => elgamal::accumulate(&total, &ballot.ciphertexts, false),
+				None => Some(ballot.ciphertexts.into_inner()),
+			}
+			.ok_or(Error::<T>::InvalidBallot)?;
+
+			// Stored ballots were checked when they were cast
+			let replaced = previous
+				.and_then(|index| Ballots::<T>::get(election_id, index))
+				.and_then(|old| elgamal::decode_ballot(&old, options));
+			if let Some(replaced) = replaced {
+				total = elgamal::accumulate(&total, &replaced.ciphertexts, true)
+					.ok_or(Error::<T>::InvalidBallot)?;
+			}
+
+			EncryptedTallies::<T>::insert(election_id, ElGamalBallot::truncate_from(total));
+			Ok(())
+		}
+
+		/// Tally committee of an election whose DKG has not completed.
+		fn pending_committee(election_id: ElectionId) -> Result<TallyCommittee<T>, DispatchError> {
+			let election = Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+			let committee = election.committee.ok_or(Error::<T>::NoCommittee)?;
+			ensure!(committee.public_key.is_none(), Error::<T>::CommitteeKeyEstablished);
+			Ok(committee)
+		}
+
+		/// Share index (1-based) of `who` in a tally committee.
+		fn trustee_index(committee: &TallyCommittee<T>, who: &T::AccountId) -> Option<u32> {
+			committee
+				.trustees
+				.iter()
+				.position(|(trustee, _)| trustee == who)
+				.map(|position| position as u32 + 1)
+		}
+
+		/// Last blocks of the dealing, complaint and answer rounds of a DKG started at `start`.
+		fn dkg_deadlines(
+			start: BlockNumberFor<T>,
+		) -> (BlockNumberFor<T>, BlockNumberFor<T>, BlockNumberFor<T>) {
+			let period = T::DkgRoundPeriod::get();
+			let dealing_end = start.saturating_add(period);
+			let complaint_end = dealing_end.saturating_add(period);
+			(dealing_end, complaint_end, complaint_end.saturating_add(period))
+		}
+
+		/// Remove the deals and complaints of the DKG of an election.
+		fn clear_dkg(election_id: ElectionId) {
+			let _ = DkgDeals::<T>::clear_prefix(election_id, T::MaxTrustees::get(), None);
+			let _ = DkgComplaints::<T>::clear_prefix(
+				election_id,
+				T::MaxTrustees::get().saturating_mul(T::MaxTrustees::get()),
+				None,
+			);
+		}
+
+		/// Remove the trustee attestations, verified counts and shuffle proof of an election
+		/// whose tally was rejected.
+		fn clear_tally(election_id: ElectionId) {
+			let _ = TallyAttestations::<T>::clear_prefix(election_id, T::MaxTrustees::get(), None);
+			DecryptedCounts::<T>::remove(election_id);
+
+			let hops = <MixCascade<X25519PublicKey>>::bound() as u32;
+			let _ = ShuffleHops::<T>::clear_prefix(election_id, hops, None);
+			let _ = ShuffleChallenges::<T>::clear_prefix(election_id, hops, None);
+			ShuffleStages::<T>::remove(election_id);
+		}
+
+		/// Update the in-memory election phase and emit `PhaseChanged`.
+		///
+		/// The caller is responsible for writing the election back to storage.
+		fn set_phase(election_id: ElectionId, election: &mut Election<T>, to: ElectionPhase) {
+			let from = election.phase;
+			election.phase = to;
+			Self::deposit_event(Event::PhaseChanged { election_id, from, to });
+		}
+	}
+
+	impl<T: Config> pallet_mix_job::ElectionProvider<RootHash<T>> for Pallet<T> {
+		fn election_exists(election_id: ElectionId) -> bool {
+			Elections::<T>::contains_key(election_id)
+		}
+
+		fn is_ready_for_mixing(election_id: ElectionId) -> bool {
+			Elections::<T>::get(election_id)
+				.is_some_and(|election| election.phase == ElectionPhase::Mixing)
+		}
+
+		fn set_mix_commitments(
+			election_id: ElectionId,
+			input_root: RootHash<T>,
+			output_root: RootHash<T>,
+		) -> DispatchResult {
+			let mut election =
+				Elections::<T>::get(election_id).ok_or(Error::<T>::ElectionNotFound)?;
+
+			Self::do_set_mix_commitments(election_id, &mut election, input_root, output_root)?;
+
+			Elections::<T>::insert(election_id, &election);
+
+			Ok(())
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn setup_ready_election(election_id: ElectionId) -> RootHash<T> {
+			let admin = T::AccountId::decode(&mut TrailingZeroInput::zeroes())
+				.expect("infinite input yields an account; qed");
+			let election = Election::<T> {
+				admin: admin.clone(),
+				deposit: Zero::zero(),
+				tally_authority: admin,
+				registration_deadline: Zero::zero(),
+				voting_deadline: Zero::zero(),
+				revote_policy: RevotePolicy::RejectRevote,
+				tally_mode: TallyMode::Mixnet,
+				keys: None,
+				committee: None,
+				commitment_input_root: None,
+				commitment_output_root: None,
+				phase: ElectionPhase::Mixing,
+			};
+			Elections::<T>::insert(election_id, election);
+
+			// Both are all zeroes, as the 32-byte `RootHash` commitments must be.
+			InputRoots::<T>::insert(election_id, MerkleHash::default());
+			RootHash::<T>::default()
+		}
 	}
 }
 
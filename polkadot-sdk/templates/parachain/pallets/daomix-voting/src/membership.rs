@@ -0,0 +1,75 @@
+//! Anonymous eligibility through a Semaphore-style membership proof.
+//!
+//! The election admin commits to the Merkle root of the voters' identity commitments instead of
+//! registering accounts. The verifying key of the membership circuit is pinned by the runtime
+//! through `Config::MembershipVerifier`, so no admin can bring a key they hold the trapdoor of.
+//! The circuit, its setup and a prover are in the `membership-circuit` crate next to the pallet.
+//! A voter then casts from any account with a zk proof that they know the secret behind one of
+//! the committed identities, revealing only a nullifier derived from that secret and the
+//! election's scope. The same identity always yields the same nullifier, so it
+//! stands in for the voter in `Nullifiers` without linking the ballot to the identity.
+//!
+//! The circuit's public inputs are, in order, the allowlist root, the nullifier, the signal (the
+//! hash of the ballot ciphertext, so a proof cannot be replayed with another ballot) and the
+//! scope. Hashes enter the circuit as field elements through `to_field`.
+
+use crate::ElectionId;
+use frame::prelude::*;
+
+/// A 32-byte little-endian element of the proof system's scalar field.
+pub type FieldElement = [u8; 32];
+
+/// Per-election nullifier of a voter identity.
+pub type Nullifier = FieldElement;
+
+/// Encoded membership proof.
+pub type MembershipProof = BoundedVec<u8, ConstU32<512>>;
+
+/// Checks membership proofs under the verifying key it pins.
+pub trait MembershipVerifier {
+	/// Whether a verifying key is pinned, so elections can be anonymous.
+	const ENABLED: bool;
+
+	/// Whether `proof` is valid for `public_inputs`.
+	fn verify(public_inputs: &[FieldElement; 4], proof: &[u8]) -> bool;
+
+	/// A valid proof for `public_inputs`, if the verifier can make one.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn prove(public_inputs: &[FieldElement; 4]) -> Option<sp_std::vec::Vec<u8>>;
+}
+
+/// No verifying key: elections cannot be anonymous.
+impl MembershipVerifier for () {
+	const ENABLED: bool = false;
+
+	fn verify(_: &[FieldElement; 4], _: &[u8]) -> bool {
+		false
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn prove(_: &[FieldElement; 4]) -> Option<sp_std::vec::Vec<u8>> {
+		None
+	}
+}
+
+/// A 32-byte hash as a field element: little-endian with the top byte cleared, so it is below
+/// any 255-bit modulus.
+pub fn to_field(mut hash: [u8; 32]) -> FieldElement {
+	hash[31] = 0;
+	hash
+}
+
+/// Scope of the nullifiers of an election.
+pub fn scope(election_id: ElectionId) -> FieldElement {
+	to_field(keccak_256(&(b"daomix/election", election_id).encode()))
+}
+
+/// Public inputs of a membership proof for `ciphertext`.
+pub fn public_inputs(
+	election_id: ElectionId,
+	root: &FieldElement,
+	nullifier: &Nullifier,
+	ciphertext: &[u8],
+) -> [FieldElement; 4] {
+	[*root, *nullifier, to_field(keccak_256(ciphertext)), scope(election_id)]
+}
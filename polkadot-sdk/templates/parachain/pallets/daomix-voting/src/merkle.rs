@@ -0,0 +1,242 @@
+//! Keccak-256 Merkle tree over ballot ciphertexts.
+//!
+//! Matches `buildMerkleRoot` in `mixer/src/orchestrator.ts` (merkletreejs with `sortPairs`):
+//! leaves are `keccak256(ciphertext)`, each pair is hashed smaller node first, and the last node
+//! of an odd level moves up unchanged. Such a tree is the right fold of the perfect subtrees
+//! ("peaks") given by the binary decomposition of the leaf count, so its root can be built one
+//! leaf at a time while keeping at most 32 peaks.
+//!
+//! Sorting pairs makes proofs independent of the side of each sibling, so a proof shows that a
+//! leaf is in the tree, not at which position.
+
+use frame::prelude::*;
+use sp_std::vec::Vec;
+
+/// A node of the ballot Merkle tree.
+pub type MerkleHash = [u8; 32];
+
+/// Peaks of a partially built tree, largest first.
+pub type Peaks = BoundedVec<MerkleHash, ConstU32<32>>;
+
+/// Leaf of a ballot ciphertext.
+pub fn hash_leaf(ciphertext: &[u8]) -> MerkleHash {
+	keccak_256(ciphertext)
+}
+
+/// Parent of two nodes, in either order.
+pub fn hash_pair(a: &MerkleHash, b: &MerkleHash) -> MerkleHash {
+	let (first, second) = if a <= b { (a, b) } else { (b, a) };
+	let mut data = [0u8; 64];
+	data[..32].copy_from_slice(first);
+	data[32..].copy_from_slice(second);
+	keccak_256(&data)
+}
+
+/// Append a leaf to a tree of `leaf_count` leaves.
+///
+/// `store(level, index, node)` is called with the leaf and with every node over a whole power
+/// of two of leaves it completes, so that `stored_proof` can read them back.
+pub fn push_leaf(
+	peaks: &mut Peaks,
+	leaf_count: u32,
+	leaf: MerkleHash,
+	mut store: impl FnMut(u8, u32, &MerkleHash),
+) {
+	let mut node = leaf;
+	let mut count = leaf_count;
+	let mut level = 0u8;
+	store(level, count, &node);
+	while count & 1 == 1 {
+		if let Some(left) = peaks.pop() {
+			node = hash_pair(&left, &node);
+		}
+		count >>= 1;
+		level.saturating_inc();
+		store(level, count, &node);
+	}
+	// A `u32` leaf count has at most 32 peaks.
+	let _ = peaks.try_push(node);
+}
+
+/// Root of a tree given its peaks; all zeroes for an empty tree.
+pub fn root(peaks: &[MerkleHash]) -> MerkleHash {
+	peaks
+		.iter()
+		.rev()
+		.copied()
+		.reduce(|acc, peak| hash_pair(&peak, &acc))
+		.unwrap_or([0; 32])
+}
+
+/// Sibling path of the leaf at `position`, from the leaf up to the root.
+///
+/// Levels where the node moves up unpaired add no sibling.
+pub fn proof(leaves: Vec<MerkleHash>, mut position: usize) -> Option<Vec<MerkleHash>> {
+	if position >= leaves.len() {
+		return None
+	}
+
+	let mut proof = Vec::new();
+	let mut level = leaves;
+	while level.len() > 1 {
+		if let Some(sibling) = level.get(position ^ 1) {
+			proof.push(*sibling);
+		}
+		level = level
+			.chunks(2)
+			.map(|pair| pair.get(1).map_or(pair[0], |right| hash_pair(&pair[0], right)))
+			.collect();
+		position /= 2;
+	}
+	Some(proof)
+}
+
+/// Sibling path of the leaf at `position` of a tree of `leaf_count` leaves, from the nodes
+/// stored by `push_leaf`.
+///
+/// `node(level, index)` is the stored node over leaves `index << level..(index + 1) << level`.
+/// The last node of a level may cover fewer leaves; it is folded from the peaks below it on the
+/// way up, so at most two nodes are read per level.
+pub fn stored_proof(
+	mut position: u32,
+	leaf_count: u32,
+	mut node: impl FnMut(u8, u32) -> Option<MerkleHash>,
+) -> Option<Vec<MerkleHash>> {
+	if position >= leaf_count {
+		return None
+	}
+
+	let mut proof = Vec::new();
+	// Fold of the peaks below `level`, which is the partial last node of the level if any.
+	let mut partial: Option<MerkleHash> = None;
+	let mut level = 0u8;
+	let mut width = leaf_count;
+	while width > 1 {
+		let complete = leaf_count >> level;
+		let sibling = position ^ 1;
+		if sibling < complete {
+			proof.push(node(level, sibling)?);
+		} else if sibling < width {
+			proof.push(partial?);
+		}
+		// A peak at this level pairs with the partial node, or moves up as one.
+		if complete & 1 == 1 {
+			let peak = node(level, complete - 1)?;
+			partial = Some(partial.map_or(peak, |right| hash_pair(&peak, &right)));
+		}
+		position /= 2;
+		width = width / 2 + width % 2;
+		level.saturating_inc();
+	}
+	Some(proof)
+}
+
+/// Whether `proof` links `leaf`, at `position` of a tree of `leaf_count` leaves, to `root`.
+///
+/// Pairs are hashed in sorted order, so the proof needs no left/right flags. The position and
+/// leaf count only tell at which levels the node moves up unpaired, so the proof must have a
+/// sibling for every other level; any position with the same levels verifies too.
+pub fn verify_proof(
+	root: &MerkleHash,
+	leaf: MerkleHash,
+	mut position: u32,
+	leaf_count: u32,
+	proof: &[MerkleHash],
+) -> bool {
+	if position >= leaf_count {
+		return false
+	}
+
+	let mut siblings = proof.iter();
+	let mut node = leaf;
+	let mut width = leaf_count;
+	while width > 1 {
+		if position ^ 1 < width {
+			let Some(sibling) = siblings.next() else { return false };
+			node = hash_pair(&node, sibling);
+		}
+		position /= 2;
+		width = width / 2 + width % 2;
+	}
+	siblings.next().is_none() && node == *root
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::BTreeMap;
+
+	/// Root of the incrementally built tree over the ballots `ballot-0`, `ballot-1`, ...
+	fn root_of(count: u32) -> MerkleHash {
+		let mut peaks = Peaks::default();
+		for index in 0..count {
+			push_leaf(
+				&mut peaks,
+				index,
+				hash_leaf(format!("ballot-{index}").as_bytes()),
+				|_, _, _| {},
+			);
+		}
+		root(&peaks)
+	}
+
+	fn from_hex(hex: &str) -> MerkleHash {
+		let mut node = [0u8; 32];
+		for (byte, pair) in node.iter_mut().zip(hex.as_bytes().chunks(2)) {
+			*byte = u8::from_str_radix(core::str::from_utf8(pair).unwrap(), 16).unwrap();
+		}
+		node
+	}
+
+	// The same roots are pinned for `buildMerkleRoot` in `mixer/test/merkle.test.ts`.
+	#[test]
+	fn root_matches_the_mixer() {
+		assert_eq!(root_of(0), [0; 32]);
+		assert_eq!(root_of(1), hash_leaf(b"ballot-0"));
+		assert_eq!(
+			root_of(1),
+			from_hex("0166671d2301d93d0fc12659e86b4e285bd21602d3258162649498c4e2c96b0b")
+		);
+		assert_eq!(
+			root_of(3),
+			from_hex("d7d34d52ef045d74107fd6849f0fddf85dc5b0429419a0c6350827c6be1e7db9")
+		);
+		assert_eq!(
+			root_of(5),
+			from_hex("8ad5e4239c38ef89abc7e5109a915019293d561d9b8f2a40bc7174d3faa89bcc")
+		);
+	}
+
+	#[test]
+	fn proofs_verify_against_the_root() {
+		for count in 1..8u32 {
+			let leaves = (0..count)
+				.map(|index| hash_leaf(format!("ballot-{index}").as_bytes()))
+				.collect::<Vec<_>>();
+			let root = root_of(count);
+			let mut peaks = Peaks::default();
+			let mut nodes = BTreeMap::new();
+			for (index, leaf) in leaves.iter().enumerate() {
+				push_leaf(&mut peaks, index as u32, *leaf, |level, index, node| {
+					nodes.insert((level, index), *node);
+				});
+			}
+
+			for position in 0..count {
+				let leaf = leaves[position as usize];
+				let proof = proof(leaves.clone(), position as usize).unwrap();
+				let stored = stored_proof(position, count, |level, index| {
+					nodes.get(&(level, index)).copied()
+				});
+				assert_eq!(stored.as_ref(), Some(&proof));
+				assert!(verify_proof(&root, leaf, position, count, &proof));
+				assert!(!verify_proof(&root, hash_leaf(b"other"), position, count, &proof));
+				assert!(!verify_proof(&root, leaf, count, count, &proof));
+				// A proof missing its last sibling stops below the root.
+				if let Some((_, shorter)) = proof.split_last() {
+					assert!(!verify_proof(&root, leaf, position, count, shorter));
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,144 @@
+//! Storage migrations of the DaoMix voting pallet.
+
+use crate::pallet::*;
+use frame::{
+	deps::frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade},
+	prelude::*,
+};
+
+/// Storage layouts of version 0, before elections had phases.
+mod v0 {
+	use super::*;
+
+	/// An election of version 0.
+	#[derive(Encode, Decode)]
+	pub struct Election<AccountId, BlockNumber, Hash> {
+		pub admin: AccountId,
+		pub tally_authority: AccountId,
+		pub registration_deadline: BlockNumber,
+		pub voting_deadline: BlockNumber,
+		pub commitment_input_root: Option<Hash>,
+		pub commitment_output_root: Option<Hash>,
+		pub finalized: bool,
+	}
+
+	/// A tally result of version 0.
+	#[derive(Encode, Decode)]
+	pub struct TallyResult<Hash> {
+		pub result_uri: BoundedVec<u8, ConstU32<256>>,
+		pub result_hash: Hash,
+	}
+}
+
+/// Migrates elections and tally results from version 0 to version 1.
+///
+/// The phase of an election is derived from its deadlines, its mix commitments and whether it
+/// was finalized, and the phase checks still ahead of it are scheduled. Elections already
+/// mixing get their input root computed. Migrated elections reject revotes, are tallied
+/// through the mixnet and hold no deposit; their tallies hold no bond and can no longer be
+/// disputed. Ballots cast before the upgrade are all valid, but are not linked to their voters.
+pub struct InnerMigrateV0ToV1<T>(PhantomData<T>);
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut migrated = sp_std::vec::Vec::new();
+
+		Elections::<T>::translate::<v0::Election<T::AccountId, BlockNumberFor<T>, RootHash<T>>, _>(
+			|election_id, old| {
+				let phase = if old.finalized {
+					ElectionPhase::Finalized
+				} else if old.commitment_input_root.is_some() {
+					ElectionPhase::Tallying
+				} else if now > old.voting_deadline {
+					ElectionPhase::Mixing
+				} else if now > old.registration_deadline {
+					ElectionPhase::Voting
+				} else {
+					ElectionPhase::Registration
+				};
+				migrated.push((election_id, phase, old.registration_deadline, old.voting_deadline));
+
+				Some(Election {
+					admin: old.admin,
+					deposit: Zero::zero(),
+					tally_authority: old.tally_authority,
+					registration_deadline: old.registration_deadline,
+					voting_deadline: old.voting_deadline,
+					revote_policy: RevotePolicy::RejectRevote,
+					tally_mode: TallyMode::Mixnet,
+					keys: None,
+					committee: None,
+					commitment_input_root: old.commitment_input_root,
+					commitment_output_root: old.commitment_output_root,
+					phase,
+				})
+			},
+		);
+
+		let elections = migrated.len() as u64;
+		for (election_id, phase, registration_deadline, voting_deadline) in migrated {
+			ValidBallotCount::<T>::insert(election_id, BallotCount::<T>::get(election_id));
+
+			// Scheduling only fails behind `MAX_SCHEDULE_SPILL` full blocks, which no chain
+			// still on version 0 has.
+			match phase {
+				ElectionPhase::Registration => {
+					let at = registration_deadline.saturating_add(One::one());
+					let _ = Pallet::<T>::schedule_transition(election_id, at);
+					let at = voting_deadline.saturating_add(One::one());
+					let _ = Pallet::<T>::schedule_transition(election_id, at);
+				},
+				ElectionPhase::Voting => {
+					let at = voting_deadline.saturating_add(One::one());
+					let _ = Pallet::<T>::schedule_transition(election_id, at);
+				},
+				ElectionPhase::Mixing =>
+					InputRootProgress::<T>::insert(election_id, InputRootBuilder::default()),
+				_ => {},
+			}
+		}
+
+		let mut tallies = 0u64;
+		TallyResults::<T>::translate::<v0::TallyResult<RootHash<T>>, _>(|_, old| {
+			tallies.saturating_inc();
+			Some(TallyResult {
+				result_uri: old.result_uri,
+				result_hash: old.result_hash,
+				challenge_deadline: now,
+				bond: Zero::zero(),
+			})
+		});
+
+		// Per election: the translation, both ballot counts and two phase checks.
+		T::DbWeight::get().reads_writes(
+			elections.saturating_mul(4).saturating_add(tallies).saturating_add(1),
+			elections.saturating_mul(4).saturating_add(tallies),
+		)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(
+		_state: sp_std::vec::Vec<u8>,
+	) -> Result<(), frame::try_runtime::TryRuntimeError> {
+		ensure!(
+			Elections::<T>::iter_keys().all(|id| Elections::<T>::try_get(id).is_ok()),
+			"an election does not decode after the migration"
+		);
+		ensure!(
+			TallyResults::<T>::iter_keys().all(|id| TallyResults::<T>::try_get(id).is_ok()),
+			"a tally result does not decode after the migration"
+		);
+		Ok(())
+	}
+}
+
+/// `InnerMigrateV0ToV1`, run only while the on-chain storage version is 0, which it then sets
+/// to 1.
+pub type MigrateV0ToV1<T> = VersionedMigration<
+	0,
+	1,
+	InnerMigrateV0ToV1<T>,
+	Pallet<T>,
+	<T as frame_system::Config>::DbWeight,
+>;
@@ -0,0 +1,182 @@
+use crate::{
+	groth16::{Groth16Verifier, MembershipCircuitKey},
+	membership::{FieldElement, MembershipVerifier},
+	merkle::MerkleHash,
+	shuffle::{HopIndex, ShuffleLinkVerifier},
+	ElectionId,
+};
+use frame::{
+	deps::{frame_support::weights::constants::RocksDbWeight, frame_system::GenesisConfig},
+	prelude::*,
+	runtime::prelude::*,
+	testing_prelude::*,
+};
+
+// Configure a mock runtime to test the pallet.
+#[frame_construct_runtime]
+mod test_runtime {
+	#[runtime::runtime]
+	#[runtime::derive(
+		RuntimeCall,
+		RuntimeEvent,
+		RuntimeError,
+		RuntimeOrigin,
+		RuntimeFreezeReason,
+		RuntimeHoldReason,
+		RuntimeSlashReason,
+		RuntimeLockId,
+		RuntimeTask,
+		RuntimeViewFunction
+	)]
+	pub struct Test;
+
+	#[runtime::pallet_index(0)]
+	pub type System = frame_system;
+	#[runtime::pallet_index(1)]
+	pub type DaomixVoting = crate;
+	#[runtime::pallet_index(2)]
+	pub type Balances = pallet_balances;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Nonce = u64;
+	type Block = MockBlock<Test>;
+	type BlockHashCount = ConstU64<250>;
+	type DbWeight = RocksDbWeight;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+/// Membership proof `MockMembership` accepts.
+pub const VALID_PROOF: &[u8] = b"member";
+
+parameter_types! {
+	/// Membership proofs `MockMembership` has checked.
+	pub static MembershipChecks: u32 = 0;
+}
+
+/// Membership verifier accepting `VALID_PROOF`, and the membership circuit's proofs as the
+/// runtime does.
+pub struct MockMembership;
+impl MembershipVerifier for MockMembership {
+	const ENABLED: bool = true;
+
+	fn verify(public_inputs: &[FieldElement; 4], proof: &[u8]) -> bool {
+		MembershipChecks::mutate(|checks| *checks += 1);
+		proof == VALID_PROOF ||
+			Groth16Verifier::<MembershipCircuitKey>::verify(public_inputs, proof)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn prove(_: &[FieldElement; 4]) -> Option<Vec<u8>> {
+		Some(VALID_PROOF.to_vec())
+	}
+}
+
+/// What `MockLinkVerifier` takes as the decryption of an input leaf.
+pub fn mock_decrypt(input_leaf: &MerkleHash) -> MerkleHash {
+	keccak_256(input_leaf)
+}
+
+/// Link verifier accepting an output leaf only if it is `mock_decrypt` of the input leaf.
+pub struct MockLinkVerifier;
+impl ShuffleLinkVerifier for MockLinkVerifier {
+	fn verify(
+		_: ElectionId,
+		_: HopIndex,
+		input: &MerkleHash,
+		output: &MerkleHash,
+		_: &[u8],
+	) -> bool {
+		mock_decrypt(input) == *output
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn link(_: HopIndex, input: &MerkleHash) -> Option<(MerkleHash, Vec<u8>)> {
+		Some((mock_decrypt(input), Vec::new()))
+	}
+}
+
+parameter_types! {
+	pub const VotingPalletId: PalletId = PalletId(*b"dm/votes");
+}
+
+/// Deposit held for each election in the mock.
+pub const ELECTION_DEPOSIT: u64 = 50;
+/// Bond held with each tally in the mock.
+pub const TALLY_BOND: u64 = 20;
+/// Bond held with each dispute in the mock.
+pub const DISPUTE_BOND: u64 = 10;
+/// Blocks a tally can be disputed in the mock.
+pub const CHALLENGE_PERIOD: u64 = 5;
+/// Fee the funding pot pays for each feeless ballot in the mock.
+pub const FEELESS_BALLOT_FEE: u64 = 3;
+/// Feeless ballots per voter in the mock.
+pub const MAX_FEELESS_BALLOTS: u32 = 2;
+/// Blocks an unsigned ballot stays in the pool at most in the mock.
+pub const UNSIGNED_LONGEVITY: u64 = 2;
+/// Balance `new_test_ext` gives accounts 1 to 5.
+pub const INITIAL_BALANCE: u64 = 1_000;
+
+impl crate::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type MaxPhaseTransitionsPerBlock = ConstU32<2>; // Small limit to test spilling
+	type MaxBallotsHashedPerBlock = ConstU32<4>;
+	type LinkVerifier = MockLinkVerifier;
+	type ShuffleChallengesPerPair = ConstU32<2>;
+	type MaxLinksPerProof = ConstU32<4>;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type Currency = Balances;
+	type ElectionDeposit = ConstU64<ELECTION_DEPOSIT>;
+	type ChallengePeriod = ConstU64<CHALLENGE_PERIOD>;
+	type TallyBond = ConstU64<TALLY_BOND>;
+	type DisputeBond = ConstU64<DISPUTE_BOND>;
+	type MaxDisputes = ConstU32<2>;
+	type DisputeOrigin = EnsureRoot<u64>;
+	type MaxTrustees = ConstU32<4>;
+	type DkgRoundPeriod = ConstU64<3>;
+	type MembershipVerifier = MockMembership;
+	type PalletId = VotingPalletId;
+	type FeelessBallotFee = ConstU64<FEELESS_BALLOT_FEE>;
+	type MaxFeelessBallots = ConstU32<MAX_FEELESS_BALLOTS>;
+	type UnsignedPriority = ConstU64<100>;
+	type UnsignedLongevity = ConstU64<UNSIGNED_LONGEVITY>;
+}
+
+/// Admin of the elections created by the tests.
+pub const ADMIN: u64 = 1;
+/// Tally authority of the elections created by the tests.
+pub const AUTHORITY: u64 = 2;
+/// Voters of the elections created by the tests.
+pub const VOTER: u64 = 3;
+pub const OTHER_VOTER: u64 = 4;
+/// Account disputing tallies in the tests.
+pub const CHALLENGER: u64 = 5;
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> TestState {
+	let mut ext: TestState = GenesisConfig::<Test>::default().build_storage().unwrap().into();
+	ext.execute_with(|| {
+		// Go past genesis block so events get deposited.
+		System::set_block_number(1);
+		for who in [ADMIN, AUTHORITY, VOTER, OTHER_VOTER, CHALLENGER] {
+			assert_ok!(<Balances as fungible::Mutate<u64>>::mint_into(&who, INITIAL_BALANCE));
+		}
+	});
+	ext
+}
+
+/// Run the hooks of every block up to and including `n`.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		<DaomixVoting as Hooks<u64>>::on_initialize(next);
+	}
+}
@@ -0,0 +1,50 @@
+//! Runtime API definitions for the DaoMix Voting pallet.
+
+use crate::{BallotIndex, BallotProof, Ciphertext, ElectionId, ElectionKeys, ElectionStatus};
+use codec::Codec;
+use pallet_mix_job::{JobId, JobStatus, MixJobInfo};
+use sp_std::vec::Vec;
+
+frame::deps::sp_api::decl_runtime_apis! {
+	pub trait DaoMixVotingApi {
+		/// Mix cascade and tally public keys published for an election, if any.
+		fn election_keys(election_id: ElectionId) -> Option<ElectionKeys>;
+	}
+
+	/// Elections, ballots and mix jobs, for orchestrators and front ends.
+	pub trait DaoMixApi<AccountId, Hash, BlockNumber, Balance>
+	where
+		AccountId: Codec,
+		Hash: Codec,
+		BlockNumber: Codec,
+		Balance: Codec,
+	{
+		/// Ballots of an election with index in `start..start + limit`, skipping replaced
+		/// ballots. At most `MAX_BALLOTS_PER_PAGE` indices are scanned per call.
+		fn ballots(
+			election_id: ElectionId,
+			start: BallotIndex,
+			limit: u32,
+		) -> Vec<(BallotIndex, Ciphertext)>;
+
+		/// Merkle inclusion proof of a ballot in the input root of its election, once the root
+		/// is computed. Replaced ballots have no proof.
+		fn ballot_proof(election_id: ElectionId, index: BallotIndex) -> Option<BallotProof>;
+
+		/// Phase, deadlines and ballot counts of an election, if it exists.
+		fn election_status(election_id: ElectionId) -> Option<ElectionStatus<BlockNumber>>;
+
+		/// Jobs with `status` and an id in `start..start + limit`, in id order; at most
+		/// `MAX_JOBS_PER_PAGE` ids are scanned, so page by advancing `start` by `limit`.
+		fn jobs_by_status(
+			status: JobStatus,
+			start: JobId,
+			limit: u32,
+		) -> Vec<MixJobInfo<AccountId, BlockNumber, Hash, Balance>>;
+
+		/// Stored jobs of an election, in id order.
+		fn jobs_for_election(
+			election_id: ElectionId,
+		) -> Vec<MixJobInfo<AccountId, BlockNumber, Hash, Balance>>;
+	}
+}
@@ -0,0 +1,209 @@
+//! Randomized partial checking of the mix cascade.
+//!
+//! Each mix hop commits to the Merkle roots of its input and output ciphertexts and to a
+//! `link_root` over one salted link commitment per output position:
+//! `keccak256(SCALE((output_index, input_index, input_leaf, output_leaf, salt)))`, in output
+//! order. Once every hop of the
+//! cascade is committed, challenges are drawn from a later block hash. Hops are checked in pairs:
+//! a position between hops `2k` and `2k + 1` is opened either backwards in hop `2k` or forwards in
+//! hop `2k + 1`, never both, so no ballot can be followed through the pair. An unpaired last hop
+//! is only opened backwards.
+//!
+//! Revealing a link shows which input a given output came from. The link commitment names both
+//! positions and both leaves, so a hop cannot answer with the leaves of another link once
+//! `link_root` is committed. If the runtime's [`ShuffleLinkVerifier`] checks decryptions, the
+//! opening also proves that the output is the decryption of that input, and a hop that drops or
+//! replaces `d` of `n` ballots survives `c` challenges with probability about `(1 - d / n)^c`.
+//!
+//! The ballot trees hash pairs in sorted order to match the mixer, so an inclusion proof does not
+//! fix the position of its leaf. A hop that links an output to a copy of another input leaf is
+//! therefore not caught by the opening; only the leaf count and the shape of the proof are
+//! checked against the position.
+
+use crate::{
+	merkle::{self, MerkleHash},
+	ElectionId,
+};
+use frame::prelude::*;
+use sp_std::vec::Vec;
+
+/// Position of a hop in the mix cascade, in peeling order.
+pub type HopIndex = u8;
+
+/// Sibling path of an opened leaf, see `merkle::verify_proof`.
+pub type MerkleProof = BoundedVec<MerkleHash, ConstU32<32>>;
+
+/// Evidence that an output ciphertext is the decryption of an input ciphertext.
+pub type LinkEvidence = BoundedVec<u8, ConstU32<4096>>;
+
+/// Commitments of one mix hop.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	MaxEncodedLen,
+	TypeInfo,
+	Clone,
+	Copy,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+)]
+pub struct HopCommitment {
+	/// Merkle root of the ciphertexts the hop received.
+	pub input_root: MerkleHash,
+	/// Merkle root of the ciphertexts the hop produced, in output order.
+	pub output_root: MerkleHash,
+	/// Merkle root of the link commitments, in output order.
+	pub link_root: MerkleHash,
+}
+
+/// Which end of a link a challenge names.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum ChallengeSide {
+	/// Reveal where the output at `position` came from.
+	Output,
+	/// Reveal where the input at `position` went.
+	Input,
+}
+
+/// A link a hop must open.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub struct LinkChallenge {
+	/// End of the link named by `position`.
+	pub side: ChallengeSide,
+	/// Input or output position of the link.
+	pub position: u32,
+	/// Whether a valid opening was submitted.
+	pub answered: bool,
+}
+
+/// An opened link of a hop.
+#[derive(Encode, Decode, DecodeWithMemTracking, TypeInfo, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct LinkOpening {
+	/// Position of the ciphertext in the hop output.
+	pub output_index: u32,
+	/// Position of the ciphertext it was decrypted from in the hop input.
+	pub input_index: u32,
+	/// Salt hiding the link in `link_root`.
+	pub salt: [u8; 32],
+	/// Path of the link commitment in `link_root`.
+	pub link_proof: MerkleProof,
+	/// Leaf of the input ciphertext.
+	pub input_leaf: MerkleHash,
+	/// Path of `input_leaf` in `input_root`.
+	pub input_proof: MerkleProof,
+	/// Leaf of the output ciphertext.
+	pub output_leaf: MerkleHash,
+	/// Path of `output_leaf` in `output_root`.
+	pub output_proof: MerkleProof,
+	/// Data checked by the runtime's `ShuffleLinkVerifier`.
+	pub evidence: LinkEvidence,
+}
+
+/// Checks that an output ciphertext of a hop is the decryption of an input ciphertext.
+pub trait ShuffleLinkVerifier {
+	/// Whether `evidence` shows that `output_leaf` decrypts from `input_leaf` at `hop`.
+	fn verify(
+		election_id: ElectionId,
+		hop: HopIndex,
+		input_leaf: &MerkleHash,
+		output_leaf: &MerkleHash,
+		evidence: &[u8],
+	) -> bool;
+
+	/// An output leaf and evidence that `verify` accepts for `input_leaf`, if the verifier can
+	/// make them.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn link(hop: HopIndex, input_leaf: &MerkleHash) -> Option<(MerkleHash, Vec<u8>)>;
+}
+
+/// Accepts every link, checking no decryption.
+///
+/// Only the commitment, inclusion and coverage checks of this module then apply. The mixer layer
+/// key of a hop is shared by every ballot of a batch, so it cannot be revealed per link.
+impl ShuffleLinkVerifier for () {
+	fn verify(_: ElectionId, _: HopIndex, _: &MerkleHash, _: &MerkleHash, _: &[u8]) -> bool {
+		true
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn link(_: HopIndex, input_leaf: &MerkleHash) -> Option<(MerkleHash, Vec<u8>)> {
+		Some((*input_leaf, Vec::new()))
+	}
+}
+
+/// Leaf of a link commitment in `link_root`.
+pub fn link_leaf(
+	output_index: u32,
+	input_index: u32,
+	input_leaf: &MerkleHash,
+	output_leaf: &MerkleHash,
+	salt: &[u8; 32],
+) -> MerkleHash {
+	keccak_256(&(output_index, input_index, input_leaf, output_leaf, salt).encode())
+}
+
+/// Whether `opening` is consistent with the commitments of its hop over `size` ballots.
+///
+/// The link must commit to both leaves, and each leaf must be in its tree with a proof shaped
+/// for the position the link names.
+pub fn check_opening(commitment: &HopCommitment, size: u32, opening: &LinkOpening) -> bool {
+	let LinkOpening { output_index, input_index, .. } = *opening;
+	let link = link_leaf(
+		output_index,
+		input_index,
+		&opening.input_leaf,
+		&opening.output_leaf,
+		&opening.salt,
+	);
+
+	let in_tree = |root: &MerkleHash, leaf: MerkleHash, position: u32, proof: &MerkleProof| {
+		merkle::verify_proof(root, leaf, position, size, proof)
+	};
+
+	in_tree(&commitment.link_root, link, output_index, &opening.link_proof) &&
+		in_tree(&commitment.input_root, opening.input_leaf, input_index, &opening.input_proof) &&
+		in_tree(&commitment.output_root, opening.output_leaf, output_index, &opening.output_proof)
+}
+
+/// Whether `opening` answers `challenge`.
+pub fn answers(challenge: &LinkChallenge, opening: &LinkOpening) -> bool {
+	match challenge.side {
+		ChallengeSide::Output => challenge.position == opening.output_index,
+		ChallengeSide::Input => challenge.position == opening.input_index,
+	}
+}
+
+/// Challenges of each hop of a cascade of `hops` hops over `size` ballots.
+///
+/// Draws `per_pair` positions for each pair of hops from `seed`. Returns one list per hop, each
+/// at most `per_pair` long; all empty if there are no ballots.
+pub fn draw_challenges(
+	seed: &[u8; 32],
+	hops: HopIndex,
+	size: u32,
+	per_pair: u32,
+) -> Vec<Vec<LinkChallenge>> {
+	let mut challenges = (0..hops).map(|_| Vec::new()).collect::<Vec<_>>();
+	if size == 0 {
+		return challenges
+	}
+
+	for first in (0..hops).step_by(2) {
+		let paired = first + 1 < hops;
+		for sample in 0..per_pair {
+			let draw = blake2_256(&(seed, first, sample).encode());
+			let position = u32::from_le_bytes([draw[0], draw[1], draw[2], draw[3]]) % size;
+
+			let (hop, side) = if paired && draw[4] & 1 == 1 {
+				(first + 1, ChallengeSide::Input)
+			} else {
+				(first, ChallengeSide::Output)
+			};
+			challenges[hop as usize].push(LinkChallenge { side, position, answered: false });
+		}
+	}
+
+	challenges
+}
@@ -0,0 +1,1596 @@
+use crate::{
+	elgamal::{self, DleqProof, PartialDecryption},
+	merkle::{self, MerkleHash},
+	migrations,
+	mock::*,
+	shuffle::{self, ChallengeSide, HopCommitment, LinkOpening, MerkleProof},
+	BallotCount, BallotSender, Ballots, Call, Ciphertext, DecryptedCounts, DisputeEvidence,
+	ElectionKeys, ElectionPhase, Elections, EncryptedTallies, Error, Event, FeelessBallots,
+	HoldReason, InputRoots, Nullifiers, PhaseSchedule, ReplacedBallots, RevotePolicy,
+	ShuffleChallenges, ShuffleHops, ShuffleStage, ShuffleStages, TallyAttestations, TallyMode,
+	TallyResults, ValidBallotCount, VoterBallot,
+};
+use curve25519_dalek::{
+	constants::{RISTRETTO_BASEPOINT_COMPRESSED, RISTRETTO_BASEPOINT_POINT},
+	scalar::Scalar,
+};
+use frame::{
+	deps::frame_support::{storage::unhashed, traits::UncheckedOnRuntimeUpgrade},
+	testing_prelude::*,
+};
+
+/// Registration deadline of the elections created by `create`.
+const REGISTRATION_DEADLINE: u64 = 3;
+/// Voting deadline of the elections created by `create`.
+const VOTING_DEADLINE: u64 = 6;
+
+/// Create election `election_id` from `ADMIN`, with `AUTHORITY` as tally authority.
+fn create(election_id: u32, revote_policy: RevotePolicy) {
+	assert_ok!(DaomixVoting::create_election(
+		RuntimeOrigin::signed(ADMIN),
+		election_id,
+		AUTHORITY,
+		REGISTRATION_DEADLINE,
+		VOTING_DEADLINE,
+		revote_policy,
+		TallyMode::Mixnet,
+	));
+}
+
+/// Create election `election_id` with `VOTER` and `OTHER_VOTER` registered and move it to
+/// `Voting`.
+fn voting_election(election_id: u32, revote_policy: RevotePolicy) {
+	create(election_id, revote_policy);
+	for voter in [VOTER, OTHER_VOTER] {
+		assert_ok!(DaomixVoting::register_voter(RuntimeOrigin::signed(ADMIN), election_id, voter));
+	}
+	run_to_block(REGISTRATION_DEADLINE + 1);
+}
+
+/// Amount held from `who` for `reason`.
+fn held(reason: HoldReason, who: u64) -> u64 {
+	<Balances as fungible::InspectHold<u64>>::balance_on_hold(&reason.into(), &who)
+}
+
+fn phase(election_id: u32) -> ElectionPhase {
+	Elections::<Test>::get(election_id).unwrap().phase
+}
+
+#[test]
+fn create_election_holds_deposit_and_schedules_phase_checks() {
+	new_test_ext().execute_with(|| {
+		create(0, RevotePolicy::RejectRevote);
+
+		let election = Elections::<Test>::get(0).unwrap();
+		assert_eq!(election.phase, ElectionPhase::Registration);
+		assert_eq!(election.deposit, ELECTION_DEPOSIT);
+		assert_eq!(held(HoldReason::ElectionDeposit, ADMIN), ELECTION_DEPOSIT);
+		assert_eq!(PhaseSchedule::<Test>::get(REGISTRATION_DEADLINE + 1).into_inner(), vec![0]);
+		assert_eq!(PhaseSchedule::<Test>::get(VOTING_DEADLINE + 1).into_inner(), vec![0]);
+		System::assert_last_event(Event::ElectionCreated { election_id: 0 }.into());
+
+		assert_noop!(
+			DaomixVoting::create_election(
+				RuntimeOrigin::signed(ADMIN),
+				0,
+				AUTHORITY,
+				REGISTRATION_DEADLINE,
+				VOTING_DEADLINE,
+				RevotePolicy::RejectRevote,
+				TallyMode::Mixnet,
+			),
+			Error::<Test>::ElectionAlreadyExists
+		);
+	});
+}
+
+#[test]
+fn create_election_rejects_invalid_deadlines() {
+	new_test_ext().execute_with(|| {
+		for (registration, voting) in [(5, 5), (6, 5), (1, 5)] {
+			assert_noop!(
+				DaomixVoting::create_election(
+					RuntimeOrigin::signed(ADMIN),
+					0,
+					AUTHORITY,
+					registration,
+					voting,
+					RevotePolicy::RejectRevote,
+					TallyMode::Mixnet,
+				),
+				Error::<Test>::InvalidDeadlines
+			);
+		}
+	});
+}
+
+#[test]
+fn create_election_needs_the_deposit() {
+	new_test_ext().execute_with(|| {
+		// Account 6 has no funds.
+		assert_noop!(
+			DaomixVoting::create_election(
+				RuntimeOrigin::signed(6),
+				0,
+				AUTHORITY,
+				REGISTRATION_DEADLINE,
+				VOTING_DEADLINE,
+				RevotePolicy::RejectRevote,
+				TallyMode::Mixnet,
+			),
+			TokenError::FundsUnavailable
+		);
+	});
+}
+
+#[test]
+fn phases_follow_deadlines() {
+	new_test_ext().execute_with(|| {
+		create(0, RevotePolicy::RejectRevote);
+
+		run_to_block(REGISTRATION_DEADLINE);
+		assert_eq!(phase(0), ElectionPhase::Registration);
+
+		run_to_block(REGISTRATION_DEADLINE + 1);
+		assert_eq!(phase(0), ElectionPhase::Voting);
+		System::assert_has_event(
+			Event::PhaseChanged {
+				election_id: 0,
+				from: ElectionPhase::Registration,
+				to: ElectionPhase::Voting,
+			}
+			.into(),
+		);
+		assert_noop!(
+			DaomixVoting::register_voter(RuntimeOrigin::signed(ADMIN), 0, VOTER),
+			Error::<Test>::NotInRegistrationPhase
+		);
+
+		run_to_block(VOTING_DEADLINE + 1);
+		assert_eq!(phase(0), ElectionPhase::Mixing);
+		assert!(PhaseSchedule::<Test>::get(VOTING_DEADLINE + 1).is_empty());
+	});
+}
+
+#[test]
+fn full_schedule_spills_to_later_blocks() {
+	new_test_ext().execute_with(|| {
+		// Two phase checks fit in a block of the mock.
+		for election_id in 0..3 {
+			create(election_id, RevotePolicy::RejectRevote);
+		}
+		assert_eq!(PhaseSchedule::<Test>::get(REGISTRATION_DEADLINE + 1).into_inner(), vec![0, 1]);
+		assert_eq!(PhaseSchedule::<Test>::get(REGISTRATION_DEADLINE + 2).into_inner(), vec![2]);
+
+		// The spilled election starts voting one block late.
+		run_to_block(REGISTRATION_DEADLINE + 1);
+		assert_eq!(phase(1), ElectionPhase::Voting);
+		assert_eq!(phase(2), ElectionPhase::Registration);
+		run_to_block(REGISTRATION_DEADLINE + 2);
+		assert_eq!(phase(2), ElectionPhase::Voting);
+	});
+}
+
+#[test]
+fn cancel_election_releases_deposit() {
+	new_test_ext().execute_with(|| {
+		create(0, RevotePolicy::RejectRevote);
+
+		assert_noop!(
+			DaomixVoting::cancel_election(RuntimeOrigin::signed(VOTER), 0),
+			Error::<Test>::NotAdmin
+		);
+		assert_ok!(DaomixVoting::cancel_election(RuntimeOrigin::signed(ADMIN), 0));
+
+		assert_eq!(phase(0), ElectionPhase::Cancelled);
+		System::assert_last_event(Event::ElectionCancelled { election_id: 0 }.into());
+		assert_eq!(held(HoldReason::ElectionDeposit, ADMIN), 0);
+		assert_noop!(
+			DaomixVoting::cancel_election(RuntimeOrigin::signed(ADMIN), 0),
+			Error::<Test>::ElectionClosed
+		);
+
+		// Phase checks of a cancelled election do nothing.
+		run_to_block(VOTING_DEADLINE + 1);
+		assert_eq!(phase(0), ElectionPhase::Cancelled);
+	});
+}
+
+#[test]
+fn homomorphic_election_without_committee_key_is_cancelled() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(DaomixVoting::create_election(
+			RuntimeOrigin::signed(ADMIN),
+			0,
+			AUTHORITY,
+			REGISTRATION_DEADLINE,
+			VOTING_DEADLINE,
+			RevotePolicy::RejectRevote,
+			TallyMode::Homomorphic { options: 2 },
+		));
+		assert_ok!(DaomixVoting::set_tally_committee(
+			RuntimeOrigin::signed(ADMIN),
+			0,
+			vec![(AUTHORITY, [0; 32]), (CHALLENGER, [0; 32])],
+			2
+		));
+
+		// The DKG is still running when registration ends.
+		run_to_block(REGISTRATION_DEADLINE + 1);
+		assert_eq!(phase(0), ElectionPhase::Cancelled);
+		System::assert_has_event(Event::ElectionCancelled { election_id: 0 }.into());
+		assert_eq!(held(HoldReason::ElectionDeposit, ADMIN), 0);
+		run_to_block(VOTING_DEADLINE + 1);
+		assert_eq!(phase(0), ElectionPhase::Cancelled);
+	});
+}
+
+#[test]
+fn cast_vote_requires_registered_voter_and_voting_phase() {
+	new_test_ext().execute_with(|| {
+		create(0, RevotePolicy::RejectRevote);
+		assert_ok!(DaomixVoting::register_voter(RuntimeOrigin::signed(ADMIN), 0, VOTER));
+		assert_noop!(
+			DaomixVoting::register_voter(RuntimeOrigin::signed(VOTER), 0, OTHER_VOTER),
+			Error::<Test>::NotAdmin
+		);
+		assert_noop!(
+			DaomixVoting::register_voter(RuntimeOrigin::signed(ADMIN), 0, VOTER),
+			Error::<Test>::AlreadyRegistered
+		);
+
+		assert_noop!(
+			DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, b"early".to_vec()),
+			Error::<Test>::NotInVotingPhase
+		);
+
+		run_to_block(REGISTRATION_DEADLINE + 1);
+		assert_noop!(
+			DaomixVoting::cast_vote(RuntimeOrigin::signed(OTHER_VOTER), 0, b"ballot".to_vec()),
+			Error::<Test>::NotRegistered
+		);
+		assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, b"ballot".to_vec()));
+		System::assert_last_event(
+			Event::BallotCast {
+				election_id: 0,
+				voter: VOTER,
+				index: 0,
+				ciphertext_hash: merkle::hash_leaf(b"ballot"),
+			}
+			.into(),
+		);
+
+		run_to_block(VOTING_DEADLINE + 1);
+		assert_noop!(
+			DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, b"late".to_vec()),
+			Error::<Test>::NotInVotingPhase
+		);
+	});
+}
+
+#[test]
+fn reject_revote_keeps_the_first_ballot() {
+	new_test_ext().execute_with(|| {
+		voting_election(0, RevotePolicy::RejectRevote);
+
+		assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, b"first".to_vec()));
+		assert_noop!(
+			DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, b"second".to_vec()),
+			Error::<Test>::AlreadyVoted
+		);
+
+		assert_eq!(BallotCount::<Test>::get(0), 1);
+		assert_eq!(ValidBallotCount::<Test>::get(0), 1);
+		assert_eq!(VoterBallot::<Test>::get(0, VOTER), Some(0));
+	});
+}
+
+#[test]
+fn last_vote_wins_replaces_the_earlier_ballot() {
+	new_test_ext().execute_with(|| {
+		voting_election(0, RevotePolicy::LastVoteWins);
+
+		assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, b"first".to_vec()));
+		assert_ok!(DaomixVoting::cast_vote(
+			RuntimeOrigin::signed(OTHER_VOTER),
+			0,
+			b"other".to_vec()
+		));
+		assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, b"second".to_vec()));
+
+		System::assert_has_event(
+			Event::BallotReplaced { election_id: 0, voter: VOTER, old_index: 0, new_index: 2 }
+				.into(),
+		);
+		assert_eq!(BallotCount::<Test>::get(0), 3);
+		assert_eq!(ValidBallotCount::<Test>::get(0), 2);
+		assert!(ReplacedBallots::<Test>::get(0, 0));
+		assert_eq!(VoterBallot::<Test>::get(0, VOTER), Some(2));
+
+		// Replaced ballots stay stored but are skipped by the runtime API.
+		assert!(Ballots::<Test>::contains_key(0, 0));
+		let page = DaomixVoting::ballots_page(0, 0, 10);
+		let indices = page.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+		assert_eq!(indices, vec![1, 2]);
+		assert_eq!(DaomixVoting::ballots_page(0, 2, 10).len(), 1);
+	});
+}
+
+#[test]
+fn election_keys_are_locked_once_voting_starts() {
+	new_test_ext().execute_with(|| {
+		create(0, RevotePolicy::RejectRevote);
+		assert_eq!(DaomixVoting::election_keys(0), None);
+
+		assert_noop!(
+			DaomixVoting::set_election_keys(RuntimeOrigin::signed(ADMIN), 0, vec![], None, [9; 32]),
+			Error::<Test>::NoMixKeys
+		);
+		assert_noop!(
+			DaomixVoting::set_election_keys(
+				RuntimeOrigin::signed(ADMIN),
+				0,
+				vec![[1; 32]],
+				Some(vec![]),
+				[9; 32]
+			),
+			Error::<Test>::MixKeyCountMismatch
+		);
+		assert_ok!(DaomixVoting::set_election_keys(
+			RuntimeOrigin::signed(ADMIN),
+			0,
+			vec![[1; 32], [2; 32]],
+			None,
+			[9; 32]
+		));
+		assert_eq!(
+			DaomixVoting::election_keys(0),
+			Some(ElectionKeys {
+				mix_keys: BoundedVec::truncate_from(vec![[1; 32], [2; 32]]),
+				pq_mix_keys: None,
+				tally_key: [9; 32],
+			})
+		);
+
+		run_to_block(REGISTRATION_DEADLINE + 1);
+		assert_noop!(
+			DaomixVoting::set_election_keys(
+				RuntimeOrigin::signed(ADMIN),
+				0,
+				vec![[3; 32]],
+				None,
+				[9; 32]
+			),
+			Error::<Test>::NotInRegistrationPhase
+		);
+	});
+}
+
+#[test]
+fn election_status_reports_phase_and_counts() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(DaomixVoting::election_status(0), None);
+		voting_election(0, RevotePolicy::LastVoteWins);
+		assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, b"first".to_vec()));
+		assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, b"second".to_vec()));
+
+		let status = DaomixVoting::election_status(0).unwrap();
+		assert_eq!(status.phase, ElectionPhase::Voting);
+		assert_eq!(status.tally_mode, TallyMode::Mixnet);
+		assert_eq!(
+			(status.registration_deadline, status.voting_deadline),
+			(REGISTRATION_DEADLINE, VOTING_DEADLINE)
+		);
+		assert_eq!((status.ballot_count, status.valid_ballot_count), (2, 1));
+		assert!(!status.has_mix_commitments);
+		assert_eq!(status.challenge_deadline, None);
+	});
+}
+
+#[test]
+fn ballot_proofs_verify_against_the_input_root() {
+	new_test_ext().execute_with(|| {
+		voting_election(0, RevotePolicy::LastVoteWins);
+		for (voter, ballot) in [(VOTER, &b"a"[..]), (OTHER_VOTER, b"b"), (VOTER, b"c")] {
+			assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(voter), 0, ballot.to_vec()));
+		}
+		assert_eq!(InputRoots::<Test>::get(0), None);
+		assert_eq!(DaomixVoting::ballot_proof(0, 1), None);
+
+		// Voting closes and the input root is computed over the valid ballots.
+		run_to_block(VOTING_DEADLINE + 1);
+		let root = InputRoots::<Test>::get(0).unwrap();
+		assert_eq!(root, merkle::hash_pair(&merkle::hash_leaf(b"b"), &merkle::hash_leaf(b"c")));
+		System::assert_has_event(Event::InputRootComputed { election_id: 0, root }.into());
+
+		for index in [1, 2] {
+			let proof = DaomixVoting::ballot_proof(0, index).unwrap();
+			assert_eq!(proof.root, root);
+			assert_eq!((proof.position, proof.leaf_count), (index - 1, 2));
+			assert!(merkle::verify_proof(&root, proof.leaf, index - 1, 2, &proof.proof));
+			assert!(!merkle::verify_proof(&root, proof.leaf, 2, 2, &proof.proof));
+		}
+		assert_eq!(DaomixVoting::ballot_proof(0, 0), None);
+		assert_eq!(DaomixVoting::ballot_proof(0, 3), None);
+	});
+}
+
+#[test]
+fn input_roots_are_built_across_blocks_within_the_limit() {
+	new_test_ext().execute_with(|| {
+		voting_election(0, RevotePolicy::LastVoteWins);
+		for ballot in [&b"a"[..], b"b", b"c", b"d", b"e"] {
+			assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, ballot.to_vec()));
+		}
+		assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(OTHER_VOTER), 0, b"f".to_vec()));
+
+		// Replaced ballots count against the limit too, so four of six fit in the first block.
+		run_to_block(VOTING_DEADLINE);
+		System::set_block_number(VOTING_DEADLINE + 1);
+		let weight = <DaomixVoting as Hooks<u64>>::on_initialize(VOTING_DEADLINE + 1);
+		assert_eq!(InputRoots::<Test>::get(0), None);
+		assert!(weight.proof_size() >= 4 * Ciphertext::max_encoded_len() as u64);
+
+		run_to_block(VOTING_DEADLINE + 2);
+		assert_eq!(
+			InputRoots::<Test>::get(0),
+			Some(merkle::hash_pair(&merkle::hash_leaf(b"e"), &merkle::hash_leaf(b"f")))
+		);
+	});
+}
+
+/// Ballots cast in the election of `shuffled_election`.
+const SHUFFLED_BALLOTS: [&[u8]; 2] = [b"a", b"b"];
+/// Permutation of the single mix hop of `shuffled_election`: output `i` is the decryption of
+/// input `SHUFFLE[i]`.
+const SHUFFLE: [usize; 2] = [1, 0];
+
+/// Salt of the link of output `output`.
+fn salt(output: usize) -> [u8; 32] {
+	[output as u8 + 1; 32]
+}
+
+/// Input, output and link leaves of the mix hop of `shuffled_election`.
+fn hop_leaves() -> [Vec<MerkleHash>; 3] {
+	let inputs = SHUFFLED_BALLOTS
+		.iter()
+		.map(|ballot| merkle::hash_leaf(ballot))
+		.collect::<Vec<_>>();
+	let outputs = SHUFFLE.iter().map(|&input| mock_decrypt(&inputs[input])).collect::<Vec<_>>();
+	let links = SHUFFLE
+		.iter()
+		.enumerate()
+		.map(|(output, &input)| {
+			shuffle::link_leaf(
+				output as u32,
+				input as u32,
+				&inputs[input],
+				&outputs[output],
+				&salt(output),
+			)
+		})
+		.collect();
+	[inputs, outputs, links]
+}
+
+/// Sibling path of the leaf at `position`.
+fn path(leaves: &[MerkleHash], position: usize) -> MerkleProof {
+	BoundedVec::truncate_from(merkle::proof(leaves.to_vec(), position).unwrap())
+}
+
+/// Opening of the link of output `output`.
+fn opening(output: usize) -> LinkOpening {
+	let [inputs, outputs, links] = hop_leaves();
+	let input = SHUFFLE[output];
+	LinkOpening {
+		output_index: output as u32,
+		input_index: input as u32,
+		salt: salt(output),
+		link_proof: path(&links, output),
+		input_leaf: inputs[input],
+		input_proof: path(&inputs, input),
+		output_leaf: outputs[output],
+		output_proof: path(&outputs, output),
+		evidence: Default::default(),
+	}
+}
+
+/// Move election 0, with a single mix hop and `SHUFFLED_BALLOTS` cast, to `Tallying`, commit its
+/// hop and draw its challenges.
+fn shuffled_election() {
+	create(0, RevotePolicy::RejectRevote);
+	assert_ok!(DaomixVoting::set_election_keys(
+		RuntimeOrigin::signed(ADMIN),
+		0,
+		vec![[1; 32]],
+		None,
+		[9; 32]
+	));
+	for voter in [VOTER, OTHER_VOTER] {
+		assert_ok!(DaomixVoting::register_voter(RuntimeOrigin::signed(ADMIN), 0, voter));
+	}
+	run_to_block(REGISTRATION_DEADLINE + 1);
+	for (voter, ballot) in [VOTER, OTHER_VOTER].into_iter().zip(SHUFFLED_BALLOTS) {
+		assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(voter), 0, ballot.to_vec()));
+	}
+	run_to_block(VOTING_DEADLINE + 1);
+	shuffle_ballots();
+}
+
+/// Commit the mix of `SHUFFLED_BALLOTS` in election 0, which is mixing, as a single hop and draw
+/// its challenges.
+fn shuffle_ballots() {
+	let [inputs, outputs, links] = hop_leaves();
+	let commitment = HopCommitment {
+		input_root: merkle::hash_pair(&inputs[0], &inputs[1]),
+		output_root: merkle::hash_pair(&outputs[0], &outputs[1]),
+		link_root: merkle::hash_pair(&links[0], &links[1]),
+	};
+	assert_ok!(DaomixVoting::set_mix_commitments(
+		RuntimeOrigin::signed(AUTHORITY),
+		0,
+		commitment.input_root.into(),
+		commitment.output_root.into()
+	));
+	assert_ok!(DaomixVoting::commit_shuffle_hop(
+		RuntimeOrigin::signed(AUTHORITY),
+		0,
+		0,
+		commitment
+	));
+
+	// Challenges are drawn from the hash of the next block.
+	let seed_block = System::block_number() + 1;
+	assert_eq!(ShuffleStages::<Test>::get(0), Some(ShuffleStage::Challenging { seed_block }));
+	run_to_block(seed_block + 1);
+	frame_system::BlockHash::<Test>::insert(seed_block, H256::repeat_byte(7));
+	assert_ok!(DaomixVoting::draw_shuffle_challenges(RuntimeOrigin::signed(CHALLENGER), 0));
+}
+
+/// Answer every challenge of the mix hop of `shuffled_election`.
+fn prove_shuffle() {
+	let openings = ShuffleChallenges::<Test>::get(0, 0)
+		.iter()
+		.map(|challenge| opening(challenge.position as usize))
+		.collect();
+	assert_ok!(DaomixVoting::prove_shuffle_links(
+		RuntimeOrigin::signed(CHALLENGER),
+		0,
+		0,
+		BoundedVec::truncate_from(openings)
+	));
+}
+
+#[test]
+fn shuffle_links_open_with_their_committed_leaves() {
+	new_test_ext().execute_with(|| {
+		shuffled_election();
+		let challenges = ShuffleChallenges::<Test>::get(0, 0);
+		assert_eq!(challenges.len(), 2);
+		assert!(challenges.iter().all(|c| c.side == ChallengeSide::Output && !c.answered));
+
+		// The tally waits for the shuffle proof.
+		assert_noop!(
+			DaomixVoting::submit_tally(
+				RuntimeOrigin::signed(AUTHORITY),
+				0,
+				b"ipfs://tally".to_vec(),
+				H256::repeat_byte(1)
+			),
+			Error::<Test>::ShuffleNotProven
+		);
+
+		// The leaves of another link decrypt, but are not the leaves the link commits to.
+		let challenged = challenges[0].position as usize;
+		let other = 1 - challenged;
+		let [inputs, outputs, _] = hop_leaves();
+		let mut moved = opening(challenged);
+		moved.input_leaf = inputs[SHUFFLE[other]];
+		moved.input_proof = path(&inputs, SHUFFLE[other]);
+		moved.output_leaf = outputs[other];
+		moved.output_proof = path(&outputs, other);
+		assert_noop!(
+			DaomixVoting::prove_shuffle_links(
+				RuntimeOrigin::signed(CHALLENGER),
+				0,
+				0,
+				BoundedVec::truncate_from(vec![moved])
+			),
+			Error::<Test>::InvalidLinkOpening
+		);
+
+		prove_shuffle();
+		System::assert_has_event(Event::ShuffleProven { election_id: 0 }.into());
+		assert_eq!(ShuffleStages::<Test>::get(0), Some(ShuffleStage::Proven));
+
+		assert_ok!(DaomixVoting::submit_tally(
+			RuntimeOrigin::signed(AUTHORITY),
+			0,
+			b"ipfs://tally".to_vec(),
+			H256::repeat_byte(1)
+		));
+		assert_eq!(phase(0), ElectionPhase::Disputable);
+	});
+}
+
+#[test]
+fn shuffle_links_are_only_opened_while_tallying() {
+	new_test_ext().execute_with(|| {
+		shuffled_election();
+		let position = ShuffleChallenges::<Test>::get(0, 0)[0].position as usize;
+
+		assert_ok!(DaomixVoting::cancel_election(RuntimeOrigin::signed(ADMIN), 0));
+		assert_noop!(
+			DaomixVoting::prove_shuffle_links(
+				RuntimeOrigin::signed(CHALLENGER),
+				0,
+				0,
+				BoundedVec::truncate_from(vec![opening(position)])
+			),
+			Error::<Test>::NotInTallyingPhase
+		);
+	});
+}
+
+#[test]
+fn finalized_election_releases_deposit_and_bond() {
+	new_test_ext().execute_with(|| {
+		voting_election(0, RevotePolicy::RejectRevote);
+		assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, b"ballot".to_vec()));
+
+		let root = H256::from(merkle::hash_leaf(b"ballot"));
+		assert_noop!(
+			DaomixVoting::set_mix_commitments(RuntimeOrigin::signed(AUTHORITY), 0, root, root),
+			Error::<Test>::NotInMixingPhase
+		);
+		run_to_block(VOTING_DEADLINE + 1);
+
+		assert_noop!(
+			DaomixVoting::set_mix_commitments(RuntimeOrigin::signed(ADMIN), 0, root, root),
+			Error::<Test>::NotTallyAuthority
+		);
+		assert_noop!(
+			DaomixVoting::set_mix_commitments(
+				RuntimeOrigin::signed(AUTHORITY),
+				0,
+				H256::zero(),
+				root
+			),
+			Error::<Test>::InputRootMismatch
+		);
+		assert_ok!(DaomixVoting::set_mix_commitments(
+			RuntimeOrigin::signed(AUTHORITY),
+			0,
+			root,
+			root
+		));
+		assert_eq!(phase(0), ElectionPhase::Tallying);
+
+		let now = System::block_number();
+		assert_ok!(DaomixVoting::submit_tally(
+			RuntimeOrigin::signed(AUTHORITY),
+			0,
+			b"ipfs://tally".to_vec(),
+			H256::repeat_byte(1)
+		));
+		assert_eq!(phase(0), ElectionPhase::Disputable);
+		assert_eq!(held(HoldReason::TallyBond, AUTHORITY), TALLY_BOND);
+		assert_eq!(
+			DaomixVoting::election_status(0).unwrap().challenge_deadline,
+			Some(now + CHALLENGE_PERIOD)
+		);
+
+		run_to_block(now + CHALLENGE_PERIOD + 1);
+		assert_eq!(phase(0), ElectionPhase::Finalized);
+		assert_eq!(held(HoldReason::TallyBond, AUTHORITY), 0);
+		assert_eq!(held(HoldReason::ElectionDeposit, ADMIN), 0);
+		assert_eq!(Balances::free_balance(ADMIN), INITIAL_BALANCE);
+		assert_eq!(TallyResults::<Test>::get(0).unwrap().result_hash, H256::repeat_byte(1));
+		assert_noop!(
+			DaomixVoting::cancel_election(RuntimeOrigin::signed(ADMIN), 0),
+			Error::<Test>::ElectionClosed
+		);
+	});
+}
+
+/// Move election 0, with a ballot cast and no mix cascade, to `Tallying`.
+fn tallying_election() {
+	voting_election(0, RevotePolicy::RejectRevote);
+	assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, b"ballot".to_vec()));
+	run_to_block(VOTING_DEADLINE + 1);
+
+	let root = H256::from(InputRoots::<Test>::get(0).unwrap());
+	assert_ok!(DaomixVoting::set_mix_commitments(RuntimeOrigin::signed(AUTHORITY), 0, root, root));
+}
+
+/// Submit a tally of election 0, returning the block it was submitted in.
+fn submit_tally() -> u64 {
+	assert_ok!(DaomixVoting::submit_tally(
+		RuntimeOrigin::signed(AUTHORITY),
+		0,
+		b"ipfs://tally".to_vec(),
+		H256::repeat_byte(1)
+	));
+	System::block_number()
+}
+
+#[test]
+fn rejected_dispute_pays_its_bond_to_the_tally_authority() {
+	new_test_ext().execute_with(|| {
+		tallying_election();
+		let submitted = submit_tally();
+		assert_noop!(
+			DaomixVoting::dispute_tally(
+				RuntimeOrigin::signed(CHALLENGER),
+				0,
+				DisputeEvidence::MissingBallot { index: 1 }
+			),
+			Error::<Test>::InvalidEvidence
+		);
+		assert_ok!(DaomixVoting::dispute_tally(
+			RuntimeOrigin::signed(CHALLENGER),
+			0,
+			DisputeEvidence::MissingBallot { index: 0 }
+		));
+		assert_eq!(held(HoldReason::DisputeBond, CHALLENGER), DISPUTE_BOND);
+
+		// An open dispute keeps the election from being finalized.
+		run_to_block(submitted + CHALLENGE_PERIOD + 1);
+		assert_eq!(phase(0), ElectionPhase::Disputable);
+		assert_noop!(
+			DaomixVoting::dispute_tally(
+				RuntimeOrigin::signed(VOTER),
+				0,
+				DisputeEvidence::MissingBallot { index: 0 }
+			),
+			Error::<Test>::ChallengePeriodOver
+		);
+
+		assert_noop!(DaomixVoting::reject_dispute(RuntimeOrigin::signed(ADMIN), 0, 0), BadOrigin);
+		assert_ok!(DaomixVoting::reject_dispute(RuntimeOrigin::root(), 0, 0));
+		assert_eq!(phase(0), ElectionPhase::Finalized);
+		assert_eq!(held(HoldReason::DisputeBond, CHALLENGER), 0);
+		assert_eq!(held(HoldReason::TallyBond, AUTHORITY), 0);
+		assert_eq!(Balances::free_balance(CHALLENGER), INITIAL_BALANCE - DISPUTE_BOND);
+		assert_eq!(Balances::free_balance(AUTHORITY), INITIAL_BALANCE + DISPUTE_BOND);
+	});
+}
+
+#[test]
+fn upheld_dispute_discards_the_tally_and_what_it_was_accepted_on() {
+	new_test_ext().execute_with(|| {
+		shuffled_election();
+		prove_shuffle();
+		// What a committee or homomorphic tally would have left.
+		TallyAttestations::<Test>::insert(0, AUTHORITY, H256::repeat_byte(1));
+		DecryptedCounts::<Test>::insert(0, BoundedVec::truncate_from(vec![1, 1]));
+		submit_tally();
+
+		let evidence_uri = BoundedVec::truncate_from(b"ipfs://decryption".to_vec());
+		let evidence = DisputeEvidence::InvalidDecryption { leaf: [1; 32], evidence_uri };
+		for challenger in [CHALLENGER, VOTER] {
+			assert_ok!(DaomixVoting::dispute_tally(
+				RuntimeOrigin::signed(challenger),
+				0,
+				evidence.clone()
+			));
+		}
+
+		assert_ok!(DaomixVoting::uphold_dispute(RuntimeOrigin::root(), 0, 0));
+		assert_eq!(phase(0), ElectionPhase::Tallying);
+		assert_eq!(TallyResults::<Test>::get(0), None);
+
+		// The tally bond goes to the challenger and every dispute bond is released.
+		assert_eq!(held(HoldReason::TallyBond, AUTHORITY), 0);
+		assert_eq!(Balances::free_balance(AUTHORITY), INITIAL_BALANCE - TALLY_BOND);
+		assert_eq!(Balances::free_balance(CHALLENGER), INITIAL_BALANCE + TALLY_BOND);
+		assert_eq!(held(HoldReason::DisputeBond, VOTER), 0);
+		assert_eq!(Balances::free_balance(VOTER), INITIAL_BALANCE);
+
+		// The mix commitments stand, but the shuffle is proven again before the next tally.
+		assert!(Elections::<Test>::get(0).unwrap().commitment_output_root.is_some());
+		assert_eq!(TallyAttestations::<Test>::iter_prefix(0).count(), 0);
+		assert_eq!(DecryptedCounts::<Test>::get(0), None);
+		assert_eq!(ShuffleStages::<Test>::get(0), None);
+		assert_eq!(ShuffleHops::<Test>::get(0, 0), None);
+		assert!(ShuffleChallenges::<Test>::get(0, 0).is_empty());
+		assert_noop!(
+			DaomixVoting::submit_tally(
+				RuntimeOrigin::signed(AUTHORITY),
+				0,
+				b"ipfs://tally".to_vec(),
+				H256::repeat_byte(1)
+			),
+			Error::<Test>::ShuffleNotProven
+		);
+	});
+}
+
+#[test]
+fn upheld_commitment_dispute_returns_to_mixing() {
+	new_test_ext().execute_with(|| {
+		tallying_election();
+		submit_tally();
+		assert_ok!(DaomixVoting::dispute_tally(
+			RuntimeOrigin::signed(CHALLENGER),
+			0,
+			DisputeEvidence::RootMismatch { expected: [1; 32], committed: [2; 32] }
+		));
+
+		assert_ok!(DaomixVoting::uphold_dispute(RuntimeOrigin::root(), 0, 0));
+		assert_eq!(phase(0), ElectionPhase::Mixing);
+		let election = Elections::<Test>::get(0).unwrap();
+		assert_eq!((election.commitment_input_root, election.commitment_output_root), (None, None));
+
+		// The mix is redone from new commitments.
+		let root = H256::from(InputRoots::<Test>::get(0).unwrap());
+		assert_ok!(DaomixVoting::set_mix_commitments(
+			RuntimeOrigin::signed(AUTHORITY),
+			0,
+			root,
+			H256::repeat_byte(3)
+		));
+		assert_eq!(phase(0), ElectionPhase::Tallying);
+	});
+}
+
+#[test]
+fn committee_tally_needs_threshold_attestations() {
+	new_test_ext().execute_with(|| {
+		// Registration outlasts the three DKG rounds started at block 1.
+		let registration_deadline = 12;
+		assert_ok!(DaomixVoting::create_election(
+			RuntimeOrigin::signed(ADMIN),
+			0,
+			AUTHORITY,
+			registration_deadline,
+			registration_deadline + 3,
+			RevotePolicy::RejectRevote,
+			TallyMode::Mixnet,
+		));
+		assert_ok!(DaomixVoting::set_election_keys(
+			RuntimeOrigin::signed(ADMIN),
+			0,
+			vec![[1; 32]],
+			None,
+			[9; 32]
+		));
+		for voter in [VOTER, OTHER_VOTER] {
+			assert_ok!(DaomixVoting::register_voter(RuntimeOrigin::signed(ADMIN), 0, voter));
+		}
+
+		assert_noop!(
+			DaomixVoting::set_tally_committee(
+				RuntimeOrigin::signed(ADMIN),
+				0,
+				vec![(CHALLENGER, [0; 32]); 2],
+				1
+			),
+			Error::<Test>::InvalidCommittee
+		);
+		assert_ok!(DaomixVoting::set_tally_committee(
+			RuntimeOrigin::signed(ADMIN),
+			0,
+			vec![(AUTHORITY, [0; 32]), (CHALLENGER, [0; 32])],
+			1
+		));
+
+		// A single dealer of the 1-of-2 committee deals the polynomial `f(x) = 1`.
+		let public_key = RISTRETTO_BASEPOINT_COMPRESSED.to_bytes();
+		assert_noop!(
+			DaomixVoting::dkg_deal(
+				RuntimeOrigin::signed(OTHER_VOTER),
+				0,
+				vec![public_key],
+				vec![vec![0; 32]; 2]
+			),
+			Error::<Test>::NotTrustee
+		);
+		assert_ok!(DaomixVoting::dkg_deal(
+			RuntimeOrigin::signed(AUTHORITY),
+			0,
+			vec![public_key],
+			vec![vec![0; 32]; 2]
+		));
+
+		// Its share for the second trustee is disputed and revealed.
+		run_to_block(5);
+		assert_ok!(DaomixVoting::dkg_complain(RuntimeOrigin::signed(CHALLENGER), 0, AUTHORITY));
+		let mut share = [0; 32];
+		share[0] = 2;
+		assert_noop!(
+			DaomixVoting::dkg_answer_complaint(
+				RuntimeOrigin::signed(AUTHORITY),
+				0,
+				CHALLENGER,
+				share
+			),
+			Error::<Test>::InvalidShare
+		);
+		share[0] = 1;
+		assert_ok!(DaomixVoting::dkg_answer_complaint(
+			RuntimeOrigin::signed(AUTHORITY),
+			0,
+			CHALLENGER,
+			share
+		));
+
+		assert_noop!(
+			DaomixVoting::finish_dkg(RuntimeOrigin::signed(CHALLENGER), 0),
+			Error::<Test>::DkgInProgress
+		);
+		run_to_block(11);
+		assert_ok!(DaomixVoting::finish_dkg(RuntimeOrigin::signed(CHALLENGER), 0));
+		System::assert_has_event(Event::DkgCompleted { election_id: 0, public_key }.into());
+
+		run_to_block(registration_deadline + 1);
+		for (voter, ballot) in [VOTER, OTHER_VOTER].into_iter().zip(SHUFFLED_BALLOTS) {
+			assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(voter), 0, ballot.to_vec()));
+		}
+		run_to_block(registration_deadline + 4);
+		shuffle_ballots();
+		prove_shuffle();
+
+		// The tally is only taken once a trustee attested to it.
+		let result_hash = H256::repeat_byte(1);
+		assert_noop!(
+			DaomixVoting::submit_tally(
+				RuntimeOrigin::signed(AUTHORITY),
+				0,
+				b"ipfs://tally".to_vec(),
+				result_hash
+			),
+			Error::<Test>::NotEnoughAttestations
+		);
+		assert_noop!(
+			DaomixVoting::attest_tally(RuntimeOrigin::signed(OTHER_VOTER), 0, result_hash),
+			Error::<Test>::NotTrustee
+		);
+		assert_ok!(DaomixVoting::attest_tally(RuntimeOrigin::signed(CHALLENGER), 0, result_hash));
+		assert_ok!(DaomixVoting::submit_tally(
+			RuntimeOrigin::signed(AUTHORITY),
+			0,
+			b"ipfs://tally".to_vec(),
+			result_hash
+		));
+		assert_eq!(phase(0), ElectionPhase::Disputable);
+	});
+}
+
+#[test]
+fn committee_key_becomes_the_tally_key() {
+	new_test_ext().execute_with(|| {
+		// Registration outlasts the three DKG rounds started at block 1.
+		let registration_deadline = 12;
+		assert_ok!(DaomixVoting::create_election(
+			RuntimeOrigin::signed(ADMIN),
+			0,
+			AUTHORITY,
+			registration_deadline,
+			registration_deadline + 3,
+			RevotePolicy::RejectRevote,
+			TallyMode::Mixnet,
+		));
+		assert_ok!(DaomixVoting::set_election_keys(
+			RuntimeOrigin::signed(ADMIN),
+			0,
+			vec![[1; 32]],
+			None,
+			[9; 32]
+		));
+		for voter in [VOTER, OTHER_VOTER] {
+			assert_ok!(DaomixVoting::register_voter(RuntimeOrigin::signed(ADMIN), 0, voter));
+		}
+
+		// Trustees are bounded before they are checked for duplicates.
+		assert_noop!(
+			DaomixVoting::set_tally_committee(
+				RuntimeOrigin::signed(ADMIN),
+				0,
+				vec![(CHALLENGER, [0; 32]); 5],
+				1
+			),
+			Error::<Test>::TooManyTrustees
+		);
+		assert_ok!(DaomixVoting::set_tally_committee(
+			RuntimeOrigin::signed(ADMIN),
+			0,
+			vec![(AUTHORITY, [0; 32]), (CHALLENGER, [0; 32])],
+			1
+		));
+
+		// A single dealer of the 1-of-2 committee deals the polynomial `f(x) = 1`.
+		let public_key = RISTRETTO_BASEPOINT_COMPRESSED.to_bytes();
+		assert_ok!(DaomixVoting::dkg_deal(
+			RuntimeOrigin::signed(AUTHORITY),
+			0,
+			vec![public_key],
+			vec![vec![0; 32]; 2]
+		));
+		run_to_block(11);
+		assert_ok!(DaomixVoting::finish_dkg(RuntimeOrigin::signed(CHALLENGER), 0));
+		assert_eq!(DaomixVoting::election_keys(0).unwrap().tally_key, public_key);
+
+		assert_noop!(
+			DaomixVoting::set_election_keys(
+				RuntimeOrigin::signed(ADMIN),
+				0,
+				vec![[1; 32]],
+				None,
+				[9; 32]
+			),
+			Error::<Test>::TallyKeyNotCommitteeKey
+		);
+
+		// Ballots and attestations are only taken for ballots sealed to the committee key.
+		run_to_block(registration_deadline + 1);
+		assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, b"ballot".to_vec()));
+		let set_tally_key = |tally_key| {
+			Elections::<Test>::mutate(0, |election| {
+				election.as_mut().unwrap().keys.as_mut().unwrap().tally_key = tally_key
+			})
+		};
+		set_tally_key([9; 32]);
+		assert_noop!(
+			DaomixVoting::cast_vote(RuntimeOrigin::signed(OTHER_VOTER), 0, b"ballot".to_vec()),
+			Error::<Test>::TallyKeyNotCommitteeKey
+		);
+
+		run_to_block(registration_deadline + 4);
+		let root = H256::from(InputRoots::<Test>::get(0).unwrap());
+		assert_ok!(DaomixVoting::set_mix_commitments(
+			RuntimeOrigin::signed(AUTHORITY),
+			0,
+			root,
+			root
+		));
+		assert_noop!(
+			DaomixVoting::attest_tally(RuntimeOrigin::signed(AUTHORITY), 0, H256::repeat_byte(1)),
+			Error::<Test>::TallyKeyNotCommitteeKey
+		);
+		set_tally_key(public_key);
+		assert_ok!(DaomixVoting::attest_tally(
+			RuntimeOrigin::signed(AUTHORITY),
+			0,
+			H256::repeat_byte(1)
+		));
+	});
+}
+
+/// `count * G`, compressed.
+fn times_g(count: u64) -> [u8; 32] {
+	(Scalar::from(count) * RISTRETTO_BASEPOINT_POINT).compress().to_bytes()
+}
+
+#[test]
+fn homomorphic_ballots_are_only_counted_as_a_sum() {
+	new_test_ext().execute_with(|| {
+		// Registration outlasts the three DKG rounds started at block 1.
+		let registration_deadline = 12;
+		let create = |election_id, tally_mode| {
+			DaomixVoting::create_election(
+				RuntimeOrigin::signed(ADMIN),
+				election_id,
+				AUTHORITY,
+				registration_deadline,
+				registration_deadline + 3,
+				RevotePolicy::RejectRevote,
+				tally_mode,
+			)
+		};
+		assert_noop!(
+			create(0, TallyMode::Homomorphic { options: 1 }),
+			Error::<Test>::InvalidTallyMode
+		);
+
+		// The 1-of-1 committee of each election deals the polynomial `f(x) = 1`, so its key is
+		// `G`.
+		for election_id in [0, 1] {
+			assert_ok!(create(election_id, TallyMode::Homomorphic { options: 2 }));
+			for voter in [VOTER, OTHER_VOTER] {
+				assert_ok!(DaomixVoting::register_voter(
+					RuntimeOrigin::signed(ADMIN),
+					election_id,
+					voter
+				));
+			}
+			assert_ok!(DaomixVoting::set_tally_committee(
+				RuntimeOrigin::signed(ADMIN),
+				election_id,
+				vec![(AUTHORITY, [0; 32])],
+				1
+			));
+			assert_ok!(DaomixVoting::dkg_deal(
+				RuntimeOrigin::signed(AUTHORITY),
+				election_id,
+				vec![times_g(1)],
+				vec![vec![0; 32]]
+			));
+		}
+		run_to_block(11);
+		for election_id in [0, 1] {
+			assert_ok!(DaomixVoting::finish_dkg(RuntimeOrigin::signed(CHALLENGER), election_id));
+		}
+
+		run_to_block(registration_deadline + 1);
+		assert_noop!(
+			DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, b"ballot".to_vec()),
+			Error::<Test>::InvalidBallot
+		);
+		let context = |voter| DaomixVoting::ballot_context(0, &BallotSender::Account(voter));
+		let ballots = [(VOTER, 0), (OTHER_VOTER, 1)].map(|(voter, choice)| {
+			(voter, elgamal::prove_ballot(&times_g(1), &context(voter), 2, choice, &voter.encode()))
+		});
+		let forged = elgamal::prove_ballot(&times_g(2), &context(VOTER), 2, 0, b"seed").unwrap();
+		assert_noop!(
+			DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, forged.encode()),
+			Error::<Test>::InvalidBallotProof
+		);
+		// A ballot proven for another voter is not taken either.
+		let copied = ballots[0].1.as_ref().unwrap().encode();
+		assert_noop!(
+			DaomixVoting::cast_vote(RuntimeOrigin::signed(OTHER_VOTER), 0, copied),
+			Error::<Test>::InvalidBallotProof
+		);
+		for (voter, ballot) in &ballots {
+			let ballot = ballot.as_ref().unwrap().encode();
+			assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(*voter), 0, ballot));
+		}
+		let [first, second] = ballots.map(|(_, ballot)| ballot.unwrap().ciphertexts);
+		let sum = elgamal::accumulate(&first, &second, false).unwrap();
+		assert_eq!(EncryptedTallies::<Test>::get(0).unwrap().into_inner(), sum);
+
+		// Homomorphic ballots are tallied without mixing, and only from checked counts.
+		run_to_block(registration_deadline + 4);
+		assert_eq!(phase(0), ElectionPhase::Tallying);
+		assert_noop!(
+			DaomixVoting::submit_tally(
+				RuntimeOrigin::signed(AUTHORITY),
+				0,
+				b"ipfs://tally".to_vec(),
+				H256::repeat_byte(1)
+			),
+			Error::<Test>::CountsNotVerified
+		);
+		assert_noop!(
+			DaomixVoting::submit_decrypted_counts(RuntimeOrigin::signed(AUTHORITY), 0, vec![1, 1]),
+			Error::<Test>::NotEnoughPartialDecryptions
+		);
+		let proof = DleqProof { challenge: [0; 32], response: [0; 32] };
+		let partial = PartialDecryption { share: sum[0].a, proof };
+		assert_noop!(
+			DaomixVoting::submit_partial_decryption(
+				RuntimeOrigin::signed(AUTHORITY),
+				0,
+				vec![partial; 2]
+			),
+			Error::<Test>::InvalidPartialDecryption
+		);
+
+		// Without ballots, every count is zero.
+		assert_eq!(phase(1), ElectionPhase::Tallying);
+		assert_noop!(
+			DaomixVoting::submit_decrypted_counts(RuntimeOrigin::signed(AUTHORITY), 1, vec![1, 0]),
+			Error::<Test>::InvalidCounts
+		);
+		assert_ok!(DaomixVoting::submit_decrypted_counts(
+			RuntimeOrigin::signed(AUTHORITY),
+			1,
+			vec![0, 0]
+		));
+		let counts = DecryptedCounts::<Test>::get(1).unwrap();
+		assert_noop!(
+			DaomixVoting::submit_tally(
+				RuntimeOrigin::signed(AUTHORITY),
+				1,
+				b"ipfs://tally".to_vec(),
+				H256::repeat_byte(1)
+			),
+			Error::<Test>::ResultHashMismatch
+		);
+		assert_ok!(DaomixVoting::submit_tally(
+			RuntimeOrigin::signed(AUTHORITY),
+			1,
+			b"ipfs://tally".to_vec(),
+			BlakeTwo256::hash_of(&counts)
+		));
+		assert_eq!(phase(1), ElectionPhase::Disputable);
+	});
+}
+
+/// Allowlist root of the elections created by `anonymous_election`.
+const ALLOWLIST_ROOT: [u8; 32] = [1; 32];
+
+/// Create anonymous election `election_id` committed to `ALLOWLIST_ROOT`, fund its pot for
+/// `feeless_ballots` ballots and move it to `Voting`.
+fn anonymous_election(election_id: u32, revote_policy: RevotePolicy, feeless_ballots: u64) {
+	create(election_id, revote_policy);
+	assert_ok!(DaomixVoting::set_voter_allowlist(
+		RuntimeOrigin::signed(ADMIN),
+		election_id,
+		ALLOWLIST_ROOT
+	));
+	if feeless_ballots > 0 {
+		assert_ok!(DaomixVoting::fund_election(
+			RuntimeOrigin::signed(ADMIN),
+			election_id,
+			feeless_ballots * FEELESS_BALLOT_FEE
+		));
+	}
+	run_to_block(REGISTRATION_DEADLINE + 1);
+}
+
+/// Anonymous ballot of `nullifier` in election 0 against `root`.
+fn anonymous_ballot(root: [u8; 32], nullifier: [u8; 32], proof: &[u8]) -> Call<Test> {
+	Call::cast_anonymous_vote {
+		election_id: 0,
+		root,
+		nullifier,
+		proof: proof.to_vec(),
+		ciphertext: b"ballot".to_vec(),
+	}
+}
+
+fn validate(call: &Call<Test>) -> TransactionValidity {
+	<DaomixVoting as ValidateUnsigned>::validate_unsigned(TransactionSource::External, call)
+}
+
+fn pot_balance(election_id: u32) -> u64 {
+	Balances::free_balance(DaomixVoting::pot_account(election_id))
+}
+
+#[test]
+fn voter_allowlist_makes_the_election_anonymous() {
+	new_test_ext().execute_with(|| {
+		create(0, RevotePolicy::RejectRevote);
+		assert_noop!(
+			DaomixVoting::set_voter_allowlist(RuntimeOrigin::signed(VOTER), 0, ALLOWLIST_ROOT),
+			Error::<Test>::NotAdmin
+		);
+		assert_ok!(DaomixVoting::set_voter_allowlist(
+			RuntimeOrigin::signed(ADMIN),
+			0,
+			ALLOWLIST_ROOT
+		));
+		System::assert_last_event(
+			Event::VoterAllowlistSet { election_id: 0, root: ALLOWLIST_ROOT }.into(),
+		);
+		assert_noop!(
+			DaomixVoting::register_voter(RuntimeOrigin::signed(ADMIN), 0, VOTER),
+			Error::<Test>::AnonymousElection
+		);
+
+		run_to_block(REGISTRATION_DEADLINE + 1);
+		assert_noop!(
+			DaomixVoting::set_voter_allowlist(RuntimeOrigin::signed(ADMIN), 0, [2; 32]),
+			Error::<Test>::NotInRegistrationPhase
+		);
+		assert_noop!(
+			DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, b"ballot".to_vec()),
+			Error::<Test>::AnonymousElection
+		);
+	});
+}
+
+#[test]
+fn anonymous_ballots_need_the_allowlist_root_and_a_membership_proof() {
+	new_test_ext().execute_with(|| {
+		anonymous_election(0, RevotePolicy::RejectRevote, 0);
+		let cast = |root, proof: &[u8]| {
+			DaomixVoting::cast_anonymous_vote(
+				RuntimeOrigin::signed(VOTER),
+				0,
+				root,
+				[2; 32],
+				proof.to_vec(),
+				b"ballot".to_vec(),
+			)
+		};
+
+		assert_noop!(cast([9; 32], VALID_PROOF), Error::<Test>::AllowlistRootMismatch);
+		assert_noop!(cast(ALLOWLIST_ROOT, b"forged"), Error::<Test>::InvalidMembershipProof);
+
+		assert_ok!(cast(ALLOWLIST_ROOT, VALID_PROOF));
+		System::assert_last_event(
+			Event::AnonymousBallotCast {
+				election_id: 0,
+				nullifier: [2; 32],
+				index: 0,
+				ciphertext_hash: merkle::hash_leaf(b"ballot"),
+				replaced: None,
+			}
+			.into(),
+		);
+		assert_eq!(Nullifiers::<Test>::get(0, [2; 32]), Some(0));
+		assert_noop!(cast(ALLOWLIST_ROOT, VALID_PROOF), Error::<Test>::AlreadyVoted);
+
+		// Signed anonymous ballots pay their own fee
+		assert_eq!(FeelessBallots::<Test>::get(0, BallotSender::Nullifier([2; 32])), 0);
+	});
+}
+
+#[test]
+fn unsigned_anonymous_ballots_are_checked_before_their_proofs() {
+	new_test_ext().execute_with(|| {
+		anonymous_election(0, RevotePolicy::RejectRevote, 2);
+
+		// A ballot against another root is turned away without verifying its proof
+		assert_eq!(
+			validate(&anonymous_ballot([9; 32], [2; 32], VALID_PROOF)),
+			Err(InvalidTransaction::BadProof.into())
+		);
+		assert_eq!(MembershipChecks::get(), 0);
+
+		assert_eq!(
+			validate(&anonymous_ballot(ALLOWLIST_ROOT, [2; 32], b"forged")),
+			Err(InvalidTransaction::BadProof.into())
+		);
+		assert_eq!(MembershipChecks::get(), 1);
+
+		let valid = validate(&anonymous_ballot(ALLOWLIST_ROOT, [2; 32], VALID_PROOF)).unwrap();
+		assert_eq!(valid.priority, 100);
+		assert_eq!(valid.longevity, UNSIGNED_LONGEVITY);
+		assert_eq!(MembershipChecks::get(), 2);
+
+		assert_ok!(DaomixVoting::cast_anonymous_vote(
+			RuntimeOrigin::none(),
+			0,
+			ALLOWLIST_ROOT,
+			[2; 32],
+			VALID_PROOF.to_vec(),
+			b"ballot".to_vec(),
+		));
+		assert_eq!(pot_balance(0), FEELESS_BALLOT_FEE);
+		assert_eq!(FeelessBallots::<Test>::get(0, BallotSender::Nullifier([2; 32])), 1);
+
+		// A used nullifier is turned away without verifying its proof
+		MembershipChecks::set(0);
+		assert_eq!(
+			validate(&anonymous_ballot(ALLOWLIST_ROOT, [2; 32], VALID_PROOF)),
+			Err(InvalidTransaction::Stale.into())
+		);
+		assert_eq!(MembershipChecks::get(), 0);
+
+		// Ballots are not pooled past the voting deadline
+		run_to_block(VOTING_DEADLINE);
+		let valid = validate(&anonymous_ballot(ALLOWLIST_ROOT, [3; 32], VALID_PROOF)).unwrap();
+		assert_eq!(valid.longevity, 1);
+	});
+}
+
+#[test]
+fn unsigned_anonymous_ballots_are_cast_with_membership_circuit_proofs() {
+	use crate::groth16::circuit_proof::{CIPHERTEXT, ELECTION_ID, NULLIFIER, PROOF, ROOT};
+	new_test_ext().execute_with(|| {
+		create(ELECTION_ID, RevotePolicy::RejectRevote);
+		assert_ok!(DaomixVoting::set_voter_allowlist(
+			RuntimeOrigin::signed(ADMIN),
+			ELECTION_ID,
+			ROOT
+		));
+		assert_ok!(DaomixVoting::fund_election(
+			RuntimeOrigin::signed(ADMIN),
+			ELECTION_ID,
+			2 * FEELESS_BALLOT_FEE
+		));
+		run_to_block(REGISTRATION_DEADLINE + 1);
+
+		// The proof holds for its own nullifier only
+		assert_eq!(
+			validate(&anonymous_ballot(ROOT, [2; 32], &PROOF)),
+			Err(InvalidTransaction::BadProof.into())
+		);
+		let mut tampered = PROOF;
+		tampered[100] ^= 1;
+		assert_eq!(
+			validate(&anonymous_ballot(ROOT, NULLIFIER, &tampered)),
+			Err(InvalidTransaction::BadProof.into())
+		);
+
+		assert_ok!(validate(&anonymous_ballot(ROOT, NULLIFIER, &PROOF)));
+		assert_ok!(DaomixVoting::cast_anonymous_vote(
+			RuntimeOrigin::none(),
+			ELECTION_ID,
+			ROOT,
+			NULLIFIER,
+			PROOF.to_vec(),
+			CIPHERTEXT.to_vec(),
+		));
+		assert_eq!(Nullifiers::<Test>::get(ELECTION_ID, NULLIFIER), Some(0));
+		assert_eq!(pot_balance(ELECTION_ID), FEELESS_BALLOT_FEE);
+	});
+}
+
+#[test]
+fn unsigned_anonymous_ballots_need_a_funded_pot() {
+	new_test_ext().execute_with(|| {
+		anonymous_election(0, RevotePolicy::RejectRevote, 0);
+
+		assert_eq!(
+			validate(&anonymous_ballot(ALLOWLIST_ROOT, [2; 32], VALID_PROOF)),
+			Err(InvalidTransaction::Payment.into())
+		);
+		assert_eq!(MembershipChecks::get(), 0);
+		assert_noop!(
+			DaomixVoting::cast_anonymous_vote(
+				RuntimeOrigin::none(),
+				0,
+				ALLOWLIST_ROOT,
+				[2; 32],
+				VALID_PROOF.to_vec(),
+				b"ballot".to_vec(),
+			),
+			TokenError::FundsUnavailable
+		);
+	});
+}
+
+#[test]
+fn pot_pays_for_feeless_ballots_up_to_the_quota() {
+	new_test_ext().execute_with(|| {
+		voting_election(0, RevotePolicy::LastVoteWins);
+		assert_ok!(DaomixVoting::fund_election(
+			RuntimeOrigin::signed(ADMIN),
+			0,
+			3 * FEELESS_BALLOT_FEE
+		));
+		let cast = |voter| {
+			assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(voter), 0, b"ballot".to_vec()))
+		};
+		let feeless_ballots = |voter| FeelessBallots::<Test>::get(0, BallotSender::Voter(voter));
+
+		for _ in 0..MAX_FEELESS_BALLOTS {
+			assert!(DaomixVoting::sponsors_vote(&RuntimeOrigin::signed(VOTER), 0));
+			cast(VOTER);
+		}
+		assert_eq!(feeless_ballots(VOTER), MAX_FEELESS_BALLOTS);
+		assert_eq!(pot_balance(0), FEELESS_BALLOT_FEE);
+
+		// Past its quota the voter pays for their own ballots
+		assert!(!DaomixVoting::sponsors_vote(&RuntimeOrigin::signed(VOTER), 0));
+		cast(VOTER);
+		assert_eq!(feeless_ballots(VOTER), MAX_FEELESS_BALLOTS);
+		assert_eq!(pot_balance(0), FEELESS_BALLOT_FEE);
+
+		assert!(DaomixVoting::sponsors_vote(&RuntimeOrigin::signed(OTHER_VOTER), 0));
+		cast(OTHER_VOTER);
+		assert_eq!(feeless_ballots(OTHER_VOTER), 1);
+		assert_eq!(pot_balance(0), 0);
+
+		// So does everyone once the pot is exhausted
+		assert!(!DaomixVoting::sponsors_vote(&RuntimeOrigin::signed(OTHER_VOTER), 0));
+		cast(OTHER_VOTER);
+		assert_eq!(feeless_ballots(OTHER_VOTER), 1);
+		assert_eq!(pot_balance(0), 0);
+	});
+}
+
+#[test]
+fn rejected_feeless_ballots_are_not_refunded() {
+	new_test_ext().execute_with(|| {
+		// Registration outlasts the three DKG rounds started at block 1.
+		let registration_deadline = 12;
+		assert_ok!(DaomixVoting::create_election(
+			RuntimeOrigin::signed(ADMIN),
+			0,
+			AUTHORITY,
+			registration_deadline,
+			registration_deadline + 3,
+			RevotePolicy::RejectRevote,
+			TallyMode::Homomorphic { options: 2 },
+		));
+		assert_ok!(DaomixVoting::set_tally_committee(
+			RuntimeOrigin::signed(ADMIN),
+			0,
+			vec![(AUTHORITY, [0; 32]), (CHALLENGER, [0; 32])],
+			1
+		));
+		assert_ok!(DaomixVoting::dkg_deal(
+			RuntimeOrigin::signed(AUTHORITY),
+			0,
+			vec![RISTRETTO_BASEPOINT_COMPRESSED.to_bytes()],
+			vec![vec![0; 32]; 2]
+		));
+		run_to_block(11);
+		assert_ok!(DaomixVoting::finish_dkg(RuntimeOrigin::signed(CHALLENGER), 0));
+		assert_ok!(DaomixVoting::register_voter(RuntimeOrigin::signed(ADMIN), 0, VOTER));
+		assert_ok!(DaomixVoting::fund_election(
+			RuntimeOrigin::signed(ADMIN),
+			0,
+			FEELESS_BALLOT_FEE
+		));
+		run_to_block(registration_deadline + 1);
+
+		// The pot paid for the ballot, so the call succeeds and keeps the charge
+		assert!(DaomixVoting::sponsors_vote(&RuntimeOrigin::signed(VOTER), 0));
+		assert_ok!(DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, b"garbage".to_vec()));
+		System::assert_last_event(
+			Event::FeelessBallotRejected {
+				election_id: 0,
+				sender: BallotSender::Voter(VOTER),
+				error: Error::<Test>::InvalidBallot.into(),
+			}
+			.into(),
+		);
+		assert_eq!(pot_balance(0), 0);
+		assert_eq!(FeelessBallots::<Test>::get(0, BallotSender::Voter(VOTER)), 1);
+		assert_eq!(BallotCount::<Test>::get(0), 0);
+		assert_eq!(VoterBallot::<Test>::get(0, VOTER), None);
+
+		// A ballot the voter pays for fails as usual
+		assert!(!DaomixVoting::sponsors_vote(&RuntimeOrigin::signed(VOTER), 0));
+		assert_noop!(
+			DaomixVoting::cast_vote(RuntimeOrigin::signed(VOTER), 0, b"garbage".to_vec()),
+			Error::<Test>::InvalidBallot
+		);
+	});
+}
+
+/// An election of storage version 0.
+#[derive(Encode)]
+struct ElectionV0 {
+	admin: u64,
+	tally_authority: u64,
+	registration_deadline: u64,
+	voting_deadline: u64,
+	commitment_input_root: Option<H256>,
+	commitment_output_root: Option<H256>,
+	finalized: bool,
+}
+
+#[test]
+fn migration_to_v1_derives_phases() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+		let v0 = |registration_deadline, voting_deadline, committed, finalized| ElectionV0 {
+			admin: ADMIN,
+			tally_authority: AUTHORITY,
+			registration_deadline,
+			voting_deadline,
+			commitment_input_root: committed.then(H256::zero),
+			commitment_output_root: committed.then(H256::zero),
+			finalized,
+		};
+		let elections = [
+			(0, v0(12, 20, false, false)),
+			(1, v0(5, 20, false, false)),
+			(2, v0(5, 8, false, false)),
+			(3, v0(5, 8, true, false)),
+			(4, v0(5, 8, true, true)),
+		];
+		for (election_id, election) in elections {
+			unhashed::put(&Elections::<Test>::hashed_key_for(election_id), &election);
+		}
+		BallotCount::<Test>::insert(1, 3);
+		unhashed::put(
+			&TallyResults::<Test>::hashed_key_for(4),
+			&(BoundedVec::<u8, ConstU32<256>>::truncate_from(b"uri".to_vec()), H256::zero()),
+		);
+
+		migrations::InnerMigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+		let phases = (0..5).map(phase).collect::<Vec<_>>();
+		assert_eq!(
+			phases,
+			vec![
+				ElectionPhase::Registration,
+				ElectionPhase::Voting,
+				ElectionPhase::Mixing,
+				ElectionPhase::Tallying,
+				ElectionPhase::Finalized,
+			]
+		);
+		assert_eq!(Elections::<Test>::get(1).unwrap().revote_policy, RevotePolicy::RejectRevote);
+		assert_eq!(ValidBallotCount::<Test>::get(1), 3);
+		assert_eq!(TallyResults::<Test>::get(4).unwrap().challenge_deadline, 10);
+
+		// The phase checks still ahead are scheduled.
+		assert_eq!(PhaseSchedule::<Test>::get(13).into_inner(), vec![0]);
+		let mut due = PhaseSchedule::<Test>::get(21).into_inner();
+		due.sort();
+		assert_eq!(due, vec![0, 1]);
+		run_to_block(13);
+		assert_eq!(phase(0), ElectionPhase::Voting);
+		assert!(InputRoots::<Test>::contains_key(2));
+	});
+}
@@ -0,0 +1,696 @@
+//! Weights for pallet_daomix_voting.
+//!
+//! None of these figures were measured: they are hand-set upper estimates, kept in the layout of
+//! generated weights so that the output of
+//! `frame-omni-bencher v1 benchmark pallet --pallet pallet_daomix_voting` can replace this file
+//! as is, which must happen before the pallet takes real ballots. The benchmarks have not been
+//! run yet either: frame-omni-bencher needs the runtime built with `runtime-benchmarks`, which
+//! could not be done where these estimates were written.
+//!
+//! Every call has a benchmark (see `benchmarking.rs`), run on its worst case, and is charged for
+//! the largest input it can take unless the call data bounds it.
+//!
+//! Checking the validity proof of a homomorphic ballot in `cast_vote` costs one disjunctive
+//! proof per option, so its weight is linear in the options `o`. The estimate allows 450 µs per
+//! option, about ten Ristretto scalar multiplications, and covers the funding pot paying for the
+//! ballot. `cast_anonymous_vote`, benchmarked under a verifying key the benchmark can prove for
+//! (see `groth16::trapdoor`), adds a Groth16 verification run in Wasm: decoding the key and the
+//! proof, a multi-scalar multiplication and four pairings sharing one final exponentiation. The
+//! same code takes about 7 ms natively, and the estimate allows 40 ms for running it in the
+//! runtime without host calls. `hash_ballot` is the per-ballot cost of building input roots in
+//! `on_initialize`: two reads with the largest ciphertext in the proof, Keccak-256 over all of
+//! it, and storing the leaf position and tree nodes, on average two per leaf.
+//!
+//! `draw_shuffle_challenges` is linear in the hops `h` of the cascade, each pair drawing and each
+//! hop storing `ShuffleChallengesPerPair` challenges; it is charged for the longest cascade and
+//! refunded to the actual one. `prove_shuffle_links` is linear in the openings `l`: each checks
+//! three Merkle paths of up to 32 Keccak-256 hashes, estimated with the runtime's `LinkVerifier`
+//! at 400 µs, and scans the challenges of its hop.
+//!
+//! The tally committee calls are linear in the trustees `t` and the threshold `k`. Checking a
+//! commitment decompresses a Ristretto point, estimated at 25 µs, and checking a share against
+//! `k` commitments adds a scalar multiplication each, 150 µs per commitment in all.
+//! `set_tally_committee` restarts the DKG, clearing up to `MaxTrustees` deals and `MaxTrustees`
+//! squared complaints; `finish_dkg` reads and clears the `c` complaints and `t` deals and sums
+//! the `k` commitments of each qualified dealer, which the two linear terms of 200 µs cover up to
+//! `MaxTrustees` of each.
+//!
+//! `submit_partial_decryption` evaluates the trustee's verification key from `k` commitments and
+//! checks a Chaum-Pedersen proof per option `o`, about five scalar multiplications, estimated at
+//! 250 µs. `submit_decrypted_counts` combines `k` shares per option, each with a scalar
+//! multiplication and a Lagrange coefficient of `k - 1` inversions; the per-option term of 3.5 ms
+//! is set for `k` at 16, as the linear model cannot follow the square. Each count is checked with
+//! one multiplication of the generator, so the discrete-logarithm search for the counts, bounded
+//! by the number of ballots, runs off-chain and adds no component.
+//!
+//! The other calls manage elections, bonds and funds, and mostly read and write storage.
+//! `create_election` and `submit_tally` schedule phase transitions, each of which may skip
+//! `MAX_SCHEDULE_SPILL` full blocks of `PhaseSchedule`. `set_election_keys` is linear in the mix
+//! hops `h`. `submit_tally` reads the attestations of up to `t` trustees, and `cancel_election`
+//! and `uphold_dispute` release the bonds of `d` open disputes, charged for `MaxDisputes`;
+//! `uphold_dispute` also clears the attestations and the shuffle proof of every hop.
+//! `reject_dispute` is charged for finalizing the election.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame::{deps::frame_support::weights::constants::RocksDbWeight, prelude::*};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_daomix_voting.
+pub trait WeightInfo {
+	fn cast_vote(o: u32, ) -> Weight;
+	fn cast_anonymous_vote(o: u32, ) -> Weight;
+	fn hash_ballot() -> Weight;
+	fn commit_shuffle_hop() -> Weight;
+	fn draw_shuffle_challenges(h: u32, ) -> Weight;
+	fn prove_shuffle_links(l: u32, ) -> Weight;
+	fn set_tally_committee(t: u32, ) -> Weight;
+	fn dkg_deal(t: u32, k: u32, ) -> Weight;
+	fn dkg_complain(t: u32, ) -> Weight;
+	fn dkg_answer_complaint(t: u32, k: u32, ) -> Weight;
+	fn finish_dkg(t: u32, k: u32, c: u32, ) -> Weight;
+	fn attest_tally(t: u32, ) -> Weight;
+	fn submit_partial_decryption(o: u32, k: u32, ) -> Weight;
+	fn submit_decrypted_counts(o: u32, k: u32, ) -> Weight;
+	fn create_election() -> Weight;
+	fn register_voter() -> Weight;
+	fn set_mix_commitments() -> Weight;
+	fn submit_tally(t: u32, ) -> Weight;
+	fn cancel_election(d: u32, ) -> Weight;
+	fn set_election_keys(h: u32, ) -> Weight;
+	fn dispute_tally() -> Weight;
+	fn reject_dispute() -> Weight;
+	fn uphold_dispute(d: u32, ) -> Weight;
+	fn set_voter_allowlist() -> Weight;
+	fn fund_election() -> Weight;
+	fn reclaim_election_funds() -> Weight;
+}
+
+/// Estimated weights for pallet_daomix_voting, see the module docs.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting VoterAllowlists (r:1 w:0)
+	/// Storage: DaomixVoting Voters (r:1 w:0)
+	/// Storage: DaomixVoting VoterBallot (r:1 w:1)
+	/// Storage: DaomixVoting EncryptedTallies (r:1 w:1)
+	/// Storage: DaomixVoting Ballots (r:1 w:1)
+	/// Storage: DaomixVoting BallotCount (r:1 w:1)
+	/// Storage: DaomixVoting ReplacedBallots (r:0 w:1)
+	/// Storage: DaomixVoting FeelessBallots (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `o` is `[2, 16]`.
+	fn cast_vote(o: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 70_000)
+			.saturating_add(Weight::from_parts(450_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting VoterAllowlists (r:1 w:0)
+	/// Storage: DaomixVoting Nullifiers (r:1 w:1)
+	/// Storage: DaomixVoting EncryptedTallies (r:1 w:1)
+	/// Storage: DaomixVoting Ballots (r:1 w:1)
+	/// Storage: DaomixVoting BallotCount (r:1 w:1)
+	/// Storage: DaomixVoting ReplacedBallots (r:0 w:1)
+	/// Storage: DaomixVoting FeelessBallots (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `o` is `[2, 16]`.
+	fn cast_anonymous_vote(o: u32, ) -> Weight {
+		Weight::from_parts(40_000_000_000, 70_000)
+			.saturating_add(Weight::from_parts(450_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: DaomixVoting ReplacedBallots (r:1 w:0)
+	/// Proof: DaomixVoting ReplacedBallots (max_values: None, max_size: Some(41))
+	/// Storage: DaomixVoting Ballots (r:1 w:0)
+	/// Proof: DaomixVoting Ballots (max_values: None, max_size: Some(65580))
+	/// Storage: DaomixVoting InputLeafPositions (r:0 w:1)
+	/// Storage: DaomixVoting InputTreeNodes (r:0 w:2)
+	fn hash_ballot() -> Weight {
+		Weight::from_parts(700_000_000, 65_621)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting ShuffleStages (r:1 w:1)
+	/// Storage: DaomixVoting ShuffleHops (r:1 w:1)
+	fn commit_shuffle_hop() -> Weight {
+		Weight::from_parts(30_000_000, 22_500)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting ShuffleStages (r:1 w:1)
+	/// Storage: System BlockHash (r:1 w:0)
+	/// Storage: DaomixVoting ValidBallotCount (r:1 w:0)
+	/// Storage: DaomixVoting ShuffleChallenges (r:0 w:16)
+	/// The range of component `h` is `[1, 16]`.
+	fn draw_shuffle_challenges(h: u32, ) -> Weight {
+		Weight::from_parts(35_000_000, 22_600)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(h.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(h.into())))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting ShuffleStages (r:1 w:1)
+	/// Storage: DaomixVoting ShuffleHops (r:1 w:0)
+	/// Storage: DaomixVoting ShuffleChallenges (r:1 w:1)
+	/// Storage: DaomixVoting ValidBallotCount (r:1 w:0)
+	/// The range of component `l` is `[1, 8]`.
+	fn prove_shuffle_links(l: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 23_000)
+			.saturating_add(Weight::from_parts(400_000_000, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting DkgDeals (r:16 w:16)
+	/// Storage: DaomixVoting DkgComplaints (r:256 w:256)
+	/// The range of component `t` is `[1, 16]`.
+	fn set_tally_committee(t: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 91_000)
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(273_u64))
+			.saturating_add(T::DbWeight::get().writes(273_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting DkgDeals (r:1 w:1)
+	/// The range of component `t` is `[1, 16]`.
+	/// The range of component `k` is `[1, 16]`.
+	fn dkg_deal(t: u32, k: u32, ) -> Weight {
+		Weight::from_parts(35_000_000, 24_700)
+			.saturating_add(Weight::from_parts(1_000_000, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(25_000_000, 0).saturating_mul(k.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting DkgDeals (r:1 w:0)
+	/// Storage: DaomixVoting DkgComplaints (r:1 w:1)
+	/// The range of component `t` is `[1, 16]`.
+	fn dkg_complain(t: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 22_100)
+			.saturating_add(Weight::from_parts(500_000, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting DkgComplaints (r:1 w:1)
+	/// Storage: DaomixVoting DkgDeals (r:1 w:0)
+	/// The range of component `t` is `[1, 16]`.
+	/// The range of component `k` is `[1, 16]`.
+	fn dkg_answer_complaint(t: u32, k: u32, ) -> Weight {
+		Weight::from_parts(150_000_000, 24_800)
+			.saturating_add(Weight::from_parts(500_000, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(150_000_000, 0).saturating_mul(k.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting DkgComplaints (r:256 w:256)
+	/// Storage: DaomixVoting DkgDeals (r:16 w:16)
+	/// The range of component `t` is `[1, 16]`.
+	/// The range of component `k` is `[1, 16]`.
+	/// The range of component `c` is `[0, 256]`.
+	fn finish_dkg(t: u32, k: u32, c: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 22_000)
+			.saturating_add(Weight::from_parts(200_000_000, 2_700).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(200_000_000, 0).saturating_mul(k.into()))
+			.saturating_add(Weight::from_parts(10_000_000, 100).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(t.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(t.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(c.into())))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting TallyAttestations (r:0 w:1)
+	/// The range of component `t` is `[1, 16]`.
+	fn attest_tally(t: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 22_000)
+			.saturating_add(Weight::from_parts(500_000, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting EncryptedTallies (r:1 w:0)
+	/// Storage: DaomixVoting PartialDecryptions (r:0 w:1)
+	/// The range of component `o` is `[2, 16]`.
+	/// The range of component `k` is `[1, 16]`.
+	fn submit_partial_decryption(o: u32, k: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 24_000)
+			.saturating_add(Weight::from_parts(250_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(Weight::from_parts(150_000_000, 0).saturating_mul(k.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting EncryptedTallies (r:1 w:0)
+	/// Storage: DaomixVoting PartialDecryptions (r:16 w:0)
+	/// Storage: DaomixVoting DecryptedCounts (r:0 w:1)
+	/// The range of component `o` is `[2, 16]`.
+	/// The range of component `k` is `[1, 16]`.
+	fn submit_decrypted_counts(o: u32, k: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 24_000)
+			.saturating_add(Weight::from_parts(3_500_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(Weight::from_parts(5_000_000, 600).saturating_mul(k.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(k.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting PhaseSchedule (r:130 w:2)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: DaomixVoting BallotCount (r:0 w:1)
+	/// Storage: DaomixVoting ValidBallotCount (r:0 w:1)
+	fn create_election() -> Weight {
+		Weight::from_parts(150_000_000, 62_000)
+			.saturating_add(T::DbWeight::get().reads(133_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting VoterAllowlists (r:1 w:0)
+	/// Storage: DaomixVoting Voters (r:1 w:1)
+	fn register_voter() -> Weight {
+		Weight::from_parts(25_000_000, 23_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting InputRoots (r:1 w:0)
+	fn set_mix_commitments() -> Weight {
+		Weight::from_parts(25_000_000, 23_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting ShuffleStages (r:1 w:0)
+	/// Storage: DaomixVoting TallyAttestations (r:16 w:0)
+	/// Storage: DaomixVoting PhaseSchedule (r:65 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: DaomixVoting TallyResults (r:0 w:1)
+	/// The range of component `t` is `[1, 16]`.
+	fn submit_tally(t: u32, ) -> Weight {
+		Weight::from_parts(90_000_000, 43_000)
+			.saturating_add(Weight::from_parts(5_000_000, 2_600).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(69_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(t.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting Disputes (r:1 w:1)
+	/// Storage: DaomixVoting TallyResults (r:1 w:0)
+	/// Storage: Balances Holds (r:18 w:18)
+	/// Storage: System Account (r:18 w:18)
+	/// Storage: DaomixVoting InputRootProgress (r:0 w:1)
+	/// The range of component `d` is `[0, 16]`.
+	fn cancel_election(d: u32, ) -> Weight {
+		Weight::from_parts(70_000_000, 30_000)
+			.saturating_add(Weight::from_parts(20_000_000, 2_700).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(d.into())))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// The range of component `h` is `[1, 16]`.
+	fn set_election_keys(h: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 23_000)
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(h.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting TallyResults (r:1 w:0)
+	/// Storage: DaomixVoting BallotCount (r:1 w:0)
+	/// Storage: DaomixVoting ReplacedBallots (r:1 w:0)
+	/// Storage: DaomixVoting Disputes (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn dispute_tally() -> Weight {
+		Weight::from_parts(55_000_000, 30_000)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting Disputes (r:1 w:1)
+	/// Storage: DaomixVoting TallyResults (r:1 w:0)
+	/// Storage: Balances Holds (r:3 w:3)
+	/// Storage: System Account (r:3 w:3)
+	fn reject_dispute() -> Weight {
+		Weight::from_parts(90_000_000, 32_000)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting Disputes (r:1 w:1)
+	/// Storage: DaomixVoting TallyResults (r:1 w:1)
+	/// Storage: Balances Holds (r:18 w:18)
+	/// Storage: System Account (r:18 w:18)
+	/// Storage: DaomixVoting TallyAttestations (r:16 w:16)
+	/// Storage: DaomixVoting DecryptedCounts (r:0 w:1)
+	/// Storage: DaomixVoting ShuffleHops (r:16 w:16)
+	/// Storage: DaomixVoting ShuffleChallenges (r:16 w:16)
+	/// Storage: DaomixVoting ShuffleStages (r:0 w:1)
+	/// The range of component `d` is `[1, 16]`.
+	fn uphold_dispute(d: u32, ) -> Weight {
+		Weight::from_parts(150_000_000, 40_000)
+			.saturating_add(Weight::from_parts(20_000_000, 2_700).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(55_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(57_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(d.into())))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting VoterAllowlists (r:0 w:1)
+	fn set_voter_allowlist() -> Weight {
+		Weight::from_parts(25_000_000, 23_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	fn fund_election() -> Weight {
+		Weight::from_parts(60_000_000, 23_500)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	fn reclaim_election_funds() -> Weight {
+		Weight::from_parts(60_000_000, 23_500)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting VoterAllowlists (r:1 w:0)
+	/// Storage: DaomixVoting Voters (r:1 w:0)
+	/// Storage: DaomixVoting VoterBallot (r:1 w:1)
+	/// Storage: DaomixVoting EncryptedTallies (r:1 w:1)
+	/// Storage: DaomixVoting Ballots (r:1 w:1)
+	/// Storage: DaomixVoting BallotCount (r:1 w:1)
+	/// Storage: DaomixVoting ReplacedBallots (r:0 w:1)
+	/// Storage: DaomixVoting FeelessBallots (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `o` is `[2, 16]`.
+	fn cast_vote(o: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 70_000)
+			.saturating_add(Weight::from_parts(450_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting VoterAllowlists (r:1 w:0)
+	/// Storage: DaomixVoting Nullifiers (r:1 w:1)
+	/// Storage: DaomixVoting EncryptedTallies (r:1 w:1)
+	/// Storage: DaomixVoting Ballots (r:1 w:1)
+	/// Storage: DaomixVoting BallotCount (r:1 w:1)
+	/// Storage: DaomixVoting ReplacedBallots (r:0 w:1)
+	/// Storage: DaomixVoting FeelessBallots (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `o` is `[2, 16]`.
+	fn cast_anonymous_vote(o: u32, ) -> Weight {
+		Weight::from_parts(40_000_000_000, 70_000)
+			.saturating_add(Weight::from_parts(450_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: DaomixVoting ReplacedBallots (r:1 w:0)
+	/// Proof: DaomixVoting ReplacedBallots (max_values: None, max_size: Some(41))
+	/// Storage: DaomixVoting Ballots (r:1 w:0)
+	/// Proof: DaomixVoting Ballots (max_values: None, max_size: Some(65580))
+	/// Storage: DaomixVoting InputLeafPositions (r:0 w:1)
+	/// Storage: DaomixVoting InputTreeNodes (r:0 w:2)
+	fn hash_ballot() -> Weight {
+		Weight::from_parts(700_000_000, 65_621)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting ShuffleStages (r:1 w:1)
+	/// Storage: DaomixVoting ShuffleHops (r:1 w:1)
+	fn commit_shuffle_hop() -> Weight {
+		Weight::from_parts(30_000_000, 22_500)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting ShuffleStages (r:1 w:1)
+	/// Storage: System BlockHash (r:1 w:0)
+	/// Storage: DaomixVoting ValidBallotCount (r:1 w:0)
+	/// Storage: DaomixVoting ShuffleChallenges (r:0 w:16)
+	/// The range of component `h` is `[1, 16]`.
+	fn draw_shuffle_challenges(h: u32, ) -> Weight {
+		Weight::from_parts(35_000_000, 22_600)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(h.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(h.into())))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting ShuffleStages (r:1 w:1)
+	/// Storage: DaomixVoting ShuffleHops (r:1 w:0)
+	/// Storage: DaomixVoting ShuffleChallenges (r:1 w:1)
+	/// Storage: DaomixVoting ValidBallotCount (r:1 w:0)
+	/// The range of component `l` is `[1, 8]`.
+	fn prove_shuffle_links(l: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 23_000)
+			.saturating_add(Weight::from_parts(400_000_000, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting DkgDeals (r:16 w:16)
+	/// Storage: DaomixVoting DkgComplaints (r:256 w:256)
+	/// The range of component `t` is `[1, 16]`.
+	fn set_tally_committee(t: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 91_000)
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(273_u64))
+			.saturating_add(RocksDbWeight::get().writes(273_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting DkgDeals (r:1 w:1)
+	/// The range of component `t` is `[1, 16]`.
+	/// The range of component `k` is `[1, 16]`.
+	fn dkg_deal(t: u32, k: u32, ) -> Weight {
+		Weight::from_parts(35_000_000, 24_700)
+			.saturating_add(Weight::from_parts(1_000_000, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(25_000_000, 0).saturating_mul(k.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting DkgDeals (r:1 w:0)
+	/// Storage: DaomixVoting DkgComplaints (r:1 w:1)
+	/// The range of component `t` is `[1, 16]`.
+	fn dkg_complain(t: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 22_100)
+			.saturating_add(Weight::from_parts(500_000, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting DkgComplaints (r:1 w:1)
+	/// Storage: DaomixVoting DkgDeals (r:1 w:0)
+	/// The range of component `t` is `[1, 16]`.
+	/// The range of component `k` is `[1, 16]`.
+	fn dkg_answer_complaint(t: u32, k: u32, ) -> Weight {
+		Weight::from_parts(150_000_000, 24_800)
+			.saturating_add(Weight::from_parts(500_000, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(150_000_000, 0).saturating_mul(k.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting DkgComplaints (r:256 w:256)
+	/// Storage: DaomixVoting DkgDeals (r:16 w:16)
+	/// The range of component `t` is `[1, 16]`.
+	/// The range of component `k` is `[1, 16]`.
+	/// The range of component `c` is `[0, 256]`.
+	fn finish_dkg(t: u32, k: u32, c: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 22_000)
+			.saturating_add(Weight::from_parts(200_000_000, 2_700).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(200_000_000, 0).saturating_mul(k.into()))
+			.saturating_add(Weight::from_parts(10_000_000, 100).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(t.into())))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(c.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(t.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(c.into())))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting TallyAttestations (r:0 w:1)
+	/// The range of component `t` is `[1, 16]`.
+	fn attest_tally(t: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 22_000)
+			.saturating_add(Weight::from_parts(500_000, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting EncryptedTallies (r:1 w:0)
+	/// Storage: DaomixVoting PartialDecryptions (r:0 w:1)
+	/// The range of component `o` is `[2, 16]`.
+	/// The range of component `k` is `[1, 16]`.
+	fn submit_partial_decryption(o: u32, k: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 24_000)
+			.saturating_add(Weight::from_parts(250_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(Weight::from_parts(150_000_000, 0).saturating_mul(k.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting EncryptedTallies (r:1 w:0)
+	/// Storage: DaomixVoting PartialDecryptions (r:16 w:0)
+	/// Storage: DaomixVoting DecryptedCounts (r:0 w:1)
+	/// The range of component `o` is `[2, 16]`.
+	/// The range of component `k` is `[1, 16]`.
+	fn submit_decrypted_counts(o: u32, k: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 24_000)
+			.saturating_add(Weight::from_parts(3_500_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(Weight::from_parts(5_000_000, 600).saturating_mul(k.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(k.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting PhaseSchedule (r:130 w:2)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: DaomixVoting BallotCount (r:0 w:1)
+	/// Storage: DaomixVoting ValidBallotCount (r:0 w:1)
+	fn create_election() -> Weight {
+		Weight::from_parts(150_000_000, 62_000)
+			.saturating_add(RocksDbWeight::get().reads(133_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting VoterAllowlists (r:1 w:0)
+	/// Storage: DaomixVoting Voters (r:1 w:1)
+	fn register_voter() -> Weight {
+		Weight::from_parts(25_000_000, 23_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting InputRoots (r:1 w:0)
+	fn set_mix_commitments() -> Weight {
+		Weight::from_parts(25_000_000, 23_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting ShuffleStages (r:1 w:0)
+	/// Storage: DaomixVoting TallyAttestations (r:16 w:0)
+	/// Storage: DaomixVoting PhaseSchedule (r:65 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: DaomixVoting TallyResults (r:0 w:1)
+	/// The range of component `t` is `[1, 16]`.
+	fn submit_tally(t: u32, ) -> Weight {
+		Weight::from_parts(90_000_000, 43_000)
+			.saturating_add(Weight::from_parts(5_000_000, 2_600).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(69_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(t.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting Disputes (r:1 w:1)
+	/// Storage: DaomixVoting TallyResults (r:1 w:0)
+	/// Storage: Balances Holds (r:18 w:18)
+	/// Storage: System Account (r:18 w:18)
+	/// Storage: DaomixVoting InputRootProgress (r:0 w:1)
+	/// The range of component `d` is `[0, 16]`.
+	fn cancel_election(d: u32, ) -> Weight {
+		Weight::from_parts(70_000_000, 30_000)
+			.saturating_add(Weight::from_parts(20_000_000, 2_700).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(d.into())))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// The range of component `h` is `[1, 16]`.
+	fn set_election_keys(h: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 23_000)
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(h.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting TallyResults (r:1 w:0)
+	/// Storage: DaomixVoting BallotCount (r:1 w:0)
+	/// Storage: DaomixVoting ReplacedBallots (r:1 w:0)
+	/// Storage: DaomixVoting Disputes (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn dispute_tally() -> Weight {
+		Weight::from_parts(55_000_000, 30_000)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting Disputes (r:1 w:1)
+	/// Storage: DaomixVoting TallyResults (r:1 w:0)
+	/// Storage: Balances Holds (r:3 w:3)
+	/// Storage: System Account (r:3 w:3)
+	fn reject_dispute() -> Weight {
+		Weight::from_parts(90_000_000, 32_000)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting Disputes (r:1 w:1)
+	/// Storage: DaomixVoting TallyResults (r:1 w:1)
+	/// Storage: Balances Holds (r:18 w:18)
+	/// Storage: System Account (r:18 w:18)
+	/// Storage: DaomixVoting TallyAttestations (r:16 w:16)
+	/// Storage: DaomixVoting DecryptedCounts (r:0 w:1)
+	/// Storage: DaomixVoting ShuffleHops (r:16 w:16)
+	/// Storage: DaomixVoting ShuffleChallenges (r:16 w:16)
+	/// Storage: DaomixVoting ShuffleStages (r:0 w:1)
+	/// The range of component `d` is `[1, 16]`.
+	fn uphold_dispute(d: u32, ) -> Weight {
+		Weight::from_parts(150_000_000, 40_000)
+			.saturating_add(Weight::from_parts(20_000_000, 2_700).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(55_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(57_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(d.into())))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: DaomixVoting VoterAllowlists (r:0 w:1)
+	fn set_voter_allowlist() -> Weight {
+		Weight::from_parts(25_000_000, 23_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	fn fund_election() -> Weight {
+		Weight::from_parts(60_000_000, 23_500)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	fn reclaim_election_funds() -> Weight {
+		Weight::from_parts(60_000_000, 23_500)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
@@ -0,0 +1,254 @@
+//! Benchmarking setup for pallet-mix-job
+
+use super::*;
+use crate::ElectionProvider;
+use frame::{deps::frame_benchmarking::v2::*, prelude::*};
+use frame_system::RawOrigin;
+
+/// Election mixed by the benchmarked jobs.
+const ELECTION_ID: u32 = 0;
+
+/// Give `who` enough to pay the deposit and fee of a few jobs.
+fn fund<T: Config>(who: &T::AccountId) {
+	let job = T::JobDeposit::get().saturating_add(T::ServiceFee::get());
+	let balance = T::Currency::minimum_balance().saturating_add(job.saturating_mul(4u32.into()));
+	T::Currency::set_balance(who, balance);
+}
+
+/// Register orchestrators until `count` are registered.
+fn fill_orchestrators<T: Config>(count: u32) {
+	Orchestrators::<T>::mutate(|orchestrators| {
+		let mut i = 0;
+		while orchestrators.len() < count as usize {
+			let _ = orchestrators.try_push(account("orchestrator", i, 0));
+			i += 1;
+		}
+	});
+}
+
+/// Register `who` as the last of `MaxOrchestrators` orchestrators, so finding it scans all.
+fn orchestrator<T: Config>(who: &T::AccountId) {
+	fill_orchestrators::<T>(T::MaxOrchestrators::get().saturating_sub(1));
+	Orchestrators::<T>::mutate(|orchestrators| {
+		let _ = orchestrators.try_push(who.clone());
+	});
+}
+
+/// Allow sibling `para_id` to submit any number of jobs.
+fn allow_para<T: Config>(para_id: u32) {
+	let quota =
+		ParaQuota { max_active_jobs: u32::MAX, max_jobs_per_period: u32::MAX, period: One::one() };
+	AllowedParas::<T>::insert(para_id, quota);
+}
+
+/// The `XcmOrigin` of a funded sibling parachain on the allowlist, and its para id.
+fn sibling<T: Config>() -> Result<(T::RuntimeOrigin, u32), BenchmarkError> {
+	let origin = T::XcmOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+	let para_id =
+		T::XcmOrigin::try_origin(origin.clone()).map_err(|_| BenchmarkError::Weightless)?;
+	allow_para::<T>(para_id);
+	fund::<T>(&T::SovereignAccountOf::convert(para_id));
+	Ok((origin, para_id))
+}
+
+/// Submit a job mixing an election ready for mixing from a sibling parachain, returning the
+/// job id and the election's input root.
+fn sibling_job<T: Config>() -> Result<(JobId, T::Hash), BenchmarkError> {
+	let (origin, _) = sibling::<T>()?;
+	let input_root = T::ElectionProvider::setup_ready_election(ELECTION_ID);
+	let job_id = NextJobId::<T>::get();
+	Pallet::<T>::submit_job(origin, JobKind::Election(ELECTION_ID))?;
+	Ok((job_id, input_root))
+}
+
+/// Submit a sibling job as in `sibling_job` and claim it as orchestrator `who`.
+fn claimed_job<T: Config>(who: &T::AccountId) -> Result<(JobId, T::Hash), BenchmarkError> {
+	let (job_id, input_root) = sibling_job::<T>()?;
+	orchestrator::<T>(who);
+	Pallet::<T>::claim_job(RawOrigin::Signed(who.clone()).into(), job_id)?;
+	Ok((job_id, input_root))
+}
+
+/// Fund the pallet account, which receives slashed deposits and pays reaping rewards.
+fn fund_pot<T: Config>() {
+	let pot = T::PalletId::get().into_account_truncating();
+	let balance = T::Currency::minimum_balance().saturating_add(T::ReapReward::get());
+	T::Currency::set_balance(&pot, balance);
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	// Worst case: a sibling parachain counted against its quota submits an election job.
+	#[benchmark]
+	fn submit_job() -> Result<(), BenchmarkError> {
+		let (origin, para_id) = sibling::<T>()?;
+		T::ElectionProvider::setup_ready_election(ELECTION_ID);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, JobKind::Election(ELECTION_ID));
+
+		assert_eq!(LastJobForElection::<T>::get(ELECTION_ID), Some(0));
+		assert_eq!(ParaUsageOf::<T>::get(para_id).active_jobs, 1);
+		Ok(())
+	}
+
+	// Worst case: the lease holder fails a sibling's job with the longest error, slashing its
+	// deposit. The callback is charged separately.
+	#[benchmark]
+	fn update_job_status() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (job_id, _) = claimed_job::<T>(&caller)?;
+		fund_pot::<T>();
+		let error_code = BoundedVec::truncate_from(sp_std::vec![0; 256]);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), job_id, JobStatus::Failed, Some(error_code));
+
+		assert_eq!(Jobs::<T>::get(job_id).map(|job| job.status), Some(JobStatus::Failed));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn add_orchestrator() -> Result<(), BenchmarkError> {
+		let origin =
+			T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let who: T::AccountId = account("orchestrator", u32::MAX, 0);
+		fill_orchestrators::<T>(T::MaxOrchestrators::get().saturating_sub(1));
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone());
+
+		assert!(Pallet::<T>::is_orchestrator(&who));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_orchestrator() -> Result<(), BenchmarkError> {
+		let origin =
+			T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let who: T::AccountId = account("orchestrator", u32::MAX, 0);
+		orchestrator::<T>(&who);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone());
+
+		assert!(!Pallet::<T>::is_orchestrator(&who));
+		Ok(())
+	}
+
+	// Worst case: the lease holder completes a sibling's election job with the longest result
+	// URI, recording the election's mix commitments and earning the service fee. The callback
+	// is charged separately.
+	#[benchmark]
+	fn complete_job() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (job_id, input_root) = claimed_job::<T>(&caller)?;
+		let result_uri = ResultUri::truncate_from(sp_std::vec![0; 256]);
+		let result_hash = T::Hashing::hash(b"result");
+		let output_root = T::Hashing::hash(b"output");
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), job_id, result_uri, result_hash, input_root, output_root);
+
+		assert_eq!(Jobs::<T>::get(job_id).map(|job| job.status), Some(JobStatus::Completed));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_para_callback() -> Result<(), BenchmarkError> {
+		let origin =
+			T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, 1_000, Some([0, 0]));
+
+		assert_eq!(ParaCallbacks::<T>::get(1_000), Some([0, 0]));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_para_quota() -> Result<(), BenchmarkError> {
+		let origin =
+			T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let quota = ParaQuota { max_active_jobs: 1, max_jobs_per_period: 1, period: One::one() };
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, 1_000, quota);
+
+		assert_eq!(AllowedParas::<T>::get(1_000), Some(quota));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_para() -> Result<(), BenchmarkError> {
+		let origin =
+			T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		allow_para::<T>(1_000);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, 1_000);
+
+		assert!(!AllowedParas::<T>::contains_key(1_000));
+		Ok(())
+	}
+
+	// Worst case: a finished election job is reaped and the pallet account pays the reward.
+	#[benchmark]
+	fn reap_job() -> Result<(), BenchmarkError> {
+		let orchestrator: T::AccountId = account("orchestrator", u32::MAX, 0);
+		let (job_id, input_root) = claimed_job::<T>(&orchestrator)?;
+		Pallet::<T>::complete_job(
+			RawOrigin::Signed(orchestrator).into(),
+			job_id,
+			ResultUri::default(),
+			Default::default(),
+			input_root,
+			Default::default(),
+		)?;
+		fund_pot::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(
+			now.saturating_add(T::JobRetentionPeriod::get()),
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), job_id);
+
+		assert!(!Jobs::<T>::contains_key(job_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn claim_job() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (job_id, _) = sibling_job::<T>()?;
+		orchestrator::<T>(&caller);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), job_id);
+
+		assert_eq!(Jobs::<T>::get(job_id).and_then(|job| job.claimed_by), Some(caller));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn renew_lease() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (job_id, _) = claimed_job::<T>(&caller)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now.saturating_add(One::one()));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), job_id);
+
+		let renewed = now.saturating_add(One::one()).saturating_add(T::LeaseDuration::get());
+		assert_eq!(Jobs::<T>::get(job_id).and_then(|job| job.lease_expires_at), Some(renewed));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -2,9 +2,25 @@
 
 pub use pallet::*;
 
+pub mod migrations;
+mod traits;
+pub mod weights;
+pub use traits::{ElectionProvider, MessageBatchProvider, PublicationBatchProvider};
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 #[frame::pallet]
 pub mod pallet {
+	use crate::{
+		weights::WeightInfo, ElectionProvider, MessageBatchProvider, PublicationBatchProvider,
+	};
 	use frame::prelude::*;
+	use sp_std::vec::Vec;
+	use xcm::latest::{
+		validate_send, ExecuteXcm, Instruction, Junction, Location, OriginKind, SendXcm,
+		WeightLimit, Xcm, XcmHash,
+	};
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
@@ -12,21 +28,152 @@ pub mod pallet {
 		#[allow(deprecated)]
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
-		/// Maximum jobs allowed to avoid unbounded growth.
+		/// A type representing the weights required by the dispatchables of this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Maximum jobs stored at once, including finished jobs awaiting archival.
 		#[pallet::constant]
 		type MaxJobs: Get<u32>;
+
+		/// Overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// Currency holding job deposits and fees, and paying job reaping rewards.
+		type Currency: fungible::Mutate<Self::AccountId>
+			+ fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// Deposit held from the requester of each job until the job finishes.
+		#[pallet::constant]
+		type JobDeposit: Get<BalanceOf<Self>>;
+
+		/// Fee held from the requester of each job and paid to the orchestrator that
+		/// completes it.
+		#[pallet::constant]
+		type ServiceFee: Get<BalanceOf<Self>>;
+
+		/// Part of the deposit slashed to the pallet account when an orchestrator fails a job.
+		///
+		/// Jobs that time out are refunded in full.
+		#[pallet::constant]
+		type FailureSlash: Get<Perbill>;
+
+		/// Pallet id whose account funds job reaping rewards.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Blocks a finished job is kept before it can be archived.
+		#[pallet::constant]
+		type JobRetentionPeriod: Get<BlockNumberFor<Self>>;
+
+		/// Reward paid to whoever reaps an expired job with `reap_job`.
+		#[pallet::constant]
+		type ReapReward: Get<BalanceOf<Self>>;
+
+		/// Blocks after its submission a job may stay Pending before it fails with
+		/// `JobErrorCode::TimedOut`.
+		///
+		/// Expired leases return a job to Pending without restarting this timeout.
+		#[pallet::constant]
+		type PendingTimeout: Get<BlockNumberFor<Self>>;
+
+		/// Blocks a job may stay Running without a status update before it fails with
+		/// `JobErrorCode::TimedOut`.
+		#[pallet::constant]
+		type RunningTimeout: Get<BlockNumberFor<Self>>;
+
+		/// Blocks an orchestrator holds a claimed job before it returns to Pending.
+		#[pallet::constant]
+		type LeaseDuration: Get<BlockNumberFor<Self>>;
+
+		/// Maximum job timeouts and lease expiries due in one block.
+		///
+		/// This bounds the work done by `on_initialize`. Checks due in a full block move to the
+		/// next block with room.
+		#[pallet::constant]
+		type MaxTimeoutsPerBlock: Get<u32>;
+
+		/// Origin allowed to manage orchestrators and the sibling parachain allowlist
+		/// (e.g. root or governance).
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Maximum number of orchestrator accounts.
+		#[pallet::constant]
+		type MaxOrchestrators: Get<u32>;
+
+		/// Elections that jobs can be submitted for.
+		type ElectionProvider: ElectionProvider<Self::Hash>;
+
+		/// Mailbox message batches that jobs can be submitted for.
+		type MessageBatches: MessageBatchProvider;
+
+		/// Publication batches that jobs can be submitted for.
+		type PublicationBatches: PublicationBatchProvider;
+
+		/// Origin of jobs arriving from sibling parachains over XCM, yielding the para id.
+		///
+		/// Typically a sibling's `Transact` dispatched with `OriginKind::Native`.
+		type XcmOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = u32>;
+
+		/// Sovereign account of a sibling parachain, recorded as the requester of its jobs.
+		type SovereignAccountOf: Convert<u32, Self::AccountId>;
+
+		/// Para id of a sibling parachain's sovereign account, or `None` for other accounts.
+		///
+		/// Sovereign accounts cannot submit signed jobs, which would bypass the allowlist.
+		type ParaOfSovereignAccount: Convert<Self::AccountId, Option<u32>>;
+
+		/// Router used to send job result callbacks to sibling parachains.
+		type XcmRouter: SendXcm;
+
+		/// Executor used to charge callback delivery fees to `CallbackFeeSource`.
+		type XcmExecutor: ExecuteXcm<<Self as frame_system::Config>::RuntimeCall>;
+
+		/// Location whose funds pay the delivery fees of job result callbacks.
+		type CallbackFeeSource: Get<Location>;
+
+		/// Weight of sending one job result callback, including its delivery fee.
+		///
+		/// Charged in `on_initialize` for every timed out job that came from a sibling, and on
+		/// top of `WeightInfo` by the calls that can finish a job.
+		#[pallet::constant]
+		type CallbackWeight: Get<Weight>;
 	}
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	/// Type aliases for clarity.
+	pub type BalanceOf<T> = <<T as Config>::Currency as fungible::Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
 	pub type JobId = u64;
+	pub type ResultUri = BoundedVec<u8, ConstU32<256>>;
+	pub type MixJobInfoOf<T> = MixJobInfo<
+		<T as frame_system::Config>::AccountId,
+		BlockNumberFor<T>,
+		<T as frame_system::Config>::Hash,
+		BalanceOf<T>,
+	>;
+
+	/// Maximum job ids scanned by one `DaoMixApi::jobs_by_status` page.
+	pub const MAX_JOBS_PER_PAGE: u32 = 256;
+
+	/// Reasons the pallet holds funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Deposit and service fee of a submitted job.
+		JobDeposit,
+	}
 
 	#[derive(
 		Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, Eq, PartialEq, RuntimeDebug,
 		DecodeWithMemTracking,
 	)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 	pub enum JobStatus {
 		Pending,
 		Running,
@@ -34,16 +181,104 @@ pub mod pallet {
 		Failed,
 	}
 
+	/// What a job mixes, and the id of its target.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, Eq, PartialEq, RuntimeDebug,
+		DecodeWithMemTracking,
+	)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	pub enum JobKind {
+		/// Ballots of a DaoMix election.
+		Election(u32),
+		/// A batch of mailbox messages.
+		MessageBatch(u64),
+		/// A batch of publications.
+		PublicationBatch(u64),
+	}
+
+	impl JobKind {
+		/// Election id of an `Election` job.
+		pub fn election_id(&self) -> Option<u32> {
+			match self {
+				JobKind::Election(election_id) => Some(*election_id),
+				_ => None,
+			}
+		}
+	}
+
+	/// Why a job failed.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Eq, PartialEq, RuntimeDebug,
+		DecodeWithMemTracking,
+	)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	pub enum JobErrorCode {
+		/// Error reported by the orchestrator.
+		Orchestrator(BoundedVec<u8, ConstU32<256>>),
+		/// The job exceeded `PendingTimeout` or `RunningTimeout`.
+		TimedOut,
+	}
+
+	/// Job limits of an allowlisted sibling parachain.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, Eq, PartialEq, RuntimeDebug,
+		DecodeWithMemTracking,
+	)]
+	pub struct ParaQuota<BlockNumber> {
+		/// Maximum Pending or Running jobs of the parachain at any time.
+		pub max_active_jobs: u32,
+		/// Maximum jobs the parachain can submit within one `period`.
+		pub max_jobs_per_period: u32,
+		/// Length of the rate limit window in blocks.
+		pub period: BlockNumber,
+	}
+
+	/// Job usage of a sibling parachain, checked against its `ParaQuota`.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Default, Eq, PartialEq, RuntimeDebug,
+	)]
+	pub struct ParaUsage<BlockNumber> {
+		/// Pending or Running jobs of the parachain.
+		pub active_jobs: u32,
+		/// First block of the current rate limit window.
+		pub window_start: BlockNumber,
+		/// Jobs submitted since `window_start`.
+		pub submitted_in_window: u32,
+	}
+
 	#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Eq, PartialEq, RuntimeDebug)]
-	pub struct MixJobInfo<AccountId, BlockNumber> {
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct MixJobInfo<AccountId, BlockNumber, Hash, Balance> {
 		pub job_id: u64,
 		pub requester: AccountId,
+		/// Deposit held from `requester` until the job finishes.
+		pub deposit: Balance,
+		/// Service fee held from `requester`, paid out when the job completes.
+		pub fee: Balance,
 		pub source_para: Option<u32>,
-		pub election_id: u32,
+		/// What the job mixes.
+		pub kind: JobKind,
 		pub created_at: BlockNumber,
 		pub status: JobStatus,
 		pub last_update: BlockNumber,
-		pub error_code: Option<BoundedVec<u8, ConstU32<256>>>,
+		/// Orchestrator that performed the last status transition.
+		pub last_updated_by: Option<AccountId>,
+		/// Orchestrator holding the job while it is Running.
+		pub claimed_by: Option<AccountId>,
+		/// Block at which the lease of `claimed_by` expires.
+		pub lease_expires_at: Option<BlockNumber>,
+		/// Block whose `on_initialize` checks the job for a timeout or lease expiry.
+		pub next_check: Option<BlockNumber>,
+		pub error_code: Option<JobErrorCode>,
+		/// URI of the full mix/tally result (e.g. IPFS hash, JSON URL).
+		pub result_uri: Option<ResultUri>,
+		/// Hash of the full result data.
+		pub result_hash: Option<Hash>,
+		/// Merkle root of the ballots fed into the mix.
+		pub input_root: Option<Hash>,
+		/// Merkle root of the mixed output ballots.
+		pub output_root: Option<Hash>,
 	}
 
 	/// Storage: Next job ID counter.
@@ -54,12 +289,27 @@ pub mod pallet {
 	/// Storage: Jobs mapping JobId → MixJobInfo.
 	#[pallet::storage]
 	#[pallet::getter(fn jobs)]
-	pub type Jobs<T: Config> = StorageMap<
+	pub type Jobs<T: Config> =
+		CountedStorageMap<_, Blake2_128Concat, JobId, MixJobInfoOf<T>, OptionQuery>;
+
+	/// Storage: Finished jobs awaiting archival, in the order they finished.
+	#[pallet::storage]
+	pub type ArchiveQueue<T: Config> = StorageMap<_, Twox64Concat, u64, JobId, OptionQuery>;
+
+	/// Storage: `(head, tail)` positions of `ArchiveQueue`.
+	#[pallet::storage]
+	pub type ArchiveQueueRange<T: Config> = StorageValue<_, (u64, u64), ValueQuery>;
+
+	/// Storage: Jobs to check for a timeout or lease expiry at a given block.
+	///
+	/// Each Pending or Running job is listed once, at its `next_check` block.
+	#[pallet::storage]
+	pub type TimeoutSchedule<T: Config> = StorageMap<
 		_,
-		Blake2_128Concat,
-		JobId,
-		MixJobInfo<T::AccountId, BlockNumberFor<T>>,
-		OptionQuery,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<JobId, T::MaxTimeoutsPerBlock>,
+		ValueQuery,
 	>;
 
 	/// Storage: Index from ElectionId to last JobId.
@@ -68,6 +318,35 @@ pub mod pallet {
 	pub type LastJobForElection<T: Config> =
 		StorageMap<_, Blake2_128Concat, u32, JobId, OptionQuery>;
 
+	/// Storage: Accounts allowed to drive job status transitions.
+	#[pallet::storage]
+	#[pallet::getter(fn orchestrators)]
+	pub type Orchestrators<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxOrchestrators>, ValueQuery>;
+
+	/// Storage: Call index `[pallet_index, call_index]` on each sibling parachain that receives
+	/// job result callbacks.
+	///
+	/// The callback is a `Transact` of this call index followed by the SCALE encoding of
+	/// `(job_id, status, result_hash, result_uri)`, dispatched with the DaoChain native origin.
+	#[pallet::storage]
+	#[pallet::getter(fn para_callbacks)]
+	pub type ParaCallbacks<T: Config> = StorageMap<_, Twox64Concat, u32, [u8; 2], OptionQuery>;
+
+	/// Storage: Sibling parachains allowed to submit jobs over XCM, with their quotas.
+	///
+	/// Siblings cannot sidestep it with signed jobs from their sovereign accounts.
+	#[pallet::storage]
+	#[pallet::getter(fn allowed_paras)]
+	pub type AllowedParas<T: Config> =
+		StorageMap<_, Twox64Concat, u32, ParaQuota<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Storage: Job usage of each sibling parachain.
+	#[pallet::storage]
+	#[pallet::getter(fn para_usage)]
+	pub type ParaUsageOf<T: Config> =
+		StorageMap<_, Twox64Concat, u32, ParaUsage<BlockNumberFor<T>>, ValueQuery>;
+
 	/// Pallets use events to inform users when important changes are made.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -75,15 +354,59 @@ pub mod pallet {
 		/// A mixing job was submitted.
 		JobSubmitted {
 			job_id: JobId,
-			election_id: u32,
+			kind: JobKind,
 			requester: T::AccountId,
+			source_para: Option<u32>,
 		},
 		/// A job status was updated.
 		JobStatusUpdated {
 			job_id: JobId,
 			old_status: u8,
 			new_status: u8,
+			orchestrator: T::AccountId,
 		},
+		/// A job completed and its results were stored on-chain.
+		JobCompleted {
+			job_id: JobId,
+			result_uri: ResultUri,
+			result_hash: T::Hash,
+			input_root: T::Hash,
+			output_root: T::Hash,
+		},
+		/// A job result callback was sent to the requesting parachain.
+		CallbackSent { job_id: JobId, para_id: u32, message_id: XcmHash },
+		/// A job result callback could not be sent to the requesting parachain.
+		CallbackFailed { job_id: JobId, para_id: u32 },
+		/// The callback call of a sibling parachain was set or cleared.
+		ParaCallbackSet { para_id: u32, call_index: Option<[u8; 2]> },
+		/// An orchestrator claimed a Pending job and started running it.
+		JobClaimed {
+			job_id: JobId,
+			orchestrator: T::AccountId,
+			lease_expires_at: BlockNumberFor<T>,
+		},
+		/// The lease holder extended its lease.
+		LeaseRenewed { job_id: JobId, lease_expires_at: BlockNumberFor<T> },
+		/// A lease expired and the job returned to Pending.
+		LeaseExpired { job_id: JobId, orchestrator: T::AccountId },
+		/// A Pending or Running job exceeded its timeout and was marked Failed.
+		JobTimedOut { job_id: JobId, old_status: u8 },
+		/// A finished job was removed from storage after its retention period.
+		JobArchived { job_id: JobId },
+		/// A finished job was removed from storage by `who`, who received `reward`.
+		JobReaped { job_id: JobId, who: T::AccountId, reward: BalanceOf<T> },
+		/// The service fee of a completed job was paid to its orchestrator.
+		ServiceFeePaid { job_id: JobId, orchestrator: T::AccountId, fee: BalanceOf<T> },
+		/// Part of the deposit of a failed job was slashed.
+		DepositSlashed { job_id: JobId, requester: T::AccountId, amount: BalanceOf<T> },
+		/// A sibling parachain was allowed to submit jobs, or its quota was changed.
+		ParaQuotaSet { para_id: u32, quota: ParaQuota<BlockNumberFor<T>> },
+		/// A sibling parachain is no longer allowed to submit jobs.
+		ParaRemoved { para_id: u32 },
+		/// An account was allowed to drive jobs.
+		OrchestratorAdded { who: T::AccountId },
+		/// An account is no longer allowed to drive jobs.
+		OrchestratorRemoved { who: T::AccountId },
 	}
 
 	/// Errors inform users that something went wrong.
@@ -95,100 +418,831 @@ pub mod pallet {
 		JobNotFound,
 		/// Invalid status transition.
 		InvalidStatusTransition,
+		/// Caller is not a registered orchestrator.
+		NotOrchestrator,
+		/// Election does not exist.
+		ElectionNotFound,
+		/// Election is not past its voting deadline, or already has its commitments.
+		ElectionNotReadyForMixing,
+		/// Message batch does not exist.
+		MessageBatchNotFound,
+		/// Publication batch does not exist.
+		PublicationBatchNotFound,
+		/// Account is already a registered orchestrator.
+		AlreadyOrchestrator,
+		/// Orchestrator limit reached.
+		TooManyOrchestrators,
+		/// Sibling parachain is not on the allowlist.
+		ParaNotAllowed,
+		/// Sibling parachain already has its maximum of active jobs.
+		TooManyActiveParaJobs,
+		/// Sibling parachain submitted its maximum of jobs for the current period.
+		ParaRateLimited,
+		/// Quota period must be at least one block.
+		InvalidParaQuota,
+		/// Job is not Completed or Failed.
+		JobNotFinished,
+		/// Job retention period has not elapsed yet.
+		JobRetained,
+		/// Job is not Pending.
+		JobNotPending,
+		/// Caller does not hold the job's lease.
+		NotLeaseHolder,
+		/// Jobs are completed with their results through `complete_job`.
+		CompletionRequiresResults,
+		/// Sibling sovereign accounts can only submit jobs over XCM with a native origin.
+		SovereignAccountNotAllowed,
+		/// The caller's lease on the job has expired.
+		LeaseExpired,
 	}
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let due = TimeoutSchedule::<T>::take(now);
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+			// Bounded by `MaxTimeoutsPerBlock`.
+			for job_id in due {
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(3, 4));
+				if Self::time_out_job(job_id, now) {
+					weight = weight.saturating_add(T::CallbackWeight::get());
+				}
+			}
+
+			weight
+		}
+
+		/// Archive finished jobs past their retention period with the leftover block weight.
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::archive_expired_jobs(now, remaining_weight)
+		}
+	}
 
 	/// Dispatchable functions allows users to interact with the pallet and invoke state changes.
 	/// These functions materialize as "extrinsics", which are often compared to transactions.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Submit a new job for an existing Daomix election.
+		/// Submit a new job mixing an election, a message batch or a publication batch.
+		///
+		/// Accepts signed extrinsics and `XcmOrigin`. Jobs arriving over XCM record the
+		/// sibling's para id and use its sovereign account as requester; the sibling must be
+		/// on the allowlist and within its quota. Sovereign accounts cannot sign for jobs. The
+		/// target must exist, and an election must also be past its voting deadline.
 		///
-		/// Only signed extrinsics are allowed.
+		/// `JobDeposit` and `ServiceFee` are held from the requester until the job finishes.
 		#[pallet::call_index(0)]
-		#[pallet::weight(10_000)]
-		pub fn submit_job(origin: OriginFor<T>, election_id: u32) -> DispatchResult {
-			let who = ensure_signed(origin)?;
+		#[pallet::weight(T::WeightInfo::submit_job())]
+		pub fn submit_job(origin: OriginFor<T>, kind: JobKind) -> DispatchResult {
+			let (who, source_para) = match T::XcmOrigin::try_origin(origin) {
+				Ok(para_id) => (T::SovereignAccountOf::convert(para_id), Some(para_id)),
+				Err(origin) => {
+					let who = ensure_signed(origin)?;
+					ensure!(
+						T::ParaOfSovereignAccount::convert(who.clone()).is_none(),
+						Error::<T>::SovereignAccountNotAllowed
+					);
+					(who, None)
+				},
+			};
+
+			Self::ensure_target(kind)?;
 
-			// Enforce MaxJobs limit (simple count is fine for now).
-			let max_jobs: u32 = T::MaxJobs::get();
-			let current_jobs: u32 = Jobs::<T>::iter_keys().count() as u32;
-			ensure!(current_jobs < max_jobs, Error::<T>::JobLimitReached);
+			ensure!(Jobs::<T>::count() < T::MaxJobs::get(), Error::<T>::JobLimitReached);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			if let Some(para_id) = source_para {
+				Self::take_para_slot(para_id, now)?;
+			}
 
 			let job_id = NextJobId::<T>::get();
 			let next_id = job_id.checked_add(1).ok_or(ArithmeticError::Overflow)?;
 
-			NextJobId::<T>::put(next_id);
+			let deposit = T::JobDeposit::get();
+			let fee = T::ServiceFee::get();
+			T::Currency::hold(&HoldReason::JobDeposit.into(), &who, deposit.saturating_add(fee))?;
 
-			let now = <frame_system::Pallet<T>>::block_number();
+			NextJobId::<T>::put(next_id);
 
-			let info = MixJobInfo::<T::AccountId, BlockNumberFor<T>> {
+			let mut info = MixJobInfoOf::<T> {
 				job_id,
 				requester: who.clone(),
-				source_para: None,
-				election_id,
+				deposit,
+				fee,
+				source_para,
+				kind,
 				created_at: now,
 				status: JobStatus::Pending,
 				last_update: now,
+				last_updated_by: None,
+				claimed_by: None,
+				lease_expires_at: None,
+				next_check: None,
 				error_code: None,
+				result_uri: None,
+				result_hash: None,
+				input_root: None,
+				output_root: None,
 			};
 
+			Self::schedule_next_check(&mut info);
 			Jobs::<T>::insert(job_id, info);
-			LastJobForElection::<T>::insert(election_id, job_id);
+			if let JobKind::Election(election_id) = kind {
+				LastJobForElection::<T>::insert(election_id, job_id);
+			}
 
-			Self::deposit_event(Event::JobSubmitted {
-				job_id,
-				election_id,
-				requester: who,
-			});
+			Self::deposit_event(Event::JobSubmitted { job_id, kind, requester: who, source_para });
 
 			Ok(())
 		}
 
 		/// External updater for job status.
 		///
-		/// Only signed extrinsics are allowed.
+		/// Only the orchestrator holding the job's lease can update it, so Pending jobs have to
+		/// be claimed with `claim_job` first. A Running -> Running update restarts the
+		/// Running timeout. Jobs can only be completed through `complete_job`, so every
+		/// Completed job carries its results.
 		#[pallet::call_index(1)]
-		#[pallet::weight(10_000)]
+		#[pallet::weight(
+			T::WeightInfo::update_job_status().saturating_add(T::CallbackWeight::get())
+		)]
 		pub fn update_job_status(
 			origin: OriginFor<T>,
 			job_id: JobId,
 			new_status: JobStatus,
 			error_code: Option<BoundedVec<u8, ConstU32<256>>>,
 		) -> DispatchResult {
-			let _who = ensure_signed(origin)?;
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_orchestrator(&who), Error::<T>::NotOrchestrator);
+			ensure!(new_status != JobStatus::Completed, Error::<T>::CompletionRequiresResults);
 
 			Jobs::<T>::try_mutate(job_id, |maybe_job| -> DispatchResult {
 				let job = maybe_job.as_mut().ok_or(Error::<T>::JobNotFound)?;
 
-				// Allowed status transitions:
-				// Pending/Running -> Completed/Failed/Running (no backward moves).
-				match (job.status, new_status) {
-					(JobStatus::Pending, JobStatus::Pending)
-					| (JobStatus::Pending, JobStatus::Running)
-					| (JobStatus::Pending, JobStatus::Completed)
-					| (JobStatus::Pending, JobStatus::Failed)
-					| (JobStatus::Running, JobStatus::Running)
-					| (JobStatus::Running, JobStatus::Completed)
-					| (JobStatus::Running, JobStatus::Failed) => { /* allowed */ }
-					_ => return Err(Error::<T>::InvalidStatusTransition.into()),
-				}
+				Self::ensure_transition(job.status, new_status)?;
+
+				let now = <frame_system::Pallet<T>>::block_number();
+				Self::ensure_lease_holder(job, &who, now)?;
 
 				let old_status = job.status;
 
 				job.status = new_status;
-				job.last_update = <frame_system::Pallet<T>>::block_number();
-				job.error_code = error_code;
+				job.last_update = now;
+				job.last_updated_by = Some(who.clone());
+				job.error_code = error_code.map(JobErrorCode::Orchestrator);
 
 				Self::deposit_event(Event::JobStatusUpdated {
 					job_id,
 					old_status: old_status as u8,
 					new_status: job.status as u8,
+					orchestrator: who,
 				});
 
+				if job.status == JobStatus::Failed {
+					Self::job_finished(job);
+				} else {
+					Self::schedule_next_check(job);
+				}
+
 				Ok(())
 			})
 		}
+
+		/// Allow an account to drive job status transitions.
+		///
+		/// Only `UpdateOrigin` can call this.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::add_orchestrator())]
+		pub fn add_orchestrator(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			Orchestrators::<T>::try_mutate(|orchestrators| -> DispatchResult {
+				ensure!(!orchestrators.contains(&who), Error::<T>::AlreadyOrchestrator);
+				orchestrators
+					.try_push(who.clone())
+					.map_err(|_| Error::<T>::TooManyOrchestrators)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::OrchestratorAdded { who });
+
+			Ok(())
+		}
+
+		/// Revoke an account's permission to drive jobs.
+		///
+		/// Only `UpdateOrigin` can call this.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::remove_orchestrator())]
+		pub fn remove_orchestrator(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			Orchestrators::<T>::try_mutate(|orchestrators| -> DispatchResult {
+				let pos = orchestrators
+					.iter()
+					.position(|o| o == &who)
+					.ok_or(Error::<T>::NotOrchestrator)?;
+				orchestrators.remove(pos);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::OrchestratorRemoved { who });
+
+			Ok(())
+		}
+
+		/// Mark a job Completed and store its results.
+		///
+		/// Only the orchestrator holding the job's lease can complete it. The results and the
+		/// Completed status are written together, so a Completed job always carries its outcome.
+		/// The input and output roots are also recorded as the election's mix commitments.
+		#[pallet::call_index(4)]
+		#[pallet::weight(
+			T::WeightInfo::complete_job().saturating_add(T::CallbackWeight::get())
+		)]
+		pub fn complete_job(
+			origin: OriginFor<T>,
+			job_id: JobId,
+			result_uri: ResultUri,
+			result_hash: T::Hash,
+			input_root: T::Hash,
+			output_root: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_orchestrator(&who), Error::<T>::NotOrchestrator);
+
+			Jobs::<T>::try_mutate(job_id, |maybe_job| -> DispatchResult {
+				let job = maybe_job.as_mut().ok_or(Error::<T>::JobNotFound)?;
+
+				Self::ensure_transition(job.status, JobStatus::Completed)?;
+
+				let now = <frame_system::Pallet<T>>::block_number();
+				Self::ensure_lease_holder(job, &who, now)?;
+
+				let old_status = job.status;
+
+				job.status = JobStatus::Completed;
+				job.last_update = now;
+				job.last_updated_by = Some(who.clone());
+				job.error_code = None;
+				job.result_uri = Some(result_uri.clone());
+				job.result_hash = Some(result_hash);
+				job.input_root = Some(input_root);
+				job.output_root = Some(output_root);
+
+				if let JobKind::Election(election_id) = job.kind {
+					T::ElectionProvider::set_mix_commitments(election_id, input_root, output_root)?;
+				}
+
+				Self::deposit_event(Event::JobStatusUpdated {
+					job_id,
+					old_status: old_status as u8,
+					new_status: JobStatus::Completed as u8,
+					orchestrator: who,
+				});
+				Self::deposit_event(Event::JobCompleted {
+					job_id,
+					result_uri,
+					result_hash,
+					input_root,
+					output_root,
+				});
+
+				Self::job_finished(job);
+
+				Ok(())
+			})
+		}
+
+		/// Set or clear the callback call of a sibling parachain.
+		///
+		/// Only `UpdateOrigin` can call this. Jobs from parachains without a callback call
+		/// are not notified.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::set_para_callback())]
+		pub fn set_para_callback(
+			origin: OriginFor<T>,
+			para_id: u32,
+			call_index: Option<[u8; 2]>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			ParaCallbacks::<T>::set(para_id, call_index);
+
+			Self::deposit_event(Event::ParaCallbackSet { para_id, call_index });
+
+			Ok(())
+		}
+
+		/// Allow a sibling parachain to submit jobs, or change its quota.
+		///
+		/// Only `UpdateOrigin` can call this.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::set_para_quota())]
+		pub fn set_para_quota(
+			origin: OriginFor<T>,
+			para_id: u32,
+			quota: ParaQuota<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(!quota.period.is_zero(), Error::<T>::InvalidParaQuota);
+
+			AllowedParas::<T>::insert(para_id, quota);
+
+			Self::deposit_event(Event::ParaQuotaSet { para_id, quota });
+
+			Ok(())
+		}
+
+		/// Remove a sibling parachain from the allowlist.
+		///
+		/// Only `UpdateOrigin` can call this. Jobs it already submitted are unaffected.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::remove_para())]
+		pub fn remove_para(origin: OriginFor<T>, para_id: u32) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(AllowedParas::<T>::contains_key(para_id), Error::<T>::ParaNotAllowed);
+
+			AllowedParas::<T>::remove(para_id);
+
+			Self::deposit_event(Event::ParaRemoved { para_id });
+
+			Ok(())
+		}
+
+		/// Remove a finished job past its retention period and collect `ReapReward`.
+		///
+		/// Anyone can call this. The reward is paid from the pallet account and is skipped
+		/// if that account cannot afford it.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::reap_job())]
+		pub fn reap_job(origin: OriginFor<T>, job_id: JobId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let job = Jobs::<T>::get(job_id).ok_or(Error::<T>::JobNotFound)?;
+			ensure!(
+				matches!(job.status, JobStatus::Completed | JobStatus::Failed),
+				Error::<T>::JobNotFinished
+			);
+			ensure!(
+				Self::retention_elapsed(&job, <frame_system::Pallet<T>>::block_number()),
+				Error::<T>::JobRetained
+			);
+
+			Self::remove_job(&job);
+
+			let pot = T::PalletId::get().into_account_truncating();
+			let reward = T::ReapReward::get();
+			let reward = T::Currency::transfer(&pot, &who, reward, Preservation::Preserve)
+				.unwrap_or_else(|_| Zero::zero());
+
+			Self::deposit_event(Event::JobReaped { job_id, who, reward });
+
+			Ok(())
+		}
+
+		/// Claim a Pending job for `LeaseDuration` blocks and mark it Running.
+		///
+		/// Only registered orchestrators can claim jobs. Until the lease expires, only the
+		/// claiming orchestrator can update, complete or fail the job.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::claim_job())]
+		pub fn claim_job(origin: OriginFor<T>, job_id: JobId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_orchestrator(&who), Error::<T>::NotOrchestrator);
+
+			Jobs::<T>::try_mutate(job_id, |maybe_job| -> DispatchResult {
+				let job = maybe_job.as_mut().ok_or(Error::<T>::JobNotFound)?;
+				ensure!(job.status == JobStatus::Pending, Error::<T>::JobNotPending);
+
+				let now = <frame_system::Pallet<T>>::block_number();
+				let lease_expires_at = now.saturating_add(T::LeaseDuration::get());
+
+				job.status = JobStatus::Running;
+				job.last_update = now;
+				job.last_updated_by = Some(who.clone());
+				job.claimed_by = Some(who.clone());
+				job.lease_expires_at = Some(lease_expires_at);
+
+				Self::schedule_next_check(job);
+
+				Self::deposit_event(Event::JobClaimed {
+					job_id,
+					orchestrator: who,
+					lease_expires_at,
+				});
+
+				Ok(())
+			})
+		}
+
+		/// Extend the caller's lease on a Running job by `LeaseDuration` blocks from now.
+		///
+		/// Renewing does not count as a status update for the Running timeout.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::renew_lease())]
+		pub fn renew_lease(origin: OriginFor<T>, job_id: JobId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_orchestrator(&who), Error::<T>::NotOrchestrator);
+
+			Jobs::<T>::try_mutate(job_id, |maybe_job| -> DispatchResult {
+				let job = maybe_job.as_mut().ok_or(Error::<T>::JobNotFound)?;
+				ensure!(job.status == JobStatus::Running, Error::<T>::InvalidStatusTransition);
+
+				let now = <frame_system::Pallet<T>>::block_number();
+				Self::ensure_lease_holder(job, &who, now)?;
+
+				let lease_expires_at = now.saturating_add(T::LeaseDuration::get());
+				job.lease_expires_at = Some(lease_expires_at);
+
+				Self::schedule_next_check(job);
+
+				Self::deposit_event(Event::LeaseRenewed { job_id, lease_expires_at });
+
+				Ok(())
+			})
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Jobs with `status` and an id in `start..start + limit`, in id order.
+		///
+		/// Used by the `DaoMixApi` runtime API. `limit` is capped at `MAX_JOBS_PER_PAGE`, so
+		/// callers page through the ids below `NextJobId` by advancing `start` by `limit`.
+		pub fn jobs_with_status(
+			status: JobStatus,
+			start: JobId,
+			limit: u32,
+		) -> Vec<MixJobInfoOf<T>> {
+			let end = start
+				.saturating_add(limit.min(MAX_JOBS_PER_PAGE).into())
+				.min(NextJobId::<T>::get());
+
+			(start..end)
+				.filter_map(Jobs::<T>::get)
+				.filter(|job| job.status == status)
+				.collect()
+		}
+
+		/// Stored jobs of an election, in id order.
+		///
+		/// Used by the `DaoMixApi` runtime API; iterates all stored jobs, at most `MaxJobs`.
+		pub fn jobs_for_election(election_id: u32) -> Vec<MixJobInfoOf<T>> {
+			let mut jobs: Vec<_> = Jobs::<T>::iter_values()
+				.filter(|job| job.kind == JobKind::Election(election_id))
+				.collect();
+			jobs.sort_by_key(|job| job.job_id);
+			jobs
+		}
+
+		/// Whether `who` is allowed to drive job status transitions.
+		pub fn is_orchestrator(who: &T::AccountId) -> bool {
+			Orchestrators::<T>::get().contains(who)
+		}
+
+		/// Count a new job against the quota of an allowlisted sibling parachain.
+		fn take_para_slot(para_id: u32, now: BlockNumberFor<T>) -> DispatchResult {
+			let quota = AllowedParas::<T>::get(para_id).ok_or(Error::<T>::ParaNotAllowed)?;
+
+			ParaUsageOf::<T>::try_mutate(para_id, |usage| -> DispatchResult {
+				ensure!(
+					usage.active_jobs < quota.max_active_jobs,
+					Error::<T>::TooManyActiveParaJobs
+				);
+
+				// A window starts with the first submission after the previous one ended.
+				if usage.submitted_in_window == 0 ||
+					now >= usage.window_start.saturating_add(quota.period)
+				{
+					usage.window_start = now;
+					usage.submitted_in_window = 0;
+				}
+				ensure!(
+					usage.submitted_in_window < quota.max_jobs_per_period,
+					Error::<T>::ParaRateLimited
+				);
+
+				usage.active_jobs.saturating_inc();
+				usage.submitted_in_window.saturating_inc();
+				Ok(())
+			})
+		}
+
+		/// Ensure `who` holds an unexpired lease on `job`.
+		fn ensure_lease_holder(
+			job: &MixJobInfoOf<T>,
+			who: &T::AccountId,
+			now: BlockNumberFor<T>,
+		) -> DispatchResult {
+			ensure!(job.claimed_by.as_ref() == Some(who), Error::<T>::NotLeaseHolder);
+			ensure!(
+				job.lease_expires_at.is_some_and(|expires_at| now < expires_at),
+				Error::<T>::LeaseExpired
+			);
+			Ok(())
+		}
+
+		/// Block at which a Pending or Running job times out.
+		///
+		/// A Pending job keeps the deadline of its submission, so claiming it and letting the
+		/// lease expire never extends its time in the queue.
+		fn timeout_at(job: &MixJobInfoOf<T>) -> Option<BlockNumberFor<T>> {
+			match job.status {
+				JobStatus::Pending =>
+					Some(job.created_at.saturating_add(T::PendingTimeout::get())),
+				JobStatus::Running =>
+					Some(job.last_update.saturating_add(T::RunningTimeout::get())),
+				JobStatus::Completed | JobStatus::Failed => None,
+			}
+		}
+
+		/// Block at which a Pending or Running job times out or loses its lease, whichever
+		/// comes first.
+		fn check_due(job: &MixJobInfoOf<T>) -> Option<BlockNumberFor<T>> {
+			let timeout = Self::timeout_at(job)?;
+			Some(job.lease_expires_at.map_or(timeout, |expires_at| expires_at.min(timeout)))
+		}
+
+		/// Move the check of `job` to the block its timeout or lease expiry is due, or drop
+		/// it once the job is finished.
+		///
+		/// A job is listed in `TimeoutSchedule` once, so rescheduling never adds duplicates.
+		/// When the due block is full the check moves to the next block with room, where it
+		/// still catches the timeout. Each job holds at most one slot and at most `MaxJobs`
+		/// jobs exist, so room is found within `MaxJobs / MaxTimeoutsPerBlock + 1` blocks. A
+		/// check already overdue, as for jobs migrated past their Pending timeout, is listed at
+		/// the current block.
+		pub(crate) fn schedule_next_check(job: &mut MixJobInfoOf<T>) {
+			let job_id = job.job_id;
+			if let Some(at) = job.next_check.take() {
+				TimeoutSchedule::<T>::mutate_exists(at, |due| {
+					if let Some(jobs) = due {
+						jobs.retain(|id| *id != job_id);
+						if jobs.is_empty() {
+							*due = None;
+						}
+					}
+				});
+			}
+
+			let Some(due) = Self::check_due(job) else { return };
+			let mut at = due.max(<frame_system::Pallet<T>>::block_number());
+			let blocks = T::MaxJobs::get() / T::MaxTimeoutsPerBlock::get().max(1);
+			for _ in 0..=blocks {
+				if TimeoutSchedule::<T>::try_mutate(at, |due| due.try_push(job_id)).is_ok() {
+					job.next_check = Some(at);
+					return;
+				}
+				at.saturating_inc();
+			}
+		}
+
+		/// Return a Running job whose lease expired at block `now` to Pending, and fail a job
+		/// that has stayed Pending or Running past its timeout.
+		///
+		/// A job returned to Pending keeps its Pending timeout, so it fails at once if that has
+		/// passed. A job that is neither timed out nor past its lease is checked again when the
+		/// earlier of the two is due. Returns whether a failed job was sent back to its source
+		/// parachain.
+		fn time_out_job(job_id: JobId, now: BlockNumberFor<T>) -> bool {
+			Jobs::<T>::mutate(job_id, |maybe_job| {
+				let Some(job) = maybe_job else { return false };
+				// `on_initialize` already took the job out of this block's schedule.
+				if job.next_check == Some(now) {
+					job.next_check = None;
+				}
+
+				if job.status == JobStatus::Running &&
+					job.lease_expires_at.is_some_and(|expires_at| now >= expires_at)
+				{
+					let orchestrator = job.claimed_by.take();
+					job.status = JobStatus::Pending;
+					job.last_update = now;
+					job.lease_expires_at = None;
+
+					if let Some(orchestrator) = orchestrator {
+						Self::deposit_event(Event::LeaseExpired { job_id, orchestrator });
+					}
+				}
+
+				let Some(timeout_at) = Self::timeout_at(job) else { return false };
+				if now < timeout_at {
+					Self::schedule_next_check(job);
+					return false;
+				}
+
+				let old_status = job.status;
+
+				job.status = JobStatus::Failed;
+				job.last_update = now;
+				job.error_code = Some(JobErrorCode::TimedOut);
+
+				Self::deposit_event(Event::JobTimedOut { job_id, old_status: old_status as u8 });
+
+				Self::job_finished(job);
+				job.source_para.is_some()
+			})
+		}
+
+		/// Bookkeeping once a job reaches Completed or Failed.
+		fn job_finished(job: &mut MixJobInfoOf<T>) {
+			Self::schedule_next_check(job);
+			if let Some(para_id) = job.source_para {
+				ParaUsageOf::<T>::mutate(para_id, |usage| usage.active_jobs.saturating_dec());
+			}
+			ArchiveQueueRange::<T>::mutate(|(_, tail)| {
+				ArchiveQueue::<T>::insert(*tail, job.job_id);
+				tail.saturating_inc();
+			});
+			Self::settle_funds(job);
+			Self::notify_source_para(job);
+		}
+
+		/// Release the deposit and fee held for a finished job.
+		///
+		/// A completed job pays its fee to the orchestrator that completed it. A job failed by
+		/// an orchestrator loses `FailureSlash` of its deposit to the pallet account. Whatever
+		/// cannot be paid out is released back to the requester.
+		fn settle_funds(job: &MixJobInfoOf<T>) {
+			let reason = HoldReason::JobDeposit.into();
+			let pot = T::PalletId::get().into_account_truncating();
+
+			let (payee, amount) = match (job.status, &job.error_code) {
+				(JobStatus::Completed, _) => (job.last_updated_by.clone(), job.fee),
+				(JobStatus::Failed, Some(JobErrorCode::TimedOut)) => (None, Zero::zero()),
+				(JobStatus::Failed, _) => (Some(pot), T::FailureSlash::get() * job.deposit),
+				(JobStatus::Pending | JobStatus::Running, _) => (None, Zero::zero()),
+			};
+
+			let mut paid = Zero::zero();
+			if let Some(payee) = payee.filter(|_| !amount.is_zero()) {
+				paid = T::Currency::transfer_on_hold(
+					&reason,
+					&job.requester,
+					&payee,
+					amount,
+					Precision::BestEffort,
+					tokens::Restriction::Free,
+					Fortitude::Polite,
+				)
+				.unwrap_or_else(|_| Zero::zero());
+
+				if !paid.is_zero() {
+					if job.status == JobStatus::Completed {
+						Self::deposit_event(Event::ServiceFeePaid {
+							job_id: job.job_id,
+							orchestrator: payee,
+							fee: paid,
+						});
+					} else {
+						Self::deposit_event(Event::DepositSlashed {
+							job_id: job.job_id,
+							requester: job.requester.clone(),
+							amount: paid,
+						});
+					}
+				}
+			}
+
+			let remaining = job.deposit.saturating_add(job.fee).saturating_sub(paid);
+			let _ = T::Currency::release(&reason, &job.requester, remaining, Precision::BestEffort);
+		}
+
+		/// Ensure the target of a new job exists and can be mixed.
+		fn ensure_target(kind: JobKind) -> DispatchResult {
+			match kind {
+				JobKind::Election(election_id) => {
+					ensure!(
+						T::ElectionProvider::election_exists(election_id),
+						Error::<T>::ElectionNotFound
+					);
+					ensure!(
+						T::ElectionProvider::is_ready_for_mixing(election_id),
+						Error::<T>::ElectionNotReadyForMixing
+					);
+				},
+				JobKind::MessageBatch(batch_id) => ensure!(
+					T::MessageBatches::batch_exists(batch_id),
+					Error::<T>::MessageBatchNotFound
+				),
+				JobKind::PublicationBatch(batch_id) => ensure!(
+					T::PublicationBatches::batch_exists(batch_id),
+					Error::<T>::PublicationBatchNotFound
+				),
+			}
+			Ok(())
+		}
+
+		/// Whether a finished job has been kept for `JobRetentionPeriod` blocks.
+		fn retention_elapsed(job: &MixJobInfoOf<T>, now: BlockNumberFor<T>) -> bool {
+			now >= job.last_update.saturating_add(T::JobRetentionPeriod::get())
+		}
+
+		/// Remove a job and its election index entry.
+		fn remove_job(job: &MixJobInfoOf<T>) {
+			Jobs::<T>::remove(job.job_id);
+			if let JobKind::Election(election_id) = job.kind {
+				if LastJobForElection::<T>::get(election_id) == Some(job.job_id) {
+					LastJobForElection::<T>::remove(election_id);
+				}
+			}
+		}
+
+		/// Archive jobs from the head of `ArchiveQueue` until one is still retained or
+		/// `limit` is used up.
+		///
+		/// Finished jobs never change again, so the queue is ordered by retention expiry.
+		/// Entries of jobs already reaped are dropped.
+		fn archive_expired_jobs(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+			let db = T::DbWeight::get();
+			let per_job = db.reads_writes(3, 5);
+			let mut used = db.reads_writes(1, 1);
+			if used.any_gt(limit) {
+				return Weight::zero();
+			}
+
+			let (start, tail) = ArchiveQueueRange::<T>::get();
+			let mut head = start;
+			while head < tail && used.saturating_add(per_job).all_lte(limit) {
+				used.saturating_accrue(per_job);
+
+				if let Some(job) = ArchiveQueue::<T>::get(head).and_then(Jobs::<T>::get) {
+					if !Self::retention_elapsed(&job, now) {
+						break;
+					}
+					Self::remove_job(&job);
+					Self::deposit_event(Event::JobArchived { job_id: job.job_id });
+				}
+				ArchiveQueue::<T>::remove(head);
+				head.saturating_inc();
+			}
+
+			if head != start {
+				ArchiveQueueRange::<T>::put((head, tail));
+			}
+			used
+		}
+
+		/// Send the outcome of a finished job back to the parachain that submitted it.
+		///
+		/// Delivery fees are charged to `CallbackFeeSource`. A failed callback never fails
+		/// the status transition; it is reported through `CallbackFailed` instead.
+		fn notify_source_para(job: &MixJobInfoOf<T>) {
+			let Some(para_id) = job.source_para else { return };
+			let Some(call_index) = ParaCallbacks::<T>::get(para_id) else { return };
+
+			let mut call = call_index.to_vec();
+			(job.job_id, job.status, job.result_hash, job.result_uri.clone())
+				.encode_to(&mut call);
+
+			let message = Xcm(sp_std::vec![
+				Instruction::UnpaidExecution {
+					weight_limit: WeightLimit::Unlimited,
+					check_origin: None,
+				},
+				Instruction::Transact {
+					origin_kind: OriginKind::Native,
+					fallback_max_weight: None,
+					call: call.into(),
+				},
+			]);
+			let dest = Location::new(1, [Junction::Parachain(para_id)]);
+
+			let sent = validate_send::<T::XcmRouter>(dest, message).ok().and_then(
+				|(ticket, price)| {
+					T::XcmExecutor::charge_fees(T::CallbackFeeSource::get(), price).ok()?;
+					T::XcmRouter::deliver(ticket).ok()
+				},
+			);
+
+			match sent {
+				Some(message_id) => Self::deposit_event(Event::CallbackSent {
+					job_id: job.job_id,
+					para_id,
+					message_id,
+				}),
+				None => {
+					Self::deposit_event(Event::CallbackFailed { job_id: job.job_id, para_id })
+				},
+			}
+		}
+
+		/// Allowed status transitions:
+		/// Pending -> Running/Failed, Running -> Running/Completed/Failed (no backward moves).
+		///
+		/// A job is only completed from Running, by the orchestrator that claimed it. The
+		/// return of an expired lease to Pending is not a transition made through this check.
+		fn ensure_transition(from: JobStatus, to: JobStatus) -> DispatchResult {
+			match (from, to) {
+				(JobStatus::Pending, JobStatus::Running)
+				| (JobStatus::Pending, JobStatus::Failed)
+				| (JobStatus::Running, JobStatus::Running)
+				| (JobStatus::Running, JobStatus::Completed)
+				| (JobStatus::Running, JobStatus::Failed) => Ok(()),
+				_ => Err(Error::<T>::InvalidStatusTransition.into()),
+			}
+		}
 	}
 }
 
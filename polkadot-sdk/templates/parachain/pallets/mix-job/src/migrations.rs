@@ -0,0 +1,120 @@
+//! Storage migrations of the MixJob pallet.
+
+use crate::pallet::*;
+use frame::{
+	deps::frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade},
+	prelude::*,
+};
+use sp_std::vec::Vec;
+
+/// Storage layouts of version 0, before jobs had kinds, deposits and leases.
+mod v0 {
+	use super::*;
+
+	/// A job of version 0.
+	#[derive(Encode, Decode)]
+	pub struct MixJobInfo<AccountId, BlockNumber> {
+		pub job_id: u64,
+		pub requester: AccountId,
+		pub source_para: Option<u32>,
+		pub election_id: u32,
+		pub created_at: BlockNumber,
+		pub status: JobStatus,
+		pub last_update: BlockNumber,
+		pub error_code: Option<BoundedVec<u8, ConstU32<256>>>,
+	}
+}
+
+/// Migrates jobs from version 0 to version 1.
+///
+/// Every job of version 0 mixes an election and holds no deposit or fee. Jobs had no leases, so
+/// Running jobs return to Pending to be claimed, and unfinished jobs keep the Pending timeout of
+/// their submission; those already past it time out in the upgrade block. Finished jobs are
+/// queued for archival. Version 0 only took signed jobs, so no parachain usage is counted.
+pub struct InnerMigrateV0ToV1<T>(PhantomData<T>);
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut migrated = Vec::new();
+
+		Jobs::<T>::translate::<v0::MixJobInfo<T::AccountId, BlockNumberFor<T>>, _>(|_, old| {
+			let status = match old.status {
+				JobStatus::Running => JobStatus::Pending,
+				status => status,
+			};
+			let last_update = match status {
+				JobStatus::Pending => now,
+				_ => old.last_update,
+			};
+			migrated.push((old.job_id, status));
+
+			Some(MixJobInfo {
+				job_id: old.job_id,
+				requester: old.requester,
+				deposit: Zero::zero(),
+				fee: Zero::zero(),
+				source_para: old.source_para,
+				kind: JobKind::Election(old.election_id),
+				created_at: old.created_at,
+				status,
+				last_update,
+				last_updated_by: None,
+				claimed_by: None,
+				lease_expires_at: None,
+				next_check: None,
+				error_code: old.error_code.map(JobErrorCode::Orchestrator),
+				result_uri: None,
+				result_hash: None,
+				input_root: None,
+				output_root: None,
+			})
+		});
+		let counted = Jobs::<T>::initialize_counter();
+
+		// Queue finished jobs in id order, the order they were submitted in.
+		migrated.sort_by_key(|(job_id, _)| *job_id);
+		ArchiveQueueRange::<T>::mutate(|(_, tail)| {
+			for (job_id, status) in &migrated {
+				if *status == JobStatus::Pending {
+					Jobs::<T>::mutate(job_id, |job| {
+						if let Some(job) = job {
+							Pallet::<T>::schedule_next_check(job);
+						}
+					});
+				} else {
+					ArchiveQueue::<T>::insert(*tail, *job_id);
+					tail.saturating_inc();
+				}
+			}
+		});
+
+		// Per job: the translation, counting it, then scheduling or queueing it.
+		let jobs = u64::from(counted);
+		let accesses = jobs.saturating_mul(3).saturating_add(2);
+		T::DbWeight::get().reads_writes(accesses, accesses)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), frame::try_runtime::TryRuntimeError> {
+		ensure!(
+			Jobs::<T>::iter_keys().all(|job_id| Jobs::<T>::try_get(job_id).is_ok()),
+			"a job does not decode after the migration"
+		);
+		ensure!(
+			Jobs::<T>::iter_keys().count() as u32 == Jobs::<T>::count(),
+			"the job counter does not match the stored jobs"
+		);
+		Ok(())
+	}
+}
+
+/// `InnerMigrateV0ToV1`, run only while the on-chain storage version is 0, which it then sets
+/// to 1.
+pub type MigrateV0ToV1<T> = VersionedMigration<
+	0,
+	1,
+	InnerMigrateV0ToV1<T>,
+	Pallet<T>,
+	<T as frame_system::Config>::DbWeight,
+>;
@@ -3,6 +3,11 @@ use frame::{
 	prelude::*,
 	runtime::prelude::*,
 	testing_prelude::*,
+	traits::ConvertInto,
+};
+use xcm::latest::{
+	Assets, ExecuteXcm, InstructionError, Location, Outcome, Result as XcmResult, SendError,
+	SendResult, SendXcm, Xcm, XcmHash,
 };
 
 // Configure a mock runtime to test the pallet.
@@ -27,6 +32,8 @@ mod test_runtime {
 	pub type System = frame_system;
 	#[runtime::pallet_index(1)]
 	pub type MixJob = crate;
+	#[runtime::pallet_index(2)]
+	pub type Balances = pallet_balances;
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
@@ -35,14 +42,208 @@ impl frame_system::Config for Test {
 	type Block = MockBlock<Test>;
 	type BlockHashCount = ConstU64<250>;
 	type DbWeight = RocksDbWeight;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+/// Election id the mock provider does not know about.
+pub const UNKNOWN_ELECTION: u32 = 999;
+/// Election id the mock provider reports as still in voting.
+pub const VOTING_ELECTION: u32 = 998;
+
+parameter_types! {
+	/// Mix commitments forwarded to the election provider.
+	pub static ElectionCommitments: Vec<(u32, H256, H256)> = vec![];
+}
+
+/// Election provider where every election except the two above is ready for mixing.
+pub struct MockElectionProvider;
+impl crate::ElectionProvider<H256> for MockElectionProvider {
+	fn election_exists(election_id: u32) -> bool {
+		election_id != UNKNOWN_ELECTION
+	}
+
+	fn is_ready_for_mixing(election_id: u32) -> bool {
+		election_id != VOTING_ELECTION
+	}
+
+	fn set_mix_commitments(
+		election_id: u32,
+		input_root: H256,
+		output_root: H256,
+	) -> DispatchResult {
+		ElectionCommitments::mutate(|c| c.push((election_id, input_root, output_root)));
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn setup_ready_election(_: u32) -> H256 {
+		H256::zero()
+	}
+}
+
+/// Batch id the mock message and publication providers do not know about.
+pub const UNKNOWN_BATCH: u64 = 999;
+
+/// Message and publication batch provider knowing every batch except `UNKNOWN_BATCH`.
+pub struct MockBatches;
+impl crate::MessageBatchProvider for MockBatches {
+	fn batch_exists(batch_id: u64) -> bool {
+		batch_id != UNKNOWN_BATCH
+	}
+}
+
+impl crate::PublicationBatchProvider for MockBatches {
+	fn batch_exists(batch_id: u64) -> bool {
+		batch_id != UNKNOWN_BATCH
+	}
+}
+
+parameter_types! {
+	/// Messages delivered by `TestXcmRouter`.
+	pub static SentXcm: Vec<(Location, Xcm<()>)> = vec![];
+	/// Location charged for callback delivery fees.
+	pub CallbackFeeSource: Location = Location::here();
+	/// Weight charged for each callback.
+	pub const CallbackWeight: Weight = Weight::from_parts(1_000, 0);
+}
+
+/// Router that records every delivered message.
+pub struct TestXcmRouter;
+impl SendXcm for TestXcmRouter {
+	type Ticket = (Location, Xcm<()>);
+
+	fn validate(
+		dest: &mut Option<Location>,
+		msg: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let ticket = (dest.take().ok_or(SendError::MissingArgument)?, msg.take().unwrap());
+		Ok((ticket, Assets::new()))
+	}
+
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		let hash = ticket.1.using_encoded(blake2_256);
+		SentXcm::mutate(|sent| sent.push(ticket));
+		Ok(hash)
+	}
+}
+
+/// Executor that only supports charging (free) delivery fees.
+pub struct TestXcmExecutor;
+impl ExecuteXcm<RuntimeCall> for TestXcmExecutor {
+	type Prepared = <() as ExecuteXcm<RuntimeCall>>::Prepared;
+
+	fn prepare(
+		message: Xcm<RuntimeCall>,
+		weight_limit: Weight,
+	) -> Result<Self::Prepared, InstructionError> {
+		<() as ExecuteXcm<RuntimeCall>>::prepare(message, weight_limit)
+	}
+
+	fn execute(
+		origin: impl Into<Location>,
+		pre: Self::Prepared,
+		id: &mut XcmHash,
+		weight_credit: Weight,
+	) -> Outcome {
+		<() as ExecuteXcm<RuntimeCall>>::execute(origin, pre, id, weight_credit)
+	}
+
+	fn charge_fees(_: impl Into<Location>, _: Assets) -> XcmResult {
+		Ok(())
+	}
+}
+
+parameter_types! {
+	pub const MixJobPalletId: PalletId = PalletId(*b"dm/mixjb");
 }
 
+/// Deposit held for each job in the mock.
+pub const JOB_DEPOSIT: u64 = 10;
+/// Service fee held for each job in the mock.
+pub const SERVICE_FEE: u64 = 4;
+/// Balance `new_test_ext` gives accounts 1, 2 and the sovereign account of `SIBLING_PARA`.
+pub const INITIAL_BALANCE: u64 = 1_000;
+
+parameter_types! {
+	/// Part of the deposit slashed when an orchestrator fails a job.
+	pub FailureSlash: Perbill = Perbill::from_percent(50);
+}
+
+/// Blocks a finished job is kept in the mock.
+pub const RETENTION: u64 = 10;
+/// Reward paid for reaping a job in the mock.
+pub const REAP_REWARD: u64 = 5;
+/// Blocks a job may stay Pending in the mock.
+pub const PENDING_TIMEOUT: u64 = 20;
+/// Blocks a job may stay Running without an update in the mock.
+pub const RUNNING_TIMEOUT: u64 = 5;
+/// Blocks an orchestrator holds a claimed job in the mock.
+pub const LEASE_DURATION: u64 = 10;
+
 impl crate::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type MaxJobs = ConstU32<10>; // Small limit for testing
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type Currency = Balances;
+	type JobDeposit = ConstU64<JOB_DEPOSIT>;
+	type ServiceFee = ConstU64<SERVICE_FEE>;
+	type FailureSlash = FailureSlash;
+	type PalletId = MixJobPalletId;
+	type JobRetentionPeriod = ConstU64<RETENTION>;
+	type ReapReward = ConstU64<REAP_REWARD>;
+	type PendingTimeout = ConstU64<PENDING_TIMEOUT>;
+	type RunningTimeout = ConstU64<RUNNING_TIMEOUT>;
+	type LeaseDuration = ConstU64<LEASE_DURATION>;
+	type MaxTimeoutsPerBlock = ConstU32<4>; // Less than MaxJobs to test spilling
+	type UpdateOrigin = EnsureRoot<u64>;
+	type MaxOrchestrators = ConstU32<4>;
+	type ElectionProvider = MockElectionProvider;
+	type MessageBatches = MockBatches;
+	type PublicationBatches = MockBatches;
+	// Root stands in for a sibling parachain's native XCM origin.
+	type XcmOrigin = EnsureRootWithSuccess<u64, ConstU32<SIBLING_PARA>>;
+	type SovereignAccountOf = ConvertInto;
+	type ParaOfSovereignAccount = MockSovereignAccounts;
+	type XcmRouter = TestXcmRouter;
+	type XcmExecutor = TestXcmExecutor;
+	type CallbackFeeSource = CallbackFeeSource;
+	type CallbackWeight = CallbackWeight;
+	type WeightInfo = ();
 }
 
+/// Para id reported for jobs submitted through `XcmOrigin`.
+pub const SIBLING_PARA: u32 = 2000;
+
+/// Accounts from 1000 up are the sovereign accounts of the para ids they equal, matching
+/// `ConvertInto` as `SovereignAccountOf`.
+pub struct MockSovereignAccounts;
+impl Convert<u64, Option<u32>> for MockSovereignAccounts {
+	fn convert(who: u64) -> Option<u32> {
+		u32::try_from(who).ok().filter(|para_id| *para_id >= 1_000)
+	}
+}
+
+/// Quota of `SIBLING_PARA` set by `new_test_ext`.
+pub const SIBLING_QUOTA: crate::ParaQuota<u64> =
+	crate::ParaQuota { max_active_jobs: 3, max_jobs_per_period: 5, period: 10 };
+
+/// Account registered as an orchestrator by `new_test_ext`.
+pub const ORCHESTRATOR: u64 = 1;
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> TestState {
-	GenesisConfig::<Test>::default().build_storage().unwrap().into()
+	let mut ext: TestState = GenesisConfig::<Test>::default().build_storage().unwrap().into();
+	ext.execute_with(|| {
+		for who in [1, 2, SIBLING_PARA as u64] {
+			assert_ok!(<Balances as fungible::Mutate<u64>>::mint_into(&who, INITIAL_BALANCE));
+		}
+		assert_ok!(MixJob::add_orchestrator(RuntimeOrigin::root(), ORCHESTRATOR));
+		assert_ok!(MixJob::set_para_quota(RuntimeOrigin::root(), SIBLING_PARA, SIBLING_QUOTA));
+	});
+	ext
 }
\ No newline at end of file
@@ -0,0 +1,49 @@
+//! Traits connecting the MixJob pallet to the rest of the runtime.
+
+use frame::prelude::*;
+
+/// Source of the elections that mix jobs operate on.
+///
+/// Implemented by the DaoMix Voting pallet.
+pub trait ElectionProvider<Hash> {
+	/// Whether an election with this id exists.
+	fn election_exists(election_id: u32) -> bool;
+
+	/// Whether voting has closed and the election is waiting for its mix commitments.
+	fn is_ready_for_mixing(election_id: u32) -> bool;
+
+	/// Record the Merkle roots produced by a completed mix job on the election.
+	fn set_mix_commitments(
+		election_id: u32,
+		input_root: Hash,
+		output_root: Hash,
+	) -> DispatchResult;
+
+	/// Create election `election_id` ready for mixing and return its input root.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn setup_ready_election(election_id: u32) -> Hash;
+}
+
+/// Source of the mailbox message batches that mix jobs operate on.
+pub trait MessageBatchProvider {
+	/// Whether a message batch with this id exists.
+	fn batch_exists(batch_id: u64) -> bool;
+}
+
+impl MessageBatchProvider for () {
+	fn batch_exists(_batch_id: u64) -> bool {
+		false
+	}
+}
+
+/// Source of the publication batches that mix jobs operate on.
+pub trait PublicationBatchProvider {
+	/// Whether a publication batch with this id exists.
+	fn batch_exists(batch_id: u64) -> bool;
+}
+
+impl PublicationBatchProvider for () {
+	fn batch_exists(_batch_id: u64) -> bool {
+		false
+	}
+}
@@ -0,0 +1,237 @@
+//! Weights for pallet_mix_job.
+//!
+//! None of these figures were measured: they are hand-set upper estimates, kept in the layout of
+//! generated weights so that the output of
+//! `frame-omni-bencher v1 benchmark pallet --pallet pallet_mix_job` can replace this file as is.
+//!
+//! Every call has a benchmark (see `benchmarking.rs`), run on its worst case: a job mixing an
+//! election, submitted by a sibling parachain, driven by the last of `MaxOrchestrators`
+//! orchestrators. `update_job_status` and `complete_job` exclude the result callback to the
+//! sibling, which the pallet charges on top as `Config::CallbackWeight`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame::{deps::frame_support::weights::constants::RocksDbWeight, prelude::*};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_mix_job.
+pub trait WeightInfo {
+	fn submit_job() -> Weight;
+	fn update_job_status() -> Weight;
+	fn add_orchestrator() -> Weight;
+	fn remove_orchestrator() -> Weight;
+	fn complete_job() -> Weight;
+	fn set_para_callback() -> Weight;
+	fn set_para_quota() -> Weight;
+	fn remove_para() -> Weight;
+	fn reap_job() -> Weight;
+	fn claim_job() -> Weight;
+	fn renew_lease() -> Weight;
+}
+
+/// Estimated weights for pallet_mix_job, see the module docs.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: MixJob CounterForJobs (r:1 w:1)
+	/// Storage: MixJob AllowedParas (r:1 w:0)
+	/// Storage: MixJob ParaUsageOf (r:1 w:1)
+	/// Storage: MixJob NextJobId (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: MixJob TimeoutSchedule (r:1 w:1)
+	/// Storage: MixJob Jobs (r:0 w:1)
+	/// Storage: MixJob LastJobForElection (r:0 w:1)
+	fn submit_job() -> Weight {
+		Weight::from_parts(60_000_000, 8_000)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: MixJob Orchestrators (r:1 w:0)
+	/// Storage: MixJob Jobs (r:1 w:1)
+	/// Storage: MixJob TimeoutSchedule (r:1 w:1)
+	/// Storage: MixJob ParaUsageOf (r:1 w:1)
+	/// Storage: MixJob ArchiveQueueRange (r:1 w:1)
+	/// Storage: MixJob ArchiveQueue (r:0 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: MixJob ParaCallbacks (r:1 w:0)
+	fn update_job_status() -> Weight {
+		Weight::from_parts(70_000_000, 9_000)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: MixJob Orchestrators (r:1 w:1)
+	fn add_orchestrator() -> Weight {
+		Weight::from_parts(15_000_000, 1_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MixJob Orchestrators (r:1 w:1)
+	fn remove_orchestrator() -> Weight {
+		Weight::from_parts(15_000_000, 1_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MixJob Orchestrators (r:1 w:0)
+	/// Storage: MixJob Jobs (r:1 w:1)
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting InputRoots (r:1 w:0)
+	/// Storage: MixJob TimeoutSchedule (r:1 w:1)
+	/// Storage: MixJob ParaUsageOf (r:1 w:1)
+	/// Storage: MixJob ArchiveQueueRange (r:1 w:1)
+	/// Storage: MixJob ArchiveQueue (r:0 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: MixJob ParaCallbacks (r:1 w:0)
+	fn complete_job() -> Weight {
+		Weight::from_parts(80_000_000, 10_000)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: MixJob ParaCallbacks (r:0 w:1)
+	fn set_para_callback() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MixJob AllowedParas (r:0 w:1)
+	fn set_para_quota() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MixJob AllowedParas (r:1 w:1)
+	fn remove_para() -> Weight {
+		Weight::from_parts(12_000_000, 1_500)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MixJob Jobs (r:1 w:1)
+	/// Storage: MixJob CounterForJobs (r:1 w:1)
+	/// Storage: MixJob LastJobForElection (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn reap_job() -> Weight {
+		Weight::from_parts(50_000_000, 6_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: MixJob Orchestrators (r:1 w:0)
+	/// Storage: MixJob Jobs (r:1 w:1)
+	/// Storage: MixJob TimeoutSchedule (r:2 w:2)
+	fn claim_job() -> Weight {
+		Weight::from_parts(30_000_000, 6_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: MixJob Orchestrators (r:1 w:0)
+	/// Storage: MixJob Jobs (r:1 w:1)
+	/// Storage: MixJob TimeoutSchedule (r:2 w:2)
+	fn renew_lease() -> Weight {
+		Weight::from_parts(30_000_000, 6_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: DaomixVoting Elections (r:1 w:0)
+	/// Storage: MixJob CounterForJobs (r:1 w:1)
+	/// Storage: MixJob AllowedParas (r:1 w:0)
+	/// Storage: MixJob ParaUsageOf (r:1 w:1)
+	/// Storage: MixJob NextJobId (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: MixJob TimeoutSchedule (r:1 w:1)
+	/// Storage: MixJob Jobs (r:0 w:1)
+	/// Storage: MixJob LastJobForElection (r:0 w:1)
+	fn submit_job() -> Weight {
+		Weight::from_parts(60_000_000, 8_000)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: MixJob Orchestrators (r:1 w:0)
+	/// Storage: MixJob Jobs (r:1 w:1)
+	/// Storage: MixJob TimeoutSchedule (r:1 w:1)
+	/// Storage: MixJob ParaUsageOf (r:1 w:1)
+	/// Storage: MixJob ArchiveQueueRange (r:1 w:1)
+	/// Storage: MixJob ArchiveQueue (r:0 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: MixJob ParaCallbacks (r:1 w:0)
+	fn update_job_status() -> Weight {
+		Weight::from_parts(70_000_000, 9_000)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: MixJob Orchestrators (r:1 w:1)
+	fn add_orchestrator() -> Weight {
+		Weight::from_parts(15_000_000, 1_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MixJob Orchestrators (r:1 w:1)
+	fn remove_orchestrator() -> Weight {
+		Weight::from_parts(15_000_000, 1_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MixJob Orchestrators (r:1 w:0)
+	/// Storage: MixJob Jobs (r:1 w:1)
+	/// Storage: DaomixVoting Elections (r:1 w:1)
+	/// Storage: DaomixVoting InputRoots (r:1 w:0)
+	/// Storage: MixJob TimeoutSchedule (r:1 w:1)
+	/// Storage: MixJob ParaUsageOf (r:1 w:1)
+	/// Storage: MixJob ArchiveQueueRange (r:1 w:1)
+	/// Storage: MixJob ArchiveQueue (r:0 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: MixJob ParaCallbacks (r:1 w:0)
+	fn complete_job() -> Weight {
+		Weight::from_parts(80_000_000, 10_000)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Storage: MixJob ParaCallbacks (r:0 w:1)
+	fn set_para_callback() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MixJob AllowedParas (r:0 w:1)
+	fn set_para_quota() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MixJob AllowedParas (r:1 w:1)
+	fn remove_para() -> Weight {
+		Weight::from_parts(12_000_000, 1_500)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MixJob Jobs (r:1 w:1)
+	/// Storage: MixJob CounterForJobs (r:1 w:1)
+	/// Storage: MixJob LastJobForElection (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn reap_job() -> Weight {
+		Weight::from_parts(50_000_000, 6_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: MixJob Orchestrators (r:1 w:0)
+	/// Storage: MixJob Jobs (r:1 w:1)
+	/// Storage: MixJob TimeoutSchedule (r:2 w:2)
+	fn claim_job() -> Weight {
+		Weight::from_parts(30_000_000, 6_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: MixJob Orchestrators (r:1 w:0)
+	/// Storage: MixJob Jobs (r:1 w:1)
+	/// Storage: MixJob TimeoutSchedule (r:2 w:2)
+	fn renew_lease() -> Weight {
+		Weight::from_parts(30_000_000, 6_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+}
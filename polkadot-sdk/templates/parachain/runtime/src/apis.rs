@@ -46,9 +46,9 @@ use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-	AccountId, Balance, Block, ConsensusHook, Executive, InherentDataExt, Nonce, ParachainSystem,
-	Runtime, RuntimeCall, RuntimeGenesisConfig, SessionKeys, System, TransactionPayment,
-	SLOT_DURATION, VERSION,
+	AccountId, Balance, Block, BlockNumber, ConsensusHook, DaomixVoting, Executive, Hash,
+	InherentDataExt, MixJob, Nonce, ParachainSystem, Runtime, RuntimeCall, RuntimeGenesisConfig,
+	SessionKeys, System, TransactionPayment, SLOT_DURATION, VERSION,
 };
 
 // we move some impls outside so we can easily use them with `docify`.
@@ -226,6 +226,53 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_daomix_voting::runtime_api::DaoMixVotingApi<Block> for Runtime {
+		fn election_keys(
+			election_id: pallet_daomix_voting::ElectionId,
+		) -> Option<pallet_daomix_voting::ElectionKeys> {
+			DaomixVoting::election_keys(election_id)
+		}
+	}
+
+	impl pallet_daomix_voting::runtime_api::DaoMixApi<Block, AccountId, Hash, BlockNumber, Balance>
+		for Runtime
+	{
+		fn ballots(
+			election_id: pallet_daomix_voting::ElectionId,
+			start: pallet_daomix_voting::BallotIndex,
+			limit: u32,
+		) -> Vec<(pallet_daomix_voting::BallotIndex, pallet_daomix_voting::Ciphertext)> {
+			DaomixVoting::ballots_page(election_id, start, limit)
+		}
+
+		fn ballot_proof(
+			election_id: pallet_daomix_voting::ElectionId,
+			index: pallet_daomix_voting::BallotIndex,
+		) -> Option<pallet_daomix_voting::BallotProof> {
+			DaomixVoting::ballot_proof(election_id, index)
+		}
+
+		fn election_status(
+			election_id: pallet_daomix_voting::ElectionId,
+		) -> Option<pallet_daomix_voting::ElectionStatus<BlockNumber>> {
+			DaomixVoting::election_status(election_id)
+		}
+
+		fn jobs_by_status(
+			status: pallet_mix_job::JobStatus,
+			start: pallet_mix_job::JobId,
+			limit: u32,
+		) -> Vec<pallet_mix_job::MixJobInfo<AccountId, BlockNumber, Hash, Balance>> {
+			MixJob::jobs_with_status(status, start, limit)
+		}
+
+		fn jobs_for_election(
+			election_id: pallet_daomix_voting::ElectionId,
+		) -> Vec<pallet_mix_job::MixJobInfo<AccountId, BlockNumber, Hash, Balance>> {
+			MixJob::jobs_for_election(election_id)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
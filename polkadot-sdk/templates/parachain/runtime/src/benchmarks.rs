@@ -34,4 +34,6 @@ polkadot_sdk::frame_benchmarking::define_benchmarks!(
 	[cumulus_pallet_parachain_system, ParachainSystem]
 	[cumulus_pallet_xcmp_queue, XcmpQueue]
 	[cumulus_pallet_weight_reclaim, WeightReclaim]
+	[pallet_daomix_voting, DaomixVoting]
+	[pallet_mix_job, MixJob]
 );
@@ -124,20 +124,75 @@ parameter_types! {
 	pub const MaxAssetsIntoHolding: u32 = 64;
 }
 
-/// Allow MixJob pallet calls from sibling parachains via XCM
-pub struct AllowMixJobFromSiblings;
-impl xcm_executor::traits::Contains<(xcm::latest::Location, xcm::latest::Xcm<RuntimeCall>)> for AllowMixJobFromSiblings {
-	fn contains((origin, _xcm): &(xcm::latest::Location, xcm::latest::Xcm<RuntimeCall>)) -> bool {
-		// Only allow from sibling parachains (not relay chain or local)
-		let is_sibling_para = matches!(origin, xcm::latest::Location { parents: 1, interior: xcm::latest::Junctions::X1(_) });
-
-		if !is_sibling_para {
-			return false;
+/// Runtime calls that may be dispatched through XCM `Transact`.
+pub struct MixJobXcmCalls;
+impl frame_support::traits::Contains<RuntimeCall> for MixJobXcmCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(call, RuntimeCall::MixJob(pallet_mix_job::Call::submit_job { .. }))
+	}
+}
+
+/// Allows paid execution from sibling parachains only for programs of the exact form
+/// `WithdrawAsset`, `BuyExecution`, `Transact`, where the call is transacted with
+/// `OriginKind::Native` and is in `AllowedCalls`.
+///
+/// The native origin makes `submit_job` see the sibling itself rather than its sovereign
+/// account, so the allowlist and quotas apply.
+///
+/// A trailing `SetTopic` is stripped beforehand by `TrailingSetTopicAsId`.
+pub struct AllowMixJobFromSiblings<AllowedCalls>(core::marker::PhantomData<AllowedCalls>);
+impl<AllowedCalls: frame_support::traits::Contains<RuntimeCall>> xcm_executor::traits::ShouldExecute
+	for AllowMixJobFromSiblings<AllowedCalls>
+{
+	fn should_execute<Call>(
+		origin: &xcm::latest::Location,
+		instructions: &mut [xcm::latest::Instruction<Call>],
+		max_weight: xcm::latest::Weight,
+		_properties: &mut xcm_executor::traits::Properties,
+	) -> Result<(), frame_support::traits::ProcessMessageError> {
+		use frame_support::traits::ProcessMessageError;
+		use xcm::latest::{Instruction::*, Junction::Parachain, WeightLimit::*};
+
+		if !matches!(origin.unpack(), (1, [Parachain(_)])) {
+			return Err(ProcessMessageError::Unsupported);
 		}
 
-		// For now, allow any XCM from sibling parachains (can be made more restrictive later)
-		// In production, you would check for specific Transact instructions
-		true
+		let [
+			WithdrawAsset(..),
+			BuyExecution { weight_limit, .. },
+			Transact { origin_kind: xcm::latest::OriginKind::Native, call, .. },
+		] = instructions
+		else {
+			return Err(ProcessMessageError::BadFormat);
+		};
+
+		let enough_weight = match weight_limit {
+			Limited(weight) => weight.all_gte(max_weight),
+			Unlimited => true,
+		};
+		if !enough_weight {
+			return Err(ProcessMessageError::Overweight(max_weight));
+		}
+		*weight_limit = Limited(max_weight);
+
+		let call = call
+			.clone()
+			.into::<RuntimeCall>()
+			.try_into()
+			.map_err(|_| ProcessMessageError::BadFormat)?;
+		if !AllowedCalls::contains(&call) {
+			return Err(ProcessMessageError::Unsupported);
+		}
+
+		Ok(())
+	}
+}
+
+/// Sibling parachains and any location inside them.
+pub struct SiblingParachains;
+impl frame_support::traits::Contains<xcm::latest::Location> for SiblingParachains {
+	fn contains(location: &xcm::latest::Location) -> bool {
+		matches!(location.unpack(), (1, [xcm::latest::Junction::Parachain(_), ..]))
 	}
 }
 
@@ -153,9 +208,13 @@ pub type Barrier = xcm_builder::TrailingSetTopicAsId<
 		xcm_builder::DenyReserveTransferToRelayChain,
 		(
 			xcm_builder::TakeWeightCredit,
+			// Sibling parachains may only submit mix jobs.
+			AllowMixJobFromSiblings<MixJobXcmCalls>,
 			xcm_builder::WithComputedOrigin<
 				(
-					xcm_builder::AllowTopLevelPaidExecutionFrom<xcm_builder::Everything>,
+					xcm_builder::AllowTopLevelPaidExecutionFrom<
+						frame_support::traits::EverythingBut<SiblingParachains>,
+					>,
 					xcm_builder::AllowExplicitUnpaidExecutionFrom<ParentOrParentsExecutivePlurality>,
 					// ^^^ Parent and its exec plurality get free execution
 				),
@@ -193,7 +252,10 @@ impl xcm_executor::Config for XcmConfig {
 	type MessageExporter = ();
 	type UniversalAliases = xcm_builder::Nothing;
 	type CallDispatcher = RuntimeCall;
-	type SafeCallFilter = xcm_builder::Everything;
+	// Mix job submissions are the only calls this runtime takes over XCM: the barrier limits
+	// siblings to them, and no origin converted by `XcmOriginToTransactDispatchOrigin` is root,
+	// so the relay chain has nothing else to transact.
+	type SafeCallFilter = MixJobXcmCalls;
 	type Aliasers = xcm_builder::Nothing;
 	type TransactionalProcessor = xcm_builder::FrameTransactionalProcessor;
 	type HrmpNewChannelOpenRequestHandler = ();
@@ -349,8 +411,9 @@ impl pallet_xcm::Config for Runtime {
 	type SendXcmOrigin = xcm_builder::EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
 	type XcmRouter = XcmRouter;
 	type ExecuteXcmOrigin = xcm_builder::EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
-	type XcmExecuteFilter = AllowMixJobFromSiblings;
-	// ^ Allow MixJob pallet calls from sibling parachains for cross-chain mixing
+	type XcmExecuteFilter = xcm_builder::Nothing;
+	// ^ Disable dispatchable execute on the XCM pallet.
+	// Needs to be `Everything` for local testing.
 	type XcmExecutor = xcm_executor::XcmExecutor<XcmConfig>;
 	type XcmTeleportFilter = xcm_builder::Everything;
 	type XcmReserveTransferFilter = xcm_builder::Nothing;
@@ -207,7 +207,7 @@ export default function Home() {
                   <p><span className="text-[#9a9a9a]"> 4</span>  <span className="text-[#ff6b35]">const</span> <span className="text-white">message</span> <span className="text-white">=</span> {'{'}</p>
                   <p><span className="text-[#9a9a9a]"> 5</span>    <span className="text-white">V3:</span> [{'{'}</p>
                   <p><span className="text-[#9a9a9a]"> 6</span>      <span className="text-white">Transact:</span> {'{'}</p>
-                  <p><span className="text-[#9a9a9a]"> 7</span>        <span className="text-white">originKind:</span> <span className="text-[#fbbf24]">'SovereignAccount'</span>,</p>
+                  <p><span className="text-[#9a9a9a]"> 7</span>        <span className="text-white">originKind:</span> <span className="text-[#fbbf24]">'Native'</span>,</p>
                   <p><span className="text-[#9a9a9a]"> 8</span>        <span className="text-white">requireWeightAtMost:</span> {'{'} <span className="text-white">refTime:</span> <span className="text-[#fbbf24]">1000000000</span> {'}'},</p>
                   <p><span className="text-[#9a9a9a]"> 9</span>        <span className="text-white">call:</span> {'{'} <span className="text-white">encoded:</span> <span className="text-white">api</span>.<span className="text-white">tx</span>.<span className="text-white">mixJob</span>.<span className="text-white">submitJob</span>(<span className="text-white">electionId</span>).<span className="text-white">method</span>.<span className="text-[#ff6b35]">toHex</span>() {'}'}</p>
                   <p><span className="text-[#9a9a9a]">10</span>      {'}'}</p>
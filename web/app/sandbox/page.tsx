@@ -490,7 +490,9 @@ export default function Sandbox() {
         electionId,
         tallyAuthority,
         registrationDeadline,
-        votingDeadline
+        votingDeadline,
+        'RejectRevote',
+        'Mixnet'
       );
 
       addLog('daochain', `Preparing election ID: ${electionId}`, 'event');
@@ -620,10 +622,10 @@ export default function Sandbox() {
       const message = {
         V3: [{
           Transact: {
-            originKind: 'SovereignAccount',
+            originKind: 'Native',
             requireWeightAtMost: { refTime: 1000000000, proofSize: 64 * 1024 },
             call: {
-              encoded: votingchainApi.tx.mixJob.submitJob(xcmElectionId).method.toHex()
+              encoded: votingchainApi.tx.mixJob.submitJob({ Election: xcmElectionId }).method.toHex()
             }
           }
         }]
@@ -661,7 +663,7 @@ export default function Sandbox() {
         addLog('votingchain', `XCM message included in block ${blockHash.substring(0, 12)}...`, 'event');
         addLog('daochain', `📬 Expecting XCM message from Para 2001...`, 'event');
         addXcmTerminalLog(`[ws-proxy][VotingChain] included in block ${blockHash}`);
-        addXcmTerminalLog(`[dao-mixjob] mixJob.submitJob(${xcmElectionId}) dispatched`);
+        addXcmTerminalLog(`[dao-mixjob] mixJob.submitJob({ Election: ${xcmElectionId} }) dispatched`);
         addXcmTerminalLog('[mix-orchestrator] pending job queued (mix nodes 9000→9001→9002)');
       } catch (waitError) {
         console.warn('XCM inclusion wait error:', waitError);
@@ -851,7 +853,7 @@ export default function Sandbox() {
         const tally = election.tally_authority?.toString?.() || election.tallyAuthority?.toString?.() || '—';
         const commitmentIn = unwrapHex(election.commitment_input_root ?? election.commitmentInputRoot);
         const commitmentOut = unwrapHex(election.commitment_output_root ?? election.commitmentOutputRoot);
-        const finalized = Boolean(election.finalized);
+        const phase = election.phase?.toString?.() || '—';
 
         cards.push(`
           <div style="background: #f9fafb; border: 1px solid #e5e7eb; border-radius: 8px; padding: 12px 16px; margin-bottom: 12px;">
@@ -859,7 +861,7 @@ export default function Sandbox() {
             <div style="display: flex; flex-wrap: wrap; gap: 12px; font-size: 0.9em; color: #4b5563; line-height: 1.5;">
               <span><strong>Stage:</strong> ${describePhase(currentBlock, regDeadline, votingDeadline)}</span>
               <span><strong>Ballots:</strong> ${ballotCount}</span>
-              <span><strong>Finalized:</strong> ${finalized ? 'Yes' : 'No'}</span>
+              <span><strong>Phase:</strong> ${phase}</span>
             </div>
             <div style="display: flex; flex-wrap: wrap; gap: 12px; font-size: 0.9em; color: #4b5563; line-height: 1.5;">
               <span><strong>Admin:</strong> ${shortAddress(admin)}</span>